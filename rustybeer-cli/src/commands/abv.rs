@@ -1,4 +1,4 @@
-use rustybeer::calculators::abv::{calculate_abv, calculate_fg};
+use rustybeer::calculators::abv::{calculate_alcohol, calculate_fg, AbvFormula};
 use rustybeer::conversions::{RelativeDensity, RelativeDensityParser};
 use structopt::StructOpt;
 
@@ -17,11 +17,21 @@ pub struct AbvOptions {
     #[structopt(short, long, required_unless("fg"))]
     /// Alcohol by volume
     abv: Option<f64>,
+
+    #[structopt(long, default_value = "simple", possible_values = &["simple", "advanced", "balling", "abw"])]
+    /// Formula used to calculate ABV from the gravities
+    formula: AbvFormula,
 }
 
 pub fn calculate_and_print(abv_options: AbvOptions) {
     if let Some(fg) = abv_options.fg {
-        println!("ABV: {:.3}%", calculate_abv(&abv_options.og, &fg));
+        let result = calculate_alcohol(&abv_options.og, &fg, abv_options.formula);
+        println!("ABV: {:.3}%", result.abv);
+        println!("ABW: {:.3}%", result.abw);
+        println!("Apparent attenuation: {:.1}%", result.apparent_attenuation);
+        println!("Real attenuation: {:.1}%", result.real_attenuation);
+        println!("Original extract: {:.2}°P", result.original_extract);
+        println!("Real extract: {:.2}°P", result.real_extract);
     }
 
     if let Some(abv) = abv_options.abv {
@@ -1,59 +1,116 @@
+use anyhow::{anyhow, bail, Context, Result};
+use rustybeer::calculators::boil_off::calculate_boil_volumes;
 use rustybeer::calculators::diluting::{calculate_new_gravity, calculate_new_volume};
 use rustybeer::conversions::{RelativeDensity, RelativeDensityParser, ToMap, VolumeParser};
+use rustybeer::equipment::EquipmentProfile;
 use rustybeer::measurements::Volume;
+use std::fs;
+use std::path::{Path, PathBuf};
 use structopt::{clap::ArgGroup, StructOpt};
 
 #[derive(Debug, StructOpt)]
-#[structopt(name = "boil_off", group = ArgGroup::with_name("desired").required(true))]
+#[structopt(name = "boil-off", group = ArgGroup::with_name("desired"))]
 /// Calculates how much you need to dilute or boil down your wort volume to hit a certain gravity
 pub struct BoilOffOptions {
-    #[structopt(short, long, parse(try_from_str = VolumeParser::parse))]
+    #[structopt(short, long, required_unless = "equipment", parse(try_from_str = VolumeParser::parse))]
     /// Wort Volume
-    wort_volume: Volume,
+    wort_volume: Option<Volume>,
 
-    #[structopt(short, long, parse(try_from_str = RelativeDensityParser::parse))]
+    #[structopt(short, long, required_unless = "equipment", parse(try_from_str = RelativeDensityParser::parse))]
     /// Current Gravity
-    current_gravity: RelativeDensity,
+    current_gravity: Option<RelativeDensity>,
 
-    #[structopt(short, long, group = "desired", parse(try_from_str = RelativeDensityParser::parse))]
+    #[structopt(short, long, group = "desired", required_unless_one = &["target-volume", "equipment"], parse(try_from_str = RelativeDensityParser::parse))]
     /// Desired Gravity
     desired_gravity: Option<RelativeDensity>,
 
     #[structopt(short, long, group = "desired", parse(try_from_str = VolumeParser::parse))]
     /// Target Volume
     target_volume: Option<Volume>,
+
+    #[structopt(
+        short,
+        long,
+        conflicts_with_all = &["wort-volume", "current-gravity", "desired-gravity", "target-volume"],
+        parse(from_os_str)
+    )]
+    /// Equipment profile TOML file to calculate the pre-boil volume and gravity with
+    equipment: Option<PathBuf>,
+
+    #[structopt(short, long, requires = "equipment", parse(try_from_str = VolumeParser::parse))]
+    /// Packaged Volume, used with equipment profile
+    packaged_volume: Option<Volume>,
+
+    #[structopt(short, long, requires = "equipment", parse(try_from_str = RelativeDensityParser::parse))]
+    /// Original Gravity, used with equipment profile
+    og: Option<RelativeDensity>,
+
+    #[structopt(short, long, default_value = "60")]
+    /// Boil Time in minutes, used with equipment profile
+    boil_time: u32,
+}
+
+/// Reads an equipment profile from a TOML file and checks its values
+pub fn read_equipment(path: &Path) -> Result<EquipmentProfile> {
+    let input = fs::read_to_string(path)
+        .with_context(|| format!("could not read file {}", path.display()))?;
+    let equipment: EquipmentProfile = toml::from_str(&input)
+        .with_context(|| format!("could not parse equipment profile {}", path.display()))?;
+    equipment
+        .validate()
+        .map_err(|error| anyhow!("invalid equipment profile {}: {}", path.display(), error))?;
+    Ok(equipment)
+}
+
+fn print_equipment_volumes(
+    path: &Path,
+    packaged_volume: &Volume,
+    og: &RelativeDensity,
+    boil_time: u32,
+) -> Result<()> {
+    let equipment = read_equipment(path)?;
+    let volumes = calculate_boil_volumes(packaged_volume, og, boil_time, &equipment);
+    println!("Equipment: {}", equipment.name);
+    println!("Pre-boil Volume: {:#?}", volumes.pre_boil.to_map());
+    println!("Pre-boil Gravity: {:#?}", volumes.pre_boil_gravity.to_map());
+    println!("Post-boil Volume: {:#?}", volumes.post_boil.to_map());
+    println!("Into Fermenter: {:#?}", volumes.into_fermenter.to_map());
+    Ok(())
 }
 
-pub fn calculate_and_print(boil_off_options: BoilOffOptions) {
-    println!("Wort Volume: {:#?}", boil_off_options.wort_volume.to_map());
-    println!(
-        "Current Gravity: {:#?}",
-        boil_off_options.current_gravity.to_map()
-    );
+pub fn calculate_and_print(boil_off_options: BoilOffOptions) -> Result<()> {
+    if let Some(path) = &boil_off_options.equipment {
+        let (Some(packaged_volume), Some(og)) =
+            (boil_off_options.packaged_volume, boil_off_options.og)
+        else {
+            bail!("--equipment needs --packaged-volume and --og");
+        };
+        return print_equipment_volumes(path, &packaged_volume, &og, boil_off_options.boil_time);
+    }
+
+    let (Some(wort_volume), Some(current_gravity)) = (
+        boil_off_options.wort_volume,
+        boil_off_options.current_gravity,
+    ) else {
+        bail!("--wort-volume and --current-gravity are needed without --equipment");
+    };
+    println!("Wort Volume: {:#?}", wort_volume.to_map());
+    println!("Current Gravity: {:#?}", current_gravity.to_map());
 
     if let Some(desired_gravity) = boil_off_options.desired_gravity {
-        let new_volume = calculate_new_volume(
-            &boil_off_options.current_gravity,
-            &boil_off_options.wort_volume,
-            &desired_gravity,
-        );
+        let new_volume = calculate_new_volume(&current_gravity, &wort_volume, &desired_gravity);
         println!("New Volume: {:#?}", new_volume.to_map());
-        println!(
-            "Difference: {:#?}",
-            (new_volume - boil_off_options.wort_volume).to_map()
-        );
+        println!("Difference: {:#?}", (new_volume - wort_volume).to_map());
     }
 
     if let Some(target_volume) = boil_off_options.target_volume {
-        let new_gravity = calculate_new_gravity(
-            &boil_off_options.current_gravity,
-            &boil_off_options.wort_volume,
-            &target_volume,
-        );
+        let new_gravity = calculate_new_gravity(&current_gravity, &wort_volume, &target_volume);
         println!("New Gravity: {:#?}", new_gravity.to_map());
         println!(
             "Difference: {:#?}",
-            (new_gravity - boil_off_options.current_gravity).to_map()
+            (new_gravity - current_gravity).to_map()
         );
     }
+
+    Ok(())
 }
@@ -0,0 +1,60 @@
+use rustybeer::calculators::bottle_pressure::{calculate_bottle_pressure, BottleType};
+use rustybeer::conversions::{RelativeDensity, RelativeDensityParser, TemperatureParser};
+use rustybeer::measurements::Temperature;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "bottle-pressure")]
+/// Estimates the pressure in bottle conditioned beer and warns if it exceeds the bottle rating
+pub struct BottlePressureOptions {
+    #[structopt(short, long = "co2_volumes")]
+    /// Volumes of CO2 the beer was primed for
+    co2_volumes: f64,
+
+    #[structopt(short, long, parse(try_from_str = TemperatureParser::parse))]
+    /// Temperature of the beer at bottling with unit (C, F, K). Defaults to Celsius.
+    temp: Temperature,
+
+    #[structopt(short, long, parse(try_from_str = RelativeDensityParser::parse))]
+    /// Gravity of the beer at bottling
+    gravity: RelativeDensity,
+
+    #[structopt(short, long, parse(try_from_str = RelativeDensityParser::parse))]
+    /// Expected final gravity, defaults to the bottling gravity
+    fg: Option<RelativeDensity>,
+
+    #[structopt(short, long, default_value = "20C", parse(try_from_str = TemperatureParser::parse))]
+    /// Temperature the bottles are stored at
+    storage_temp: Temperature,
+
+    #[structopt(short, long, default_value = "standard", possible_values = &["standard", "belgian", "champagne"])]
+    /// Type of the bottles
+    bottle: BottleType,
+}
+
+pub fn calculate_and_print(bottle_options: BottlePressureOptions) {
+    let bottle = calculate_bottle_pressure(
+        bottle_options.co2_volumes,
+        &bottle_options.temp,
+        &bottle_options.gravity,
+        &bottle_options.fg.unwrap_or(bottle_options.gravity),
+        &bottle_options.storage_temp,
+        bottle_options.bottle,
+    );
+
+    println!("Volumes of CO2: {:.2}", bottle.co2_volumes);
+    println!(
+        "Bottle pressure: {:.1} psi ({:.2} bar)",
+        bottle.pressure.as_psi(),
+        bottle.pressure.as_bars()
+    );
+    println!(
+        "{} bottle rating: {:.1} psi ({:.2} bar)",
+        bottle_options.bottle,
+        bottle.rated_pressure.as_psi(),
+        bottle.rated_pressure.as_bars()
+    );
+    if bottle.exceeds_rating() {
+        println!("Warning: pressure exceeds the bottle rating, bottles may gush or burst!");
+    }
+}
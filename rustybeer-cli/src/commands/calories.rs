@@ -33,9 +33,7 @@ pub struct CaloriesOptions {
 
 pub fn calculate_and_print(calories: CaloriesOptions) {
     let conversion = MassParser::parse("12oz").unwrap().as_grams();
-    if calories.og.is_some() && calories.fg.is_some() {
-        let fg = calories.fg.unwrap();
-        let og = calories.og.unwrap();
+    if let (Some(og), Some(fg)) = (calories.og, calories.fg) {
         if let Some(volume) = calories.volume {
             let volume = volume.as_milliliters();
             let ac = calculate_alcohol_calories(&og, &fg) / conversion * volume;
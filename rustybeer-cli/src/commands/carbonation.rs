@@ -0,0 +1,98 @@
+use rustybeer::calculators::carbonation::{
+    calculate_co2_volumes, calculate_keg_pressure, calculate_line_length, line_resistance,
+};
+use rustybeer::conversions::{LengthParser, PressureParser, TemperatureParser};
+use rustybeer::measurements::{Length, Pressure, Temperature};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "carbonation")]
+/// Keg carbonation calculators
+pub enum CarbonationOptions {
+    /// Calculates the regulator pressure for the wanted volumes of CO2
+    Pressure(PressureOptions),
+    /// Calculates the volumes of CO2 reached with the regulator pressure
+    Volumes(VolumesOptions),
+    /// Calculates the serving line length to balance the keg pressure
+    Line(LineOptions),
+}
+
+#[derive(Debug, StructOpt)]
+pub struct PressureOptions {
+    #[structopt(short, long, parse(try_from_str = TemperatureParser::parse))]
+    /// Temperature of the beer with unit (C, F, K). Defaults to Celsius.
+    temp: Temperature,
+
+    #[structopt(short, long)]
+    /// Wanted volumes of CO2
+    co2_volumes: f64,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct VolumesOptions {
+    #[structopt(short, long, parse(try_from_str = TemperatureParser::parse))]
+    /// Temperature of the beer with unit (C, F, K). Defaults to Celsius.
+    temp: Temperature,
+
+    #[structopt(short, long, parse(try_from_str = PressureParser::parse))]
+    /// Regulator pressure with unit (psi, bar, kPa). Defaults to psi.
+    pressure: Pressure,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct LineOptions {
+    #[structopt(short, long, parse(try_from_str = PressureParser::parse))]
+    /// Regulator pressure with unit (psi, bar, kPa). Defaults to psi.
+    pressure: Pressure,
+
+    #[structopt(short, long, default_value = "0", parse(try_from_str = LengthParser::parse))]
+    /// Height of the tap above the keg with unit (m, cm, ft, in). Defaults to metres.
+    tap_height: Length,
+
+    #[structopt(short, long, default_value = "0.1875in", parse(try_from_str = LengthParser::parse))]
+    /// Inner diameter of the vinyl line with unit (mm, in)
+    inner_diameter: Length,
+
+    #[structopt(short, long)]
+    /// Resistance of the line in psi per foot, looked up by the inner diameter if not given
+    resistance: Option<f64>,
+}
+
+pub fn calculate_and_print(carbonation_options: CarbonationOptions) {
+    match carbonation_options {
+        CarbonationOptions::Pressure(options) => {
+            let pressure = calculate_keg_pressure(&options.temp, options.co2_volumes);
+            println!(
+                "Regulator pressure: {:.1} psi ({:.2} bar)",
+                pressure.as_psi(),
+                pressure.as_bars()
+            );
+        }
+        CarbonationOptions::Volumes(options) => {
+            println!(
+                "Volumes of CO2: {:.2}",
+                calculate_co2_volumes(&options.temp, &options.pressure)
+            );
+        }
+        CarbonationOptions::Line(options) => {
+            let resistance = match options
+                .resistance
+                .or_else(|| line_resistance(&options.inner_diameter))
+            {
+                Some(resistance) => resistance,
+                None => {
+                    println!("Unknown line diameter, give the line resistance.");
+                    return;
+                }
+            };
+            match calculate_line_length(&options.pressure, &options.tap_height, resistance) {
+                Some(length) => println!(
+                    "Line length: {:.2} m ({:.1} ft)",
+                    length.as_metres(),
+                    length.as_feet()
+                ),
+                None => println!("Line resistance must be above zero."),
+            }
+        }
+    }
+}
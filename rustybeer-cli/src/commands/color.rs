@@ -0,0 +1,43 @@
+use rustybeer::calculators::color::{
+    calculate_mcu, calculate_srm, srm_to_ebc, srm_to_hex, srm_to_lovibond, ColorModel,
+};
+use rustybeer::conversions::{MassParser, VolumeParser};
+use rustybeer::measurements::{Mass, Volume};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "color")]
+/// Calculates the color of the beer from the grain bill
+pub struct ColorOptions {
+    #[structopt(short, long, parse(try_from_str = VolumeParser::parse))]
+    /// Volume of the wort
+    volume: Volume,
+
+    #[structopt(short, long, required = true, parse(try_from_str = parse_grain))]
+    /// Grain weight and color in degrees Lovibond separated by colon, for example 4.5kg:2
+    grain: Vec<(Mass, f64)>,
+
+    #[structopt(short, long, default_value = "morey", possible_values = &["morey", "daniels", "mosher"])]
+    /// Model to convert malt color units to SRM
+    model: ColorModel,
+}
+
+fn parse_grain(grain: &str) -> Result<(Mass, f64), String> {
+    let (weight, color) = grain
+        .split_once(':')
+        .ok_or_else(|| format!("expected <weight>:<color>, got {}", grain))?;
+    let weight = MassParser::parse(weight).map_err(|e| e.to_string())?;
+    let color = color.trim().parse::<f64>().map_err(|e| e.to_string())?;
+    Ok((weight, color))
+}
+
+pub fn calculate_and_print(color_options: ColorOptions) {
+    let mcu = calculate_mcu(&color_options.grain, &color_options.volume);
+    let srm = calculate_srm(mcu, color_options.model);
+
+    println!("MCU: {:.1}", mcu);
+    println!("SRM ({}): {:.1}", color_options.model, srm);
+    println!("EBC: {:.1}", srm_to_ebc(srm));
+    println!("Lovibond: {:.1}", srm_to_lovibond(srm));
+    println!("Color: {}", srm_to_hex(srm));
+}
@@ -0,0 +1,53 @@
+use rustybeer::calculators::mash::{calculate_decoction_schedule, DecoctionSchedule, MashStep};
+use rustybeer::conversions::{TemperatureParser, VolumeParser};
+use rustybeer::measurements::{Temperature, Volume};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "decoction")]
+/// Calculates the portion of the mash to pull for each decoction
+pub struct DecoctionOptions {
+    #[structopt(short, long, parse(try_from_str = VolumeParser::parse))]
+    /// Total volume of the mash
+    volume: Volume,
+
+    #[structopt(short, long, default_value = "single", possible_values = &["single", "double", "triple"])]
+    /// Traditional decoction schedule, ignored if rests are given
+    schedule: DecoctionSchedule,
+
+    #[structopt(short, long, requires = "rest", parse(try_from_str = TemperatureParser::parse))]
+    /// Current temperature of the mash
+    temp: Option<Temperature>,
+
+    #[structopt(short, long, requires = "temp", parse(try_from_str = TemperatureParser::parse))]
+    /// Target rest temperature, can be given multiple times
+    rest: Vec<Temperature>,
+}
+
+pub fn calculate_and_print(decoction_options: DecoctionOptions) {
+    let steps = match decoction_options.temp {
+        Some(temp) => std::iter::once(temp)
+            .chain(decoction_options.rest)
+            .enumerate()
+            .map(|(i, temperature)| MashStep::new(format!("Rest {}", i + 1), temperature, 0))
+            .collect(),
+        None => decoction_options.schedule.steps(),
+    };
+
+    match calculate_decoction_schedule(&decoction_options.volume, &steps) {
+        Ok(decoctions) => {
+            println!("Dough in: {:.1}°C", steps[0].temperature.as_celsius());
+            for (i, decoction) in decoctions.iter().enumerate() {
+                println!(
+                    "Decoction {}: pull {:.2} l ({:.0}%) to reach {:.1}°C {}",
+                    i + 1,
+                    decoction.volume.as_litres(),
+                    decoction.fraction * 100.,
+                    decoction.step.temperature.as_celsius(),
+                    decoction.step.name,
+                );
+            }
+        }
+        Err(e) => println!("Invalid decoction schedule: {}", e),
+    }
+}
@@ -0,0 +1,162 @@
+use crate::commands::parsers::{parse_fermentable_with, parse_hop_with};
+use anyhow::{anyhow, Context, Result};
+use rustybeer::beer_styles::find_beer_style;
+use rustybeer::calculators::boil_off::calculate_boil_volumes;
+use rustybeer::calculators::ibu::{HopAddition, HopUse};
+use rustybeer::calculators::og::FermentableAddition;
+use rustybeer::conversions::{RelativeDensity, VolumeParser};
+use rustybeer::designer::{design_recipe, StyleFit};
+use rustybeer::equipment::EquipmentProfile;
+use rustybeer::measurements::{Mass, Volume};
+use std::fs;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "design")]
+/// Designs a recipe in the middle of the OG, IBU and SRM ranges of a beer style
+pub struct DesignOptions {
+    #[structopt(short, long)]
+    /// Name of the beer style, for example "American Pale Ale"
+    style: String,
+
+    #[structopt(short, long, required = true, parse(try_from_str = parse_grain_share))]
+    /// Percentage and name of a fermentable separated by colon, for example "90%:Maris Otter"
+    grain: Vec<FermentableAddition>,
+
+    #[structopt(long, parse(try_from_str = parse_hop_share))]
+    /// Hop addition as percentage of the hop weight, alpha acid, time and optional type and use, for example 60%@6.4%@60min@pellet or 40%@6.4%@4d@dry-hop
+    hop: Vec<HopAddition>,
+
+    #[structopt(short, long, default_value = "20", parse(try_from_str = VolumeParser::parse))]
+    /// Volume of wort into the fermenter
+    volume: Volume,
+
+    #[structopt(short, long, parse(try_from_str = VolumeParser::parse))]
+    /// Volume of wort at the start of the boil. Defaults to the pre-boil volume of a typical home brewing system.
+    boil_size: Option<Volume>,
+
+    #[structopt(short, long, default_value = "72")]
+    /// Brewhouse efficiency in percentage
+    efficiency: f64,
+
+    #[structopt(short, long)]
+    /// Name of the recipe, defaults to the style name
+    name: Option<String>,
+
+    #[structopt(short, long, parse(from_os_str))]
+    /// File to write the recipe JSON to
+    output: Option<PathBuf>,
+}
+
+fn parse_percentage(percentage: &str) -> Result<f64, String> {
+    percentage
+        .trim()
+        .trim_end_matches('%')
+        .parse::<f64>()
+        .map_err(|e| e.to_string())
+}
+
+fn parse_grain_share(grain: &str) -> Result<FermentableAddition, String> {
+    parse_fermentable_with(grain, "<percentage>%", |percentage| {
+        parse_percentage(percentage).map(|percentage| Mass::from_kilograms(percentage / 100.))
+    })
+}
+
+fn parse_hop_share(hop: &str) -> Result<HopAddition, String> {
+    parse_hop_with(hop, "<percentage>%", |percentage| {
+        parse_percentage(percentage).map(|percentage| Mass::from_grams(percentage / 100.))
+    })
+}
+
+fn print_fit(name: &str, fit: &StyleFit, precision: usize) {
+    let position = if fit.value < fit.min {
+        String::from("below the range")
+    } else if fit.value > fit.max {
+        String::from("above the range")
+    } else {
+        format!("{:.0}% of the range", fit.position() * 100.)
+    };
+    println!(
+        "{}: {:.*} ({:.*}-{:.*}, {})",
+        name, precision, fit.value, precision, fit.min, precision, fit.max, position
+    );
+}
+
+pub fn design_and_print(design_options: DesignOptions) -> Result<()> {
+    let style = find_beer_style(&design_options.style)
+        .ok_or_else(|| anyhow!("unknown beer style {}", design_options.style))?;
+    let equipment = EquipmentProfile::default();
+    let boil_size = design_options.boil_size.unwrap_or_else(|| {
+        calculate_boil_volumes(
+            &(design_options.volume - equipment.fermenter_loss),
+            &RelativeDensity::from_specific_gravity(1.),
+            60,
+            &equipment,
+        )
+        .pre_boil
+    });
+
+    let design = design_recipe(
+        design_options.name.unwrap_or_else(|| style.name.clone()),
+        style,
+        &design_options.grain,
+        &design_options.hop,
+        &design_options.volume,
+        &boil_size,
+        design_options.efficiency / 100.,
+    )?;
+    let recipe = &design.recipe;
+
+    println!(
+        "{} ({:.1} l, {:.1} l boil, {:.0}% efficiency)",
+        recipe.name,
+        recipe.batch_size.as_litres(),
+        recipe.boil_size.as_litres(),
+        recipe.efficiency * 100.
+    );
+    for fermentable in &recipe.fermentables {
+        println!(
+            "  {}: {:.2} kg",
+            fermentable.name,
+            fermentable.weight.as_kilograms()
+        );
+    }
+    for hop in &recipe.hops {
+        let timing = match hop.hop_use {
+            HopUse::Boil => format!("{} min", hop.time_mins),
+            HopUse::FirstWort => format!("first wort, {} min", hop.time_mins),
+            HopUse::Mash { time_mins } => format!("mash, {} min", time_mins),
+            HopUse::Whirlpool {
+                temperature,
+                time_mins,
+            } => format!(
+                "whirlpool at {:.0} °C, {} min",
+                temperature.as_celsius(),
+                time_mins
+            ),
+            HopUse::DryHop { days } => format!("dry hop, {} days", days),
+        };
+        println!(
+            "  {:.1} g @ {:.1}% AA, {}",
+            hop.weight.as_grams(),
+            hop.alpha_acid_percentage * 100.,
+            timing
+        );
+    }
+
+    println!("{}:", style.name);
+    print_fit("OG", &design.og, 3);
+    print_fit("FG", &design.fg, 3);
+    print_fit("ABV", &design.abv, 1);
+    print_fit("IBU", &design.ibu, 0);
+    print_fit("SRM", &design.color, 1);
+
+    if let Some(output) = design_options.output {
+        fs::write(&output, serde_json::to_string_pretty(recipe)?)
+            .with_context(|| format!("could not write {}", output.display()))?;
+        println!("Recipe written to {}", output.display());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use crate::commands::parsers::parse_fermentable;
+use rustybeer::calculators::efficiency::{calculate_efficiency, GravityReading};
+use rustybeer::calculators::og::FermentableAddition;
+use rustybeer::conversions::{RelativeDensity, RelativeDensityParser, VolumeParser};
+use rustybeer::measurements::Volume;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "efficiency")]
+/// Calculates the mash, brewhouse and overall efficiency from measured volumes and gravities
+pub struct EfficiencyOptions {
+    #[structopt(short, long, required = true, parse(try_from_str = parse_fermentable))]
+    /// Weight and name of a fermentable separated by colon, for example "4.5kg:Maris Otter"
+    grain: Vec<FermentableAddition>,
+
+    #[structopt(long, parse(try_from_str = VolumeParser::parse))]
+    /// Volume of the wort before the boil
+    pre_boil_volume: Volume,
+
+    #[structopt(long, parse(try_from_str = RelativeDensityParser::parse))]
+    /// Gravity of the wort before the boil
+    pre_boil_gravity: RelativeDensity,
+
+    #[structopt(long, parse(try_from_str = VolumeParser::parse))]
+    /// Volume of the wort after the boil
+    post_boil_volume: Volume,
+
+    #[structopt(long, parse(try_from_str = RelativeDensityParser::parse))]
+    /// Gravity of the wort after the boil
+    post_boil_gravity: RelativeDensity,
+
+    #[structopt(short = "f", long, parse(try_from_str = VolumeParser::parse))]
+    /// Volume of the wort going into the fermenter
+    into_fermenter: Volume,
+
+    #[structopt(long, requires = "first-runnings", parse(try_from_str = VolumeParser::parse))]
+    /// Volume of the mash water, needed for the conversion and lauter efficiency
+    mash_water: Option<Volume>,
+
+    #[structopt(long, requires = "mash-water", parse(try_from_str = RelativeDensityParser::parse))]
+    /// Gravity of the first runnings, needed for the conversion and lauter efficiency
+    first_runnings: Option<RelativeDensity>,
+}
+
+pub fn calculate_and_print(efficiency_options: EfficiencyOptions) {
+    let first_runnings = efficiency_options
+        .mash_water
+        .zip(efficiency_options.first_runnings)
+        .map(|(volume, gravity)| GravityReading::new(volume, gravity));
+
+    match calculate_efficiency(
+        &efficiency_options.grain,
+        &GravityReading::new(
+            efficiency_options.pre_boil_volume,
+            efficiency_options.pre_boil_gravity,
+        ),
+        &GravityReading::new(
+            efficiency_options.post_boil_volume,
+            efficiency_options.post_boil_gravity,
+        ),
+        &efficiency_options.into_fermenter,
+        first_runnings,
+    ) {
+        Some(efficiency) => {
+            if let Some(conversion) = efficiency.conversion {
+                println!("Conversion efficiency: {:.1} %", conversion * 100.);
+            }
+            if let Some(lauter) = efficiency.lauter {
+                println!("Lauter efficiency: {:.1} %", lauter * 100.);
+            }
+            println!("Mash efficiency: {:.1} %", efficiency.mash * 100.);
+            println!("Brewhouse efficiency: {:.1} %", efficiency.brewhouse * 100.);
+            println!("Overall efficiency: {:.1} %", efficiency.overall * 100.);
+        }
+        None => println!("No mashed fermentables in the grain bill"),
+    }
+}
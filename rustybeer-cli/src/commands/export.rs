@@ -0,0 +1,48 @@
+use super::import::RecipeFormat;
+use anyhow::{Context, Result};
+use rustybeer::recipe::Recipe;
+use std::fs;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "export")]
+/// Exports rustybeer recipe JSON as BeerXML or BeerJSON
+pub struct ExportOptions {
+    #[structopt(parse(from_os_str))]
+    /// Recipe JSON file containing a recipe or a list of recipes
+    input: PathBuf,
+
+    #[structopt(short, long, parse(from_os_str))]
+    /// File to write the exported recipes to. Defaults to standard output.
+    output: Option<PathBuf>,
+
+    #[structopt(short, long, default_value = "beerxml", possible_values = RecipeFormat::VALUES)]
+    /// Format of the output file
+    format: RecipeFormat,
+}
+
+pub fn export_and_print(export_options: ExportOptions) -> Result<()> {
+    let json = fs::read_to_string(&export_options.input)
+        .with_context(|| format!("could not read {}", export_options.input.display()))?;
+    let recipes: Vec<Recipe> = match serde_json::from_str(&json) {
+        Ok(recipes) => recipes,
+        Err(_) => vec![serde_json::from_str(&json).with_context(|| "invalid recipe JSON")?],
+    };
+    let exported = export_options.format.write(&recipes)?;
+
+    match export_options.output {
+        Some(output) => {
+            fs::write(&output, exported)
+                .with_context(|| format!("could not write {}", output.display()))?;
+            println!(
+                "Exported {} recipe(s) to {}",
+                recipes.len(),
+                output.display()
+            );
+        }
+        None => println!("{}", exported),
+    }
+
+    Ok(())
+}
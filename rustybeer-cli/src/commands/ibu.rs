@@ -0,0 +1,76 @@
+use crate::commands::parsers::parse_hop;
+use rustybeer::calculators::ibu::{
+    calculate_bittering_weight, calculate_ibu, HopAddition, IbuModel,
+};
+use rustybeer::conversions::{RelativeDensity, RelativeDensityParser, VolumeParser};
+use rustybeer::measurements::Volume;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "ibu")]
+/// Calculates IBU of hop additions or the bittering hop weight needed for a target IBU
+pub struct IbuOptions {
+    #[structopt(short, long, parse(try_from_str = VolumeParser::parse))]
+    /// Volume of the finished wort
+    volume: Volume,
+
+    #[structopt(short, long, parse(try_from_str = RelativeDensityParser::parse))]
+    /// Gravity of the wort during the boil
+    gravity: RelativeDensity,
+
+    #[structopt(long, required_unless("target-ibu"), parse(try_from_str = parse_hop))]
+    /// Hop addition as weight, alpha acid, time and optional type and use (boil, first-wort, mash, whirlpool:<temperature>, dry-hop with time in days), for example 28g@6.4%@60min@pellet or 20g@6.4%@20min@whirlpool:80C
+    hop: Vec<HopAddition>,
+
+    #[structopt(short, long, default_value = "tinseth", possible_values = &["tinseth", "rager", "garetz", "daniels", "mibu"])]
+    /// Model to calculate the hop utilization with
+    model: IbuModel,
+
+    #[structopt(short, long, requires("alpha-acid"))]
+    /// Target IBU to calculate the bittering hop weight for, other hop additions count towards it
+    target_ibu: Option<f64>,
+
+    #[structopt(short, long)]
+    /// Alpha acid percentage of the bittering hop
+    alpha_acid: Option<f64>,
+
+    #[structopt(long)]
+    /// Boil time of the bittering hop in minutes, defaults to 60
+    time: Option<u32>,
+}
+
+pub fn calculate_and_print(ibu_options: IbuOptions) {
+    let (Some(target_ibu), Some(alpha_acid)) = (ibu_options.target_ibu, ibu_options.alpha_acid)
+    else {
+        println!(
+            "IBU: {:.1}",
+            calculate_ibu(
+                ibu_options.hop,
+                &ibu_options.volume,
+                &ibu_options.gravity,
+                &ibu_options.model
+            )
+        );
+        return;
+    };
+
+    let hops = match ibu_options.hop.is_empty() {
+        true => None,
+        false => Some(ibu_options.hop),
+    };
+    match calculate_bittering_weight(
+        hops,
+        alpha_acid / 100.,
+        ibu_options.time,
+        &ibu_options.volume,
+        &ibu_options.gravity,
+        target_ibu,
+        &ibu_options.model,
+    ) {
+        Ok(weight) => println!("Bittering hop weight: {:.1} g", weight),
+        Err(_) => println!(
+            "Other hop additions already exceed the target of {} IBU",
+            target_ibu
+        ),
+    }
+}
@@ -0,0 +1,91 @@
+use anyhow::{Context, Result};
+use rustybeer::recipe::Recipe;
+use rustybeer::{beerjson, beerxml};
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+use structopt::StructOpt;
+
+/// Recipe file format used by import and export
+#[derive(Debug, Clone, Copy)]
+pub enum RecipeFormat {
+    BeerXml,
+    BeerJson,
+}
+
+impl RecipeFormat {
+    pub const VALUES: &'static [&'static str] = &["beerxml", "beerjson"];
+
+    pub fn read(&self, input: &[u8]) -> Result<Vec<Recipe>> {
+        Ok(match self {
+            RecipeFormat::BeerXml => beerxml::from_bytes(input)?,
+            RecipeFormat::BeerJson => beerjson::from_str(
+                std::str::from_utf8(input).with_context(|| "BeerJSON must be UTF-8 encoded")?,
+            )?,
+        })
+    }
+
+    pub fn write(&self, recipes: &[Recipe]) -> Result<String> {
+        Ok(match self {
+            RecipeFormat::BeerXml => beerxml::to_string(recipes)?,
+            RecipeFormat::BeerJson => beerjson::to_string(recipes)?,
+        })
+    }
+}
+
+impl FromStr for RecipeFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "beerxml" => Ok(RecipeFormat::BeerXml),
+            "beerjson" => Ok(RecipeFormat::BeerJson),
+            _ => Err(format!("unknown recipe format {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "import")]
+/// Imports recipes from BeerXML or BeerJSON file as rustybeer recipe JSON
+pub struct ImportOptions {
+    #[structopt(parse(from_os_str))]
+    /// BeerXML or BeerJSON file to import
+    input: PathBuf,
+
+    #[structopt(short, long, default_value = "beerxml", possible_values = RecipeFormat::VALUES)]
+    /// Format of the input file
+    format: RecipeFormat,
+
+    #[structopt(short, long, parse(from_os_str))]
+    /// File to write the recipe JSON to. Defaults to standard output.
+    output: Option<PathBuf>,
+}
+
+pub fn import_and_print(import_options: ImportOptions) -> Result<()> {
+    let bytes = fs::read(&import_options.input)
+        .with_context(|| format!("could not read {}", import_options.input.display()))?;
+    let recipes = import_options.format.read(&bytes)?;
+    let json = serde_json::to_string_pretty(&recipes)?;
+
+    match import_options.output {
+        Some(output) => {
+            fs::write(&output, json)
+                .with_context(|| format!("could not write {}", output.display()))?;
+            for recipe in &recipes {
+                println!(
+                    "Imported {}: OG {:.3}, FG {:.3}, ABV {:.1}%, IBU {:.0}, SRM {:.1}",
+                    recipe.name,
+                    recipe.og().as_specific_gravity(),
+                    recipe.fg().as_specific_gravity(),
+                    recipe.abv(),
+                    recipe.ibu(),
+                    recipe.color()
+                );
+            }
+        }
+        None => println!("{}", json),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use rustybeer::calculators::mash::{calculate_mash_schedule, MashStep};
+use rustybeer::conversions::{MassParser, TemperatureParser};
+use rustybeer::measurements::{Mass, Temperature};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "mash")]
+/// Calculates strike water and boiling water infusions for a step mash
+pub struct MashOptions {
+    #[structopt(short, long, parse(try_from_str = MassParser::parse))]
+    /// Weight of the grain
+    grain: Mass,
+
+    #[structopt(long, default_value = "20C", parse(try_from_str = TemperatureParser::parse))]
+    /// Temperature of the grain
+    grain_temp: Temperature,
+
+    #[structopt(short, long, default_value = "3")]
+    /// Water-to-grist ratio of the strike water (l/kg)
+    ratio: f64,
+
+    #[structopt(short, long, required = true, parse(try_from_str = parse_step))]
+    /// Rest temperature and time separated by @, for example 66C@60min
+    step: Vec<MashStep>,
+}
+
+fn parse_step(step: &str) -> Result<MashStep, String> {
+    let (temperature, time) = step
+        .split_once('@')
+        .ok_or_else(|| format!("expected <temperature>@<time>, got {}", step))?;
+    let temperature = TemperatureParser::parse(temperature).map_err(|e| e.to_string())?;
+    let time_mins = time
+        .trim()
+        .trim_end_matches("min")
+        .parse::<u32>()
+        .map_err(|e| e.to_string())?;
+    Ok(MashStep::new(step.to_owned(), temperature, time_mins))
+}
+
+pub fn calculate_and_print(mash_options: MashOptions) {
+    match calculate_mash_schedule(
+        &mash_options.grain,
+        &mash_options.grain_temp,
+        mash_options.ratio,
+        &mash_options.step,
+    ) {
+        Ok(schedule) => {
+            println!(
+                "{:>4} {:>10} {:>8} {:>12} {:>10} {:>12}",
+                "Step", "Rest", "Time", "Water temp", "Water", "Total water"
+            );
+            for (i, infusion) in schedule.iter().enumerate() {
+                println!(
+                    "{:>4} {:>8.1}°C {:>4} min {:>10.1}°C {:>8.2} l {:>10.2} l",
+                    i + 1,
+                    infusion.step.temperature.as_celsius(),
+                    infusion.step.time_mins,
+                    infusion.water_temperature.as_celsius(),
+                    infusion.volume.as_litres(),
+                    infusion.total_volume.as_litres(),
+                );
+            }
+        }
+        Err(e) => println!("Invalid mash schedule: {}", e),
+    }
+}
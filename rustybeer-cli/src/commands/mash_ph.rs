@@ -0,0 +1,68 @@
+use crate::commands::parsers::parse_fermentable;
+use rustybeer::calculators::mash_ph::{calculate_acid_additions, calculate_mash_ph};
+use rustybeer::calculators::og::FermentableAddition;
+use rustybeer::conversions::VolumeParser;
+use rustybeer::measurements::Volume;
+use rustybeer::water::WaterProfile;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "mash-ph")]
+/// Estimates the mash pH and the acid needed to reach a target pH
+pub struct MashPhOptions {
+    #[structopt(short, long, parse(try_from_str = VolumeParser::parse))]
+    /// Volume of the mash water
+    volume: Volume,
+
+    #[structopt(short, long, default_value = "Distilled")]
+    /// Mash water as profile name or comma separated ppm of Ca,Mg,Na,Cl,SO4,HCO3
+    water: WaterProfile,
+
+    #[structopt(short, long, required = true, parse(try_from_str = parse_fermentable))]
+    /// Weight and name of a fermentable separated by colon, for example "4.5kg:Maris Otter"
+    grain: Vec<FermentableAddition>,
+
+    #[structopt(short, long)]
+    /// Target mash pH to calculate acid additions for
+    target_ph: Option<f64>,
+}
+
+pub fn calculate_and_print(mash_ph_options: MashPhOptions) {
+    let ph = match calculate_mash_ph(
+        &mash_ph_options.grain,
+        &mash_ph_options.water,
+        &mash_ph_options.volume,
+    ) {
+        Some(ph) => ph,
+        None => {
+            println!("No fermentables to mash");
+            return;
+        }
+    };
+
+    println!(
+        "Residual alkalinity: {:.0} ppm as CaCO3",
+        mash_ph_options.water.residual_alkalinity()
+    );
+    println!("Mash pH: {:.2}", ph);
+
+    if let Some(target_ph) = mash_ph_options.target_ph {
+        if let Some(acid) = calculate_acid_additions(
+            &mash_ph_options.grain,
+            &mash_ph_options.water,
+            &mash_ph_options.volume,
+            target_ph,
+        ) {
+            println!(
+                "Lactic acid (88%) to reach pH {:.2}: {:.1} ml",
+                target_ph,
+                acid.lactic_acid.as_milliliters()
+            );
+            println!(
+                "Acidulated malt to reach pH {:.2}: {:.0} g",
+                target_ph,
+                acid.acidulated_malt.as_grams()
+            );
+        }
+    }
+}
@@ -2,12 +2,30 @@ pub mod abv;
 pub mod alcohol_volume_weight;
 pub mod beer_style;
 pub mod boil_off;
+pub mod bottle_pressure;
 pub mod calories;
+pub mod carbonation;
+pub mod color;
+pub mod decoction;
+pub mod design;
 pub mod diluting;
+pub mod efficiency;
+pub mod export;
 pub mod fg;
 pub mod hops;
+pub mod ibu;
+pub mod import;
+pub mod mash;
+pub mod mash_ph;
 pub mod num_bottles;
+pub mod parsers;
+pub mod pitch_rate;
 pub mod priming;
+pub mod refractometer;
+pub mod scale;
 pub mod sg_correction;
+pub mod validate;
+pub mod water;
+pub mod water_plan;
 pub mod yeast;
 pub mod yeast_viability;
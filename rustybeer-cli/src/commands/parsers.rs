@@ -0,0 +1,122 @@
+//! Parsers of command arguments shared by several commands
+
+use rustybeer::calculators::ibu::{HopAddition, HopAdditionType, HopUse};
+use rustybeer::calculators::og::FermentableAddition;
+use rustybeer::conversions::{MassParser, TemperatureParser};
+use rustybeer::fermentables::FERMENTABLES;
+use rustybeer::measurements::Mass;
+
+/// Parses a fermentable from the list as `<weight>:<name>`
+pub fn parse_fermentable(fermentable: &str) -> Result<FermentableAddition, String> {
+    parse_fermentable_with(fermentable, "<weight>", |weight| {
+        MassParser::parse(weight).map_err(|e| e.to_string())
+    })
+}
+
+/// Parses a fermentable whose weight is read with `parse_weight`,
+/// `weight_format` describes the weight in the error message
+pub fn parse_fermentable_with<F>(
+    fermentable: &str,
+    weight_format: &str,
+    parse_weight: F,
+) -> Result<FermentableAddition, String>
+where
+    F: Fn(&str) -> Result<Mass, String>,
+{
+    let (weight, name) = fermentable
+        .split_once(':')
+        .ok_or_else(|| format!("expected {}:<name>, got {}", weight_format, fermentable))?;
+    let weight = parse_weight(weight)?;
+    let name = name.trim();
+    FERMENTABLES
+        .iter()
+        .find(|f| f.name.eq_ignore_ascii_case(name))
+        .map(|f| FermentableAddition::from_fermentable(f, weight))
+        .ok_or_else(|| format!("unknown fermentable {}", name))
+}
+
+/// Parses a hop addition as `<weight>@<alpha acid>%@<time>min(@<type>)(@<use>)`
+pub fn parse_hop(hop: &str) -> Result<HopAddition, String> {
+    parse_hop_with(hop, "<weight>", |weight| {
+        MassParser::parse(weight).map_err(|e| e.to_string())
+    })
+}
+
+/// Parses a hop addition whose weight is read with `parse_weight`,
+/// `weight_format` describes the weight in the error message
+///
+/// The optional use is `boil` (default), `first-wort`, `mash`,
+/// `whirlpool:<temperature>` or `dry-hop`. The time is the boil time of boil
+/// and first wort hops, the time in the mash of mash hops and the steep time
+/// of whirlpool hops in minutes, and the time in the fermenter of dry hops in
+/// days, for example `50g@12%@4d@pellet@dry-hop`.
+pub fn parse_hop_with<F>(
+    hop: &str,
+    weight_format: &str,
+    parse_weight: F,
+) -> Result<HopAddition, String>
+where
+    F: Fn(&str) -> Result<Mass, String>,
+{
+    let parts: Vec<&str> = hop.split('@').map(str::trim).collect();
+    if parts.len() < 3 || parts.len() > 5 {
+        return Err(format!(
+            "expected {}@<alpha acid>%@<time>min(@<type>)(@<use>), got {}",
+            weight_format, hop
+        ));
+    }
+
+    let weight = parse_weight(parts[0])?;
+    let alpha_acid = parts[1]
+        .trim_end_matches('%')
+        .parse::<f64>()
+        .map_err(|e| e.to_string())?;
+    // The type can be left out before the use
+    let (hop_type, hop_use) = match parts[3..] {
+        [] => (HopAdditionType::default(), "boil"),
+        [part] => match part.parse::<HopAdditionType>() {
+            Ok(hop_type) => (hop_type, "boil"),
+            Err(_) => (HopAdditionType::default(), part),
+        },
+        [hop_type, hop_use] => (hop_type.parse::<HopAdditionType>()?, hop_use),
+        _ => unreachable!(),
+    };
+    let (use_name, temperature) = match hop_use.split_once(':') {
+        Some((use_name, temperature)) => (use_name, Some(temperature)),
+        None => (hop_use, None),
+    };
+    let time_unit = match use_name.to_lowercase().as_str() {
+        "dry-hop" => "d",
+        _ => "min",
+    };
+    let time = parts[2]
+        .trim_end_matches(time_unit)
+        .trim()
+        .parse::<u32>()
+        .map_err(|e| e.to_string())?;
+    let alpha_acid = alpha_acid / 100.;
+
+    match (use_name.to_lowercase().as_str(), temperature) {
+        ("boil", None) => Ok(HopAddition::new(weight, alpha_acid, time, hop_type)),
+        ("first-wort", None) => Ok(HopAddition {
+            hop_use: HopUse::FirstWort,
+            ..HopAddition::new(weight, alpha_acid, time, hop_type)
+        }),
+        ("mash", None) => Ok(HopAddition {
+            hop_use: HopUse::Mash { time_mins: time },
+            ..HopAddition::new(weight, alpha_acid, 0, hop_type)
+        }),
+        ("whirlpool", Some(temperature)) => Ok(HopAddition::whirlpool(
+            weight,
+            alpha_acid,
+            TemperatureParser::parse(temperature).map_err(|e| e.to_string())?,
+            time,
+            hop_type,
+        )),
+        ("dry-hop", None) => Ok(HopAddition::dry_hop(weight, alpha_acid, time, hop_type)),
+        _ => Err(format!(
+            "expected hop type or use boil, first-wort, mash, whirlpool:<temperature> or dry-hop, got {}",
+            hop_use
+        )),
+    }
+}
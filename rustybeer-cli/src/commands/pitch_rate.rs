@@ -0,0 +1,117 @@
+use chrono::{Local, NaiveDate};
+use rustybeer::calculators::pitch_rate::{
+    calculate_packs, calculate_required_cells, calculate_starter, calculate_starter_volume,
+    GrowthModel, PitchRate, StarterStep,
+};
+use rustybeer::calculators::yeast_viability::calculate_viability;
+use rustybeer::conversions::{RelativeDensity, RelativeDensityParser, VolumeParser};
+use rustybeer::measurements::{Temperature, Volume};
+use rustybeer::yeasts::YeastForm;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "pitch-rate")]
+/// Calculates the yeast cells and packs needed for the wort and the starter to grow them
+pub struct PitchRateOptions {
+    #[structopt(short, long, parse(try_from_str = RelativeDensityParser::parse))]
+    /// Original gravity of the wort
+    og: RelativeDensity,
+
+    #[structopt(short, long, parse(try_from_str = VolumeParser::parse))]
+    /// Volume of the wort
+    volume: Volume,
+
+    #[structopt(short, long, default_value = "ale", possible_values = &["ale", "hybrid", "lager"])]
+    /// Target pitch rate
+    rate: PitchRate,
+
+    #[structopt(short, long)]
+    /// Production date of the yeast in format %d/%m/%Y, fresh yeast is assumed if not given
+    production_date: Option<String>,
+
+    #[structopt(short, long, default_value = "liquid", possible_values = &["dry", "liquid", "slurry"])]
+    /// Form of the yeast
+    form: YeastForm,
+
+    #[structopt(short, long, default_value = "100")]
+    /// Cells in a fresh pack or vial (billion)
+    cells_per_pack: f64,
+
+    #[structopt(short = "g", long, default_value = "1.036", parse(try_from_str = RelativeDensityParser::parse))]
+    /// Gravity of the starter wort
+    starter_gravity: RelativeDensity,
+
+    #[structopt(short, long, parse(try_from_str = VolumeParser::parse))]
+    /// Volume of a starter step pitched with one pack, can be given multiple times
+    starter: Vec<Volume>,
+}
+
+pub fn calculate_and_print(pitch_rate_options: PitchRateOptions) {
+    let today = Local::now().date_naive();
+    let production_date = match &pitch_rate_options.production_date {
+        Some(date) => match NaiveDate::parse_from_str(date, "%d/%m/%Y") {
+            Ok(date) => date,
+            Err(_) => {
+                println!("Date is invalid.");
+                return;
+            }
+        },
+        None => today,
+    };
+    let viability = calculate_viability(
+        pitch_rate_options.form,
+        production_date,
+        today,
+        &Temperature::from_celsius(4.),
+    );
+    let required_cells = calculate_required_cells(
+        &pitch_rate_options.og,
+        &pitch_rate_options.volume,
+        pitch_rate_options.rate,
+    );
+    let pack_cells = pitch_rate_options.cells_per_pack * viability / 100.;
+
+    println!("Required cells: {:.0} billion", required_cells);
+    println!("Viability: {:.1}%", viability);
+    println!(
+        "Packs without starter: {}",
+        calculate_packs(required_cells, pitch_rate_options.cells_per_pack, viability)
+    );
+
+    let steps: Vec<StarterStep> = pitch_rate_options
+        .starter
+        .iter()
+        .map(|volume| StarterStep::new(*volume, pitch_rate_options.starter_gravity))
+        .collect();
+    for model in [GrowthModel::White, GrowthModel::Braukaiser] {
+        if steps.is_empty() {
+            match calculate_starter_volume(
+                pack_cells,
+                required_cells,
+                &pitch_rate_options.starter_gravity,
+                model,
+            ) {
+                Some(volume) => println!(
+                    "Starter for one pack ({}): {:.2} l",
+                    model,
+                    volume.as_litres()
+                ),
+                None => println!("Starter for one pack ({}): too large", model),
+            }
+        } else {
+            for (i, growth) in calculate_starter(pack_cells, &steps, model)
+                .iter()
+                .enumerate()
+            {
+                println!(
+                    "Starter step {} ({}): {:.2} l, inoculation rate {:.0} million cells/ml, {:.0} billion cells",
+                    i + 1,
+                    model,
+                    steps[i].volume.as_litres(),
+                    growth.inoculation_rate,
+                    growth.total_cells,
+                );
+            }
+        }
+    }
+}
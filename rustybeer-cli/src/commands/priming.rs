@@ -1,6 +1,10 @@
-use rustybeer::calculators::priming::{calculate_co2, calculate_sugars};
+use anyhow::{anyhow, Result};
+use rustybeer::calculators::priming::{
+    calculate_co2, calculate_keg_sugars, calculate_speise, calculate_sugars_with, KEG_PRIMING_RATE,
+};
+use rustybeer::priming_sugars::{find_priming_sugar, PrimingSugar, PRIMING_SUGARS};
 use rustybeer::{
-    conversions::{TemperatureParser, ToMap, VolumeParser},
+    conversions::{RelativeDensity, RelativeDensityParser, TemperatureParser, ToMap, VolumeParser},
     measurements::{Temperature, Volume},
 };
 use structopt::StructOpt;
@@ -20,18 +24,68 @@ pub struct PrimingOptions {
     #[structopt(short, long = "co2_volumes", default_value = "2.0")]
     /// Volumes of wanted CO2, depends on beer style (e.g. British Style Ales 1.5 to 2.0)
     co2_volumes: f64,
+
+    #[structopt(short, long, parse(try_from_str = parse_sugar))]
+    /// Name of the priming sugar to show, can be given multiple times. All sugars are shown if not given.
+    sugar: Vec<PrimingSugar>,
+
+    #[structopt(short, long)]
+    /// Prime a keg instead of bottles
+    keg: bool,
+
+    #[structopt(long, parse(try_from_str = RelativeDensityParser::parse))]
+    /// Gravity of unfermented wort (speise) to prime with
+    speise: Option<RelativeDensity>,
+
+    #[structopt(long, default_value = "75")]
+    /// Apparent attenuation of the yeast in percentage, used with speise
+    attenuation: f64,
 }
 
-pub fn calculate_and_print(priming: PrimingOptions) {
+fn parse_sugar(name: &str) -> Result<PrimingSugar, String> {
+    find_priming_sugar(name)
+        .cloned()
+        .ok_or_else(|| format!("unknown priming sugar {}", name))
+}
+
+pub fn calculate_and_print(priming: PrimingOptions) -> Result<()> {
     let co2_beer = calculate_co2(&priming.temp);
-    let sugars = calculate_sugars(&priming.temp, &priming.amount, priming.co2_volumes);
+    let sugars = match priming.sugar.is_empty() {
+        true => PRIMING_SUGARS.to_vec(),
+        false => priming.sugar,
+    };
+    let sugars = match priming.keg {
+        true => calculate_keg_sugars(&priming.temp, &priming.amount, priming.co2_volumes, &sugars),
+        false => {
+            calculate_sugars_with(&priming.temp, &priming.amount, priming.co2_volumes, &sugars)
+        }
+    };
 
     println!("Amount: {:#?}", priming.amount.to_map());
     println!("Volumes of CO2: {}", priming.co2_volumes);
     println!("Temperature: {:#?}", priming.temp.to_map());
     println!("CO2 in Beer: {:.2} volumes", co2_beer);
+    if let Some(gravity) = priming.speise {
+        let speise = calculate_speise(
+            &priming.temp,
+            &priming.amount,
+            priming.co2_volumes,
+            &gravity,
+            priming.attenuation,
+        )
+        .ok_or_else(|| {
+            anyhow!("--speise needs a gravity above 1.000 and a positive --attenuation")
+        })?;
+        let speise = match priming.keg {
+            true => speise * KEG_PRIMING_RATE,
+            false => speise,
+        };
+        println!("Speise: {:#?}", speise.to_map());
+    }
     println!("Priming Sugar Options:");
     for sugar in sugars.iter() {
         println!("{:>23}: {:#?}", sugar.name, sugar.ratio.to_map());
     }
+
+    Ok(())
 }
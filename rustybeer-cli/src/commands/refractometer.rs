@@ -0,0 +1,57 @@
+use rustybeer::calculators::refractometer::{calculate_refractometer, RefractometerFormula};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "refractometer")]
+/// Corrects refractometer readings of fermenting wort to real gravity and ABV
+pub struct RefractometerOptions {
+    #[structopt(short, long)]
+    /// Refractometer reading before fermentation (°Bx)
+    original_brix: f64,
+
+    #[structopt(short, long)]
+    /// Current refractometer reading (°Bx)
+    current_brix: f64,
+
+    #[structopt(short, long, default_value = "1.04", parse(try_from_str = parse_wort_correction_factor))]
+    /// Wort correction factor of the refractometer
+    wort_correction_factor: f64,
+
+    #[structopt(short, long, possible_values = &["terrill-cubic", "terrill-linear", "novotny"])]
+    /// Formula to correct the reading with, all are shown if not given
+    formula: Option<RefractometerFormula>,
+}
+
+fn parse_wort_correction_factor(factor: &str) -> Result<f64, String> {
+    let factor = factor.parse::<f64>().map_err(|e| e.to_string())?;
+    if factor <= 0. {
+        return Err(format!(
+            "wort correction factor must be positive, got {}",
+            factor
+        ));
+    }
+    Ok(factor)
+}
+
+pub fn calculate_and_print(refractometer_options: RefractometerOptions) {
+    let formulas = match refractometer_options.formula {
+        Some(formula) => vec![formula],
+        None => RefractometerFormula::ALL.to_vec(),
+    };
+
+    for formula in formulas {
+        let reading = calculate_refractometer(
+            refractometer_options.original_brix,
+            refractometer_options.current_brix,
+            refractometer_options.wort_correction_factor,
+            formula,
+        );
+        println!(
+            "{}: OG {:.3}, FG {:.3}, ABV {:.2}%",
+            formula,
+            reading.og.as_specific_gravity(),
+            reading.fg.as_specific_gravity(),
+            reading.abv
+        );
+    }
+}
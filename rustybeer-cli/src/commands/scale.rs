@@ -0,0 +1,100 @@
+use anyhow::{anyhow, Context, Result};
+use rustybeer::conversions::VolumeParser;
+use rustybeer::measurements::Volume;
+use rustybeer::recipe::Recipe;
+use std::fs;
+use std::path::PathBuf;
+use structopt::{clap::ArgGroup, StructOpt};
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "scale", group = ArgGroup::with_name("scaling").required(true).multiple(true))]
+/// Scales rustybeer recipe JSON to a new batch size and/or brewhouse efficiency
+pub struct ScaleOptions {
+    #[structopt(parse(from_os_str))]
+    /// Recipe JSON file containing a recipe or a list of recipes
+    input: PathBuf,
+
+    #[structopt(short, long, group = "scaling", parse(try_from_str = VolumeParser::parse))]
+    /// New volume of wort into the fermenter
+    volume: Option<Volume>,
+
+    #[structopt(short, long, group = "scaling", parse(try_from_str = VolumeParser::parse))]
+    /// New volume of wort at the start of the boil. Defaults to the boil size scaled with the volume.
+    boil_size: Option<Volume>,
+
+    #[structopt(short, long, group = "scaling")]
+    /// New brewhouse efficiency in percentage
+    efficiency: Option<f64>,
+
+    #[structopt(short, long, parse(from_os_str))]
+    /// File to write the scaled recipe JSON to. Defaults to standard output.
+    output: Option<PathBuf>,
+}
+
+pub fn scale_and_print(scale_options: ScaleOptions) -> Result<()> {
+    let json = fs::read_to_string(&scale_options.input)
+        .with_context(|| format!("could not read {}", scale_options.input.display()))?;
+    let recipes: Vec<Recipe> = match serde_json::from_str(&json) {
+        Ok(recipes) => recipes,
+        Err(_) => vec![serde_json::from_str(&json).with_context(|| "invalid recipe JSON")?],
+    };
+
+    let scaled = recipes
+        .iter()
+        .map(|recipe| {
+            let batch_size = scale_options.volume.unwrap_or(recipe.batch_size);
+            let boil_size = scale_options.boil_size.unwrap_or_else(|| {
+                recipe.boil_size * (batch_size.as_litres() / recipe.batch_size.as_litres())
+            });
+            let efficiency = scale_options
+                .efficiency
+                .map_or(recipe.efficiency, |efficiency| efficiency / 100.);
+            recipe
+                .scale(&batch_size, &boil_size, efficiency)
+                .ok_or_else(|| {
+                    anyhow!(
+                        "could not scale {}: batch size, boil size and efficiency must be greater than zero",
+                        recipe.name
+                    )
+                })
+        })
+        .collect::<Result<Vec<Recipe>>>()?;
+    let json = serde_json::to_string_pretty(&scaled)?;
+
+    match scale_options.output {
+        Some(output) => {
+            fs::write(&output, json)
+                .with_context(|| format!("could not write {}", output.display()))?;
+            for recipe in &scaled {
+                println!(
+                    "Scaled {} to {:.1} l at {:.0}% efficiency: OG {:.3}, IBU {:.0}",
+                    recipe.name,
+                    recipe.batch_size.as_litres(),
+                    recipe.efficiency * 100.,
+                    recipe.og().as_specific_gravity(),
+                    recipe.ibu()
+                );
+                for fermentable in &recipe.fermentables {
+                    println!(
+                        "  {}: {:.2} kg",
+                        fermentable.name,
+                        fermentable.weight.as_kilograms()
+                    );
+                }
+                for hop in &recipe.hops {
+                    println!(
+                        "  {}: {:.1} g",
+                        hop.name.as_deref().unwrap_or("Hops"),
+                        hop.weight.as_grams()
+                    );
+                }
+                if let Some(priming) = &recipe.priming {
+                    println!("  {}: {:.1} g", priming.name, priming.weight.as_grams());
+                }
+            }
+        }
+        None => println!("{}", json),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use anyhow::{bail, Context, Result};
+use rustybeer::beerjson;
+use std::fs;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "validate")]
+/// Validates BeerJSON against the records supported by rustybeer, reporting unknown properties and invalid values
+pub struct ValidateOptions {
+    #[structopt(parse(from_os_str))]
+    /// BeerJSON file to validate
+    input: PathBuf,
+}
+
+pub fn validate_and_print(validate_options: ValidateOptions) -> Result<()> {
+    let json = fs::read_to_string(&validate_options.input)
+        .with_context(|| format!("could not read {}", validate_options.input.display()))?;
+    let errors = beerjson::validate(&json);
+
+    if errors.is_empty() {
+        println!("{} is valid BeerJSON", validate_options.input.display());
+        return Ok(());
+    }
+
+    for error in &errors {
+        println!("{}", error);
+    }
+    bail!(
+        "{} is not valid BeerJSON: {} error(s)",
+        validate_options.input.display(),
+        errors.len()
+    )
+}
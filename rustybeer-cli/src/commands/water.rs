@@ -0,0 +1,136 @@
+use rustybeer::calculators::salts::{
+    calculate_salt_additions, calculate_water_profile, Salt, SaltAddition,
+};
+use rustybeer::conversions::{MassParser, VolumeParser};
+use rustybeer::measurements::Volume;
+use rustybeer::water::{WaterProfile, WATER_PROFILES};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "water")]
+/// Water chemistry calculators
+pub enum WaterOptions {
+    /// Lists the water profiles of classic brewing cities
+    Profiles,
+    /// Calculates the water profile after salt additions
+    Salts(SaltsOptions),
+    /// Calculates the salt additions to get closest to a target water profile
+    Solve(SolveOptions),
+}
+
+#[derive(Debug, StructOpt)]
+pub struct SaltsOptions {
+    #[structopt(short, long, default_value = "Distilled")]
+    /// Source water as profile name or comma separated ppm of Ca,Mg,Na,Cl,SO4,HCO3
+    source: WaterProfile,
+
+    #[structopt(short, long, parse(try_from_str = VolumeParser::parse))]
+    /// Volume of the water
+    volume: Volume,
+
+    #[structopt(long, parse(try_from_str = parse_salt_addition))]
+    /// Salt and its weight separated by colon, for example gypsum:5g
+    salt: Vec<SaltAddition>,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct SolveOptions {
+    #[structopt(short, long, default_value = "Distilled")]
+    /// Source water as profile name or comma separated ppm of Ca,Mg,Na,Cl,SO4,HCO3
+    source: WaterProfile,
+
+    #[structopt(short, long)]
+    /// Target water as profile name or comma separated ppm of Ca,Mg,Na,Cl,SO4,HCO3
+    target: WaterProfile,
+
+    #[structopt(short, long, parse(try_from_str = VolumeParser::parse))]
+    /// Volume of the water
+    volume: Volume,
+
+    #[structopt(long)]
+    /// Salt allowed in the solution, all salts are allowed if not given
+    salt: Vec<Salt>,
+}
+
+fn parse_salt_addition(addition: &str) -> Result<SaltAddition, String> {
+    let (salt, weight) = addition
+        .split_once(':')
+        .ok_or_else(|| format!("expected <salt>:<weight>, got {}", addition))?;
+    let salt = salt.parse::<Salt>()?;
+    let weight = MassParser::parse(weight).map_err(|e| e.to_string())?;
+    Ok(SaltAddition::new(salt, weight))
+}
+
+fn print_ions(water: &WaterProfile, ratio: &str) {
+    println!(
+        "{:<12} {:>6.1} {:>6.1} {:>6.1} {:>6.1} {:>6.1} {:>6.1} {:>8}",
+        water.name,
+        water.calcium,
+        water.magnesium,
+        water.sodium,
+        water.chloride,
+        water.sulfate,
+        water.bicarbonate,
+        ratio,
+    );
+}
+
+fn print_profile(water: &WaterProfile) {
+    print_ions(water, &format!("{:.2}", water.sulfate_chloride_ratio()));
+}
+
+fn print_header() {
+    println!(
+        "{:<12} {:>6} {:>6} {:>6} {:>6} {:>6} {:>6} {:>8}",
+        "Profile", "Ca", "Mg", "Na", "Cl", "SO4", "HCO3", "SO4/Cl"
+    );
+}
+
+pub fn calculate_and_print(water_options: WaterOptions) {
+    match water_options {
+        WaterOptions::Profiles => {
+            print_header();
+            WATER_PROFILES.iter().for_each(print_profile);
+        }
+        WaterOptions::Salts(salts_options) => {
+            let water = calculate_water_profile(
+                &salts_options.source,
+                &salts_options.volume,
+                &salts_options.salt,
+            );
+            print_header();
+            print_profile(&salts_options.source);
+            print_profile(&WaterProfile {
+                name: "Result".to_owned(),
+                ..water
+            });
+        }
+        WaterOptions::Solve(solve_options) => {
+            let salts = match solve_options.salt.is_empty() {
+                true => Salt::ALL.to_vec(),
+                false => solve_options.salt,
+            };
+            let solution = calculate_salt_additions(
+                &solve_options.source,
+                &solve_options.target,
+                &solve_options.volume,
+                &salts,
+            );
+            for addition in &solution.additions {
+                println!(
+                    "{:<17} {:>6.2} g",
+                    addition.salt.name(),
+                    addition.weight.as_grams()
+                );
+            }
+            println!();
+            print_header();
+            print_profile(&solve_options.target);
+            print_profile(&WaterProfile {
+                name: "Result".to_owned(),
+                ..solution.water
+            });
+            print_ions(&solution.residual, "-");
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use crate::commands::boil_off::read_equipment;
+use crate::commands::parsers::parse_fermentable;
+use anyhow::Result;
+use rustybeer::calculators::og::FermentableAddition;
+use rustybeer::calculators::water_plan::{calculate_water_plan, SpargeMethod};
+use rustybeer::conversions::VolumeParser;
+use rustybeer::equipment::EquipmentProfile;
+use rustybeer::measurements::Volume;
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(name = "water-plan")]
+/// Plans the mash, sparge and total water of a brew day
+pub struct WaterPlanOptions {
+    #[structopt(short, long, parse(try_from_str = VolumeParser::parse))]
+    /// Volume of the beer to package
+    volume: Volume,
+
+    #[structopt(short, long, required = true, parse(try_from_str = parse_fermentable))]
+    /// Weight and name of a fermentable separated by colon, for example "4.5kg:Maris Otter"
+    grain: Vec<FermentableAddition>,
+
+    #[structopt(short, long, default_value = "batch", possible_values = &["fly", "batch", "no-sparge", "biab"])]
+    /// Sparge method
+    method: SpargeMethod,
+
+    #[structopt(short, long, default_value = "3")]
+    /// Mash thickness in litres per kilogram of grain
+    thickness: f64,
+
+    #[structopt(short, long, default_value = "60")]
+    /// Boil time in minutes
+    boil_time: u32,
+
+    #[structopt(short, long, default_value = "75")]
+    /// Mash efficiency in percentage
+    efficiency: f64,
+
+    #[structopt(long, parse(from_os_str))]
+    /// Equipment profile TOML file, typical home brewing losses are used if not given
+    equipment: Option<PathBuf>,
+}
+
+pub fn calculate_and_print(water_plan_options: WaterPlanOptions) -> Result<()> {
+    let equipment = match &water_plan_options.equipment {
+        Some(path) => read_equipment(path)?,
+        None => EquipmentProfile::default(),
+    };
+    let plan = calculate_water_plan(
+        &water_plan_options.grain,
+        &water_plan_options.volume,
+        water_plan_options.boil_time,
+        water_plan_options.thickness,
+        water_plan_options.efficiency / 100.,
+        water_plan_options.method,
+        &equipment,
+    );
+
+    println!("Mash water: {:.2} l", plan.mash_water.as_litres());
+    for (i, sparge) in plan.sparges.iter().enumerate() {
+        println!("Sparge {}: {:.2} l", i + 1, sparge.as_litres());
+    }
+    println!("Sparge water: {:.2} l", plan.sparge_water.as_litres());
+    println!("Total water: {:.2} l", plan.total_water.as_litres());
+    println!(
+        "Grain absorption: {:.2} l",
+        plan.grain_absorption.as_litres()
+    );
+    println!("Pre-boil volume: {:.2} l", plan.pre_boil_volume.as_litres());
+    println!(
+        "Pre-boil gravity: {:.3}",
+        plan.pre_boil_gravity.as_specific_gravity()
+    );
+
+    Ok(())
+}
@@ -1,43 +1,65 @@
-use chrono::format::ParseError;
-use chrono::{Duration, Local, NaiveDate, NaiveDateTime, NaiveTime};
-use rustybeer::calculators::yeast_viability::{calculate_cc, calculate_yv};
+use chrono::{Local, NaiveDate};
+use rustybeer::calculators::yeast_viability::{calculate_viability, calculate_viable_cells};
+use rustybeer::conversions::TemperatureParser;
+use rustybeer::measurements::Temperature;
+use rustybeer::yeasts::YeastForm;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
-#[structopt(name = "abv", author = "Philip Golovin")]
+#[structopt(name = "yeast-viability", author = "Philip Golovin")]
 /// Estimates yeast viability based off production date
 pub struct YeastViabilityOptions {
     #[structopt(short, long)]
     /// Production date
-    pd: String,
+    production_date: String,
 
-    #[structopt(short, long)]
-    /// Cell count
-    cc: Option<f32>,
+    #[structopt(long, default_value = "liquid", possible_values = &["dry", "liquid", "slurry"])]
+    /// Form of the yeast
+    form: YeastForm,
+
+    #[structopt(short, long, default_value = "4C", parse(try_from_str = TemperatureParser::parse))]
+    /// Temperature the yeast has been stored at
+    storage_temp: Temperature,
 
     #[structopt(short, long)]
-    /// Date format (default: "%d/%m/%Y")
-    f: Option<String>,
-}
+    /// Cell count at production
+    cell_count: Option<f64>,
 
-fn parse_date(date: String, format: String) -> Result<NaiveDateTime, ParseError> {
-    let t = NaiveTime::from_hms(0, 0, 0);
-    let date_only = NaiveDate::parse_from_str(&date, &format)?.and_time(t);
-    Ok(date_only)
+    #[structopt(short = "f", long, default_value = "%d/%m/%Y")]
+    /// Format of the production date
+    date_format: String,
 }
 
 pub fn calculate_and_print(yv_options: YeastViabilityOptions) {
-    let mut format = String::from("%d/%m/%Y");
-    if let Some(f) = yv_options.f {
-        format = f;
-    }
-    if let Ok(date) = parse_date(yv_options.pd, format) {
-        let days = (Local::now().timestamp() - date.timestamp()) / Duration::days(1).num_seconds();
-        println!("Yeast viability: {:.3}%", calculate_yv(days as f32));
-        if let Some(cc) = yv_options.cc {
-            println!("Cell count: {:.3}", calculate_cc(cc, days as f32))
-        }
-    } else {
-        println!("Date is invalid.");
+    let production_date =
+        match NaiveDate::parse_from_str(&yv_options.production_date, &yv_options.date_format) {
+            Ok(date) => date,
+            Err(_) => {
+                println!("Date is invalid.");
+                return;
+            }
+        };
+    let today = Local::now().date_naive();
+
+    println!(
+        "Yeast viability: {:.3}%",
+        calculate_viability(
+            yv_options.form,
+            production_date,
+            today,
+            &yv_options.storage_temp
+        )
+    );
+    if let Some(cc) = yv_options.cell_count {
+        println!(
+            "Cell count: {:.3}",
+            calculate_viable_cells(
+                cc,
+                yv_options.form,
+                production_date,
+                today,
+                &yv_options.storage_temp
+            )
+        )
     }
 }
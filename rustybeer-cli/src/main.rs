@@ -10,13 +10,30 @@ pub enum RustyBeer {
     AbvAbw(commands::alcohol_volume_weight::AbvAbwOptions),
     BeerStyle(commands::beer_style::BeerStyleOptions),
     Hops(commands::hops::HopOptions),
+    Ibu(commands::ibu::IbuOptions),
     BoilOff(commands::boil_off::BoilOffOptions),
+    BottlePressure(commands::bottle_pressure::BottlePressureOptions),
     Calories(commands::calories::CaloriesOptions),
+    Carbonation(commands::carbonation::CarbonationOptions),
+    Color(commands::color::ColorOptions),
+    Decoction(commands::decoction::DecoctionOptions),
+    Design(commands::design::DesignOptions),
     Diluting(commands::diluting::DilutingOptions),
+    Efficiency(commands::efficiency::EfficiencyOptions),
+    Export(commands::export::ExportOptions),
     Fg(commands::fg::FgOptions),
+    Import(commands::import::ImportOptions),
+    Mash(commands::mash::MashOptions),
+    MashPh(commands::mash_ph::MashPhOptions),
     NumBottles(commands::num_bottles::NumBottlesOptions),
+    PitchRate(commands::pitch_rate::PitchRateOptions),
     Priming(commands::priming::PrimingOptions),
+    Refractometer(commands::refractometer::RefractometerOptions),
+    Scale(commands::scale::ScaleOptions),
     SgCorrection(commands::sg_correction::SgCorrectionOptions),
+    Validate(commands::validate::ValidateOptions),
+    Water(commands::water::WaterOptions),
+    WaterPlan(commands::water_plan::WaterPlanOptions),
     Yeast(commands::yeast::YeastOptions),
     YeastViability(commands::yeast_viability::YeastViabilityOptions),
 }
@@ -28,13 +45,30 @@ fn main() -> Result<()> {
         RustyBeer::AbvAbw(opts) => commands::alcohol_volume_weight::calculate_and_print(opts),
         RustyBeer::BeerStyle(opts) => commands::beer_style::calculate_and_print(opts),
         RustyBeer::Hops(opts) => commands::hops::calculate_and_print(opts),
-        RustyBeer::BoilOff(opts) => commands::boil_off::calculate_and_print(opts),
+        RustyBeer::Ibu(opts) => commands::ibu::calculate_and_print(opts),
+        RustyBeer::BoilOff(opts) => commands::boil_off::calculate_and_print(opts)?,
+        RustyBeer::BottlePressure(opts) => commands::bottle_pressure::calculate_and_print(opts),
         RustyBeer::Calories(opts) => commands::calories::calculate_and_print(opts),
+        RustyBeer::Carbonation(opts) => commands::carbonation::calculate_and_print(opts),
+        RustyBeer::Color(opts) => commands::color::calculate_and_print(opts),
+        RustyBeer::Decoction(opts) => commands::decoction::calculate_and_print(opts),
+        RustyBeer::Design(opts) => commands::design::design_and_print(opts)?,
         RustyBeer::Diluting(opts) => commands::diluting::calculate_and_print(opts),
+        RustyBeer::Efficiency(opts) => commands::efficiency::calculate_and_print(opts),
+        RustyBeer::Export(opts) => commands::export::export_and_print(opts)?,
         RustyBeer::Fg(opts) => commands::fg::calculate_and_print(opts),
+        RustyBeer::Import(opts) => commands::import::import_and_print(opts)?,
+        RustyBeer::Mash(opts) => commands::mash::calculate_and_print(opts),
+        RustyBeer::MashPh(opts) => commands::mash_ph::calculate_and_print(opts),
         RustyBeer::NumBottles(opts) => commands::num_bottles::calculate_and_print(opts),
-        RustyBeer::Priming(opts) => commands::priming::calculate_and_print(opts),
+        RustyBeer::PitchRate(opts) => commands::pitch_rate::calculate_and_print(opts),
+        RustyBeer::Priming(opts) => commands::priming::calculate_and_print(opts)?,
+        RustyBeer::Refractometer(opts) => commands::refractometer::calculate_and_print(opts),
+        RustyBeer::Scale(opts) => commands::scale::scale_and_print(opts)?,
         RustyBeer::SgCorrection(opts) => commands::sg_correction::calculate_and_print(opts),
+        RustyBeer::Validate(opts) => commands::validate::validate_and_print(opts)?,
+        RustyBeer::Water(opts) => commands::water::calculate_and_print(opts),
+        RustyBeer::WaterPlan(opts) => commands::water_plan::calculate_and_print(opts)?,
         RustyBeer::Yeast(opts) => commands::yeast::search_and_print(opts),
         RustyBeer::YeastViability(opts) => commands::yeast_viability::calculate_and_print(opts),
     }
@@ -1,4 +1,4 @@
-use rustybeer::calculators::abv::{calculate_abv, calculate_fg};
+use rustybeer::calculators::abv::{calculate_alcohol, calculate_fg, AbvFormula};
 use rustybeer::conversions::RelativeDensityParser;
 use rweb::*;
 use serde::{Deserialize, Serialize};
@@ -8,6 +8,18 @@ pub struct AbvResponse {
     /// Alcohol by volume
     #[schema(example = "\"6.825000000000006\"")]
     abv: f64,
+    /// Alcohol by weight
+    abw: f64,
+    /// Apparent attenuation in percentage
+    apparent_attenuation: f64,
+    /// Real attenuation in percentage
+    real_attenuation: f64,
+    /// Original extract in °P
+    original_extract: f64,
+    /// Apparent extract in °P
+    apparent_extract: f64,
+    /// Real extract in °P
+    real_extract: f64,
 }
 
 #[derive(Debug, Default, Deserialize, Schema)]
@@ -18,12 +30,15 @@ struct AbvRequest {
     /// Final gravity
     #[schema(example = "\"1.014\"")]
     fg: String,
+    /// Formula used to calculate ABV: simple (default), advanced, balling or abw
+    #[schema(example = "\"simple\"")]
+    formula: Option<String>,
 }
 
 #[post("/calculate/abv")]
 #[openapi(
     id = "calculate.abv",
-    description = "Calculates alcohol by volume (abv) in percentage from final and original gravity. To use plato or brix units for gravity, add the °P or °Bx after the value.",
+    description = "Calculates alcohol by volume (abv) in percentage from final and original gravity with the simple, advanced, Balling or ABW based formula, along with attenuation and extracts. To use plato or brix units for gravity, add the °P or °Bx after the value.",
     summary = "Calculate alcohol by volume",
     tags("calculator")
 )]
@@ -39,8 +54,21 @@ pub fn abv(req: Json<AbvRequest>) -> Box<dyn Reply> {
         Err(_) => return Box::new(http::StatusCode::BAD_REQUEST),
     };
 
+    let formula = match value.formula.as_deref().map(str::parse::<AbvFormula>) {
+        Some(Ok(formula)) => formula,
+        Some(Err(_)) => return Box::new(http::StatusCode::BAD_REQUEST),
+        None => AbvFormula::default(),
+    };
+
+    let result = calculate_alcohol(&ogrd, &fgrd, formula);
     Box::new(Json::from(AbvResponse {
-        abv: calculate_abv(&ogrd, &fgrd),
+        abv: result.abv,
+        abw: result.abw,
+        apparent_attenuation: result.apparent_attenuation,
+        real_attenuation: result.real_attenuation,
+        original_extract: result.original_extract,
+        apparent_extract: result.apparent_extract,
+        real_extract: result.real_extract,
     }))
 }
 
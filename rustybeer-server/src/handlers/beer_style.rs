@@ -93,7 +93,7 @@ pub fn search(q: Query<BeerQuery>) -> Json<Vec<BeerStyleResponse>> {
     let resp: Vec<BeerStyleResponse> = BEER_STYLES
         .iter()
         .filter(|style| criteria.matches(style))
-        .map(|style| BeerStyleResponse::from_beerstyle(&style))
+        .map(BeerStyleResponse::from_beerstyle)
         .collect();
 
     Json::from(resp)
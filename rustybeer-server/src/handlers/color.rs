@@ -0,0 +1,82 @@
+use rustybeer::calculators::color::{
+    calculate_mcu, calculate_srm, srm_to_ebc, srm_to_hex, srm_to_lovibond, ColorModel,
+};
+use rustybeer::conversions::{MassParser, VolumeParser};
+use rweb::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Deserialize, Schema)]
+struct GrainRequest {
+    /// Weight of the grain as string (for example "4.5kg")
+    #[schema(example = "\"4.5kg\"")]
+    weight: String,
+    /// Color of the grain in degrees Lovibond
+    #[schema(example = "\"2\"")]
+    color: f64,
+}
+
+#[derive(Debug, Default, Deserialize, Schema)]
+struct ColorRequest {
+    /// Volume of the wort as string (for example "20l")
+    #[schema(example = "\"20l\"")]
+    volume: String,
+    /// Grains of the recipe
+    grains: Vec<GrainRequest>,
+    /// Model to convert MCU to SRM: Morey (default), Daniels or Mosher
+    #[schema(example = "\"Morey\"")]
+    model: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Schema)]
+pub struct ColorResponse {
+    /// Malt color units
+    mcu: f64,
+    /// Color in SRM
+    srm: f64,
+    /// Color in EBC
+    ebc: f64,
+    /// Color in degrees Lovibond
+    lovibond: f64,
+    /// Approximate sRGB color as hex string
+    #[schema(example = "\"#EA8F00\"")]
+    hex: String,
+}
+
+#[post("/calculate/color")]
+#[openapi(
+    id = "calculate.color",
+    description = "Calculates the color of the beer in SRM, EBC and degrees Lovibond from the grain weights and colors and the wort volume.",
+    summary = "Calculate beer color",
+    tags("calculator")
+)]
+pub fn color(req: Json<ColorRequest>) -> Box<dyn Reply> {
+    let value = req.into_inner();
+    let volume = match VolumeParser::parse(&value.volume) {
+        Ok(volume) => volume,
+        Err(_) => return Box::new(http::StatusCode::BAD_REQUEST),
+    };
+
+    let model = match value.model.as_deref().map(str::parse::<ColorModel>) {
+        Some(Ok(model)) => model,
+        Some(Err(_)) => return Box::new(http::StatusCode::BAD_REQUEST),
+        None => ColorModel::default(),
+    };
+
+    let mut grains = Vec::with_capacity(value.grains.len());
+    for grain in value.grains {
+        match MassParser::parse(&grain.weight) {
+            Ok(weight) => grains.push((weight, grain.color)),
+            Err(_) => return Box::new(http::StatusCode::BAD_REQUEST),
+        }
+    }
+
+    let mcu = calculate_mcu(&grains, &volume);
+    let srm = calculate_srm(mcu, model);
+    Box::new(Json::from(ColorResponse {
+        mcu,
+        srm,
+        ebc: srm_to_ebc(srm),
+        lovibond: srm_to_lovibond(srm),
+        hex: srm_to_hex(srm),
+    }))
+}
@@ -0,0 +1,94 @@
+use rustybeer::calculators::mash::{calculate_decoction_schedule, DecoctionSchedule, MashStep};
+use rustybeer::conversions::{TemperatureParser, VolumeParser};
+use rweb::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Deserialize, Schema)]
+struct DecoctionRequest {
+    /// Total volume of the mash as string (for example "30l")
+    #[schema(example = "\"30l\"")]
+    volume: String,
+    /// Traditional decoction schedule: Single (default), Double or Triple.
+    /// Ignored if rests are given.
+    #[schema(example = "\"Triple\"")]
+    schedule: Option<String>,
+    /// Current temperature of the mash as string (for example "50C"), required with rests
+    #[schema(example = "\"50C\"")]
+    temperature: Option<String>,
+    /// Target rest temperatures as strings (for example "65C")
+    rests: Option<Vec<String>>,
+}
+
+#[derive(Debug, Default, Serialize, Schema)]
+pub struct DecoctionStepResponse {
+    /// Name of the rest
+    #[schema(example = "\"Saccharification\"")]
+    name: String,
+    /// Rest temperature in Celsius
+    rest_temperature: f64,
+    /// Pulled portion of the mash as decimal
+    fraction: f64,
+    /// Volume of thick mash to pull in litres
+    volume: f64,
+}
+
+#[derive(Debug, Default, Serialize, Schema)]
+pub struct DecoctionResponse {
+    /// Dough-in temperature in Celsius
+    dough_in_temperature: f64,
+    /// Decoctions in order
+    decoctions: Vec<DecoctionStepResponse>,
+}
+
+fn parse_steps(value: &DecoctionRequest) -> Option<Vec<MashStep>> {
+    match (&value.temperature, &value.rests) {
+        (Some(temperature), Some(rests)) => std::iter::once(temperature)
+            .chain(rests.iter())
+            .enumerate()
+            .map(|(i, temperature)| {
+                let temperature = TemperatureParser::parse(temperature).ok()?;
+                Some(MashStep::new(format!("Rest {}", i + 1), temperature, 0))
+            })
+            .collect(),
+        (None, None) => match &value.schedule {
+            Some(schedule) => Some(schedule.parse::<DecoctionSchedule>().ok()?.steps()),
+            None => Some(DecoctionSchedule::default().steps()),
+        },
+        _ => None,
+    }
+}
+
+#[post("/calculate/decoction")]
+#[openapi(
+    id = "calculate.decoction",
+    description = "Calculates the portion of thick mash to pull and boil for each decoction of a single, double or triple decoction schedule or of the given rests.",
+    summary = "Calculate decoction volumes",
+    tags("calculator")
+)]
+pub fn decoction(req: Json<DecoctionRequest>) -> Box<dyn Reply> {
+    let value = req.into_inner();
+    let volume = match VolumeParser::parse(&value.volume) {
+        Ok(volume) => volume,
+        Err(_) => return Box::new(http::StatusCode::BAD_REQUEST),
+    };
+    let steps = match parse_steps(&value) {
+        Some(steps) => steps,
+        None => return Box::new(http::StatusCode::BAD_REQUEST),
+    };
+
+    match calculate_decoction_schedule(&volume, &steps) {
+        Ok(decoctions) => Box::new(Json::from(DecoctionResponse {
+            dough_in_temperature: steps[0].temperature.as_celsius(),
+            decoctions: decoctions
+                .into_iter()
+                .map(|decoction| DecoctionStepResponse {
+                    name: decoction.step.name,
+                    rest_temperature: decoction.step.temperature.as_celsius(),
+                    fraction: decoction.fraction,
+                    volume: decoction.volume.as_litres(),
+                })
+                .collect(),
+        })),
+        Err(_) => Box::new(http::StatusCode::UNPROCESSABLE_ENTITY),
+    }
+}
@@ -84,7 +84,7 @@ pub fn search(q: Query<HopQuery>) -> Json<Vec<HopResponse>> {
     let resp: Vec<HopResponse> = HOPS
         .iter()
         .filter(|hop| criteria.matches(hop))
-        .map(|hop| HopResponse::from_hop(&hop))
+        .map(HopResponse::from_hop)
         .collect();
 
     Json::from(resp)
@@ -0,0 +1,203 @@
+use rustybeer::calculators::ibu::{
+    calculate_bittering_weight, calculate_ibu, HopAddition, HopAdditionType, HopUse, IbuModel,
+};
+use rustybeer::conversions::{
+    MassParser, RelativeDensity, RelativeDensityParser, TemperatureParser, VolumeParser,
+};
+use rustybeer::measurements::Volume;
+use rweb::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Deserialize, Schema)]
+struct HopRequest {
+    /// Weight of the hop addition as string (for example "28g")
+    #[schema(example = "\"28g\"")]
+    weight: String,
+    /// Alpha acid percentage of the hop
+    #[schema(example = "\"6.4\"")]
+    alpha_acid: f64,
+    /// Boil time in minutes, the time in the mash for mash hops and the steep time for whirlpool hops
+    #[schema(example = "\"60\"")]
+    time_mins: u32,
+    /// Type of the hop: Whole (default), Plug or Pellet
+    #[schema(example = "\"Pellet\"")]
+    hop_type: Option<String>,
+    /// Use of the hop: Boil (default), First Wort, Mash, Whirlpool or Dry Hop
+    #[schema(example = "\"Boil\"")]
+    hop_use: Option<String>,
+    /// Temperature of the wort as string (for example "80C"), needed for whirlpool hops
+    #[schema(example = "\"80C\"")]
+    temperature: Option<String>,
+    /// Days in the fermenter, needed for dry hops
+    #[schema(example = "\"4\"")]
+    days: Option<u32>,
+}
+
+impl HopRequest {
+    fn to_hop_addition(&self) -> Option<HopAddition> {
+        let weight = MassParser::parse(&self.weight).ok()?;
+        let alpha_acid = self.alpha_acid / 100.;
+        let hop_type = match &self.hop_type {
+            Some(hop_type) => hop_type.parse::<HopAdditionType>().ok()?,
+            None => HopAdditionType::default(),
+        };
+        let hop_use = self
+            .hop_use
+            .as_deref()
+            .unwrap_or("boil")
+            .to_lowercase()
+            .replace(['-', '_'], " ");
+        match hop_use.as_str() {
+            "boil" => Some(HopAddition::new(
+                weight,
+                alpha_acid,
+                self.time_mins,
+                hop_type,
+            )),
+            "first wort" => Some(HopAddition {
+                hop_use: HopUse::FirstWort,
+                ..HopAddition::new(weight, alpha_acid, self.time_mins, hop_type)
+            }),
+            "mash" => Some(HopAddition {
+                hop_use: HopUse::Mash {
+                    time_mins: self.time_mins,
+                },
+                ..HopAddition::new(weight, alpha_acid, 0, hop_type)
+            }),
+            "whirlpool" => Some(HopAddition::whirlpool(
+                weight,
+                alpha_acid,
+                TemperatureParser::parse(self.temperature.as_deref()?).ok()?,
+                self.time_mins,
+                hop_type,
+            )),
+            "dry hop" => Some(HopAddition::dry_hop(
+                weight, alpha_acid, self.days?, hop_type,
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// Parses the parameters shared by the IBU requests
+fn parse_wort(
+    volume: &str,
+    gravity: &str,
+    model: &Option<String>,
+    hops: &[HopRequest],
+) -> Option<(Volume, RelativeDensity, IbuModel, Vec<HopAddition>)> {
+    let volume = VolumeParser::parse(volume).ok()?;
+    let gravity = RelativeDensityParser::parse(gravity).ok()?;
+    let model = match model {
+        Some(model) => model.parse::<IbuModel>().ok()?,
+        None => IbuModel::default(),
+    };
+    let hops = hops
+        .iter()
+        .map(HopRequest::to_hop_addition)
+        .collect::<Option<Vec<HopAddition>>>()?;
+    Some((volume, gravity, model, hops))
+}
+
+#[derive(Debug, Default, Deserialize, Schema)]
+struct IbuRequest {
+    /// Volume of the finished wort as string (for example "20l")
+    #[schema(example = "\"20l\"")]
+    volume: String,
+    /// Gravity of the wort during the boil
+    #[schema(example = "\"1.050\"")]
+    gravity: String,
+    /// Hop additions
+    hops: Vec<HopRequest>,
+    /// Model to calculate the utilization with: Tinseth (default), Rager, Garetz, Daniels or mIBU
+    #[schema(example = "\"Tinseth\"")]
+    model: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Schema)]
+pub struct IbuResponse {
+    /// International bittering units
+    #[schema(example = "\"18.97\"")]
+    ibu: f64,
+}
+
+#[post("/calculate/ibu")]
+#[openapi(
+    id = "calculate.ibu",
+    description = "Calculates international bittering units (IBU) contributed by hop additions. To use plato or brix units for gravity, add the °P or °Bx after the value.",
+    summary = "Calculate IBU",
+    tags("calculator")
+)]
+pub fn ibu(req: Json<IbuRequest>) -> Box<dyn Reply> {
+    let value = req.into_inner();
+    let (volume, gravity, model, hops) =
+        match parse_wort(&value.volume, &value.gravity, &value.model, &value.hops) {
+            Some(parsed) => parsed,
+            None => return Box::new(http::StatusCode::BAD_REQUEST),
+        };
+
+    Box::new(Json::from(IbuResponse {
+        ibu: calculate_ibu(hops, &volume, &gravity, &model),
+    }))
+}
+
+#[derive(Debug, Default, Deserialize, Schema)]
+struct BitteringRequest {
+    /// Volume of the finished wort as string (for example "22l")
+    #[schema(example = "\"22l\"")]
+    volume: String,
+    /// Gravity of the wort during the boil
+    #[schema(example = "\"1.058\"")]
+    gravity: String,
+    /// Target IBU of the beer
+    #[schema(example = "\"40\"")]
+    target_ibu: f64,
+    /// Alpha acid percentage of the bittering hop
+    #[schema(example = "\"8.5\"")]
+    alpha_acid: f64,
+    /// Boil time of the bittering hop in minutes, defaults to 60
+    #[schema(example = "\"60\"")]
+    time_mins: Option<u32>,
+    /// Other hop additions contributing to the target IBU
+    hops: Option<Vec<HopRequest>>,
+    /// Model to calculate the utilization with: Tinseth (default), Rager, Garetz, Daniels or mIBU
+    #[schema(example = "\"Tinseth\"")]
+    model: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Schema)]
+pub struct BitteringResponse {
+    /// Weight of the bittering hop in grams
+    #[schema(example = "\"20.5\"")]
+    weight: f64,
+}
+
+#[post("/calculate/bittering")]
+#[openapi(
+    id = "calculate.bittering",
+    description = "Calculates the weight of the bittering hop needed to reach the target IBU. Responds with 422 if other hop additions alone exceed the target.",
+    summary = "Calculate bittering hop weight",
+    tags("calculator")
+)]
+pub fn bittering(req: Json<BitteringRequest>) -> Box<dyn Reply> {
+    let value = req.into_inner();
+    let other_hops = value.hops.unwrap_or_default();
+    let (volume, gravity, model, hops) =
+        match parse_wort(&value.volume, &value.gravity, &value.model, &other_hops) {
+            Some(parsed) => parsed,
+            None => return Box::new(http::StatusCode::BAD_REQUEST),
+        };
+
+    match calculate_bittering_weight(
+        Some(hops),
+        value.alpha_acid / 100.,
+        value.time_mins,
+        &volume,
+        &gravity,
+        value.target_ibu,
+        &model,
+    ) {
+        Ok(weight) => Box::new(Json::from(BitteringResponse { weight })),
+        Err(_) => Box::new(http::StatusCode::UNPROCESSABLE_ENTITY),
+    }
+}
@@ -0,0 +1,100 @@
+use rustybeer::calculators::mash_ph::{calculate_acid_additions, calculate_mash_ph};
+use rustybeer::calculators::og::FermentableAddition;
+use rustybeer::conversions::{MassParser, VolumeParser};
+use rustybeer::fermentables::FERMENTABLES;
+use rustybeer::water::WaterProfile;
+use rweb::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Deserialize, Schema)]
+pub(crate) struct FermentableRequest {
+    /// Name of the fermentable in the fermentables list
+    #[schema(example = "\"Maris Otter\"")]
+    name: String,
+    /// Weight of the fermentable as string (for example "4.5kg")
+    #[schema(example = "\"4.5kg\"")]
+    weight: String,
+}
+
+#[derive(Debug, Default, Deserialize, Schema)]
+struct MashPhRequest {
+    /// Volume of the mash water as string (for example "15l")
+    #[schema(example = "\"15l\"")]
+    volume: String,
+    /// Mash water as profile name or comma separated ppm of Ca,Mg,Na,Cl,SO4,HCO3, defaults to distilled water
+    #[schema(example = "\"Dublin\"")]
+    water: Option<String>,
+    /// Fermentables of the grist
+    fermentables: Vec<FermentableRequest>,
+    /// Target mash pH to calculate acid additions for
+    #[schema(example = "\"5.4\"")]
+    target_ph: Option<f64>,
+}
+
+#[derive(Debug, Default, Serialize, Schema)]
+pub struct MashPhResponse {
+    /// Residual alkalinity of the water as CaCO3 in ppm
+    residual_alkalinity: f64,
+    /// Estimated mash pH
+    ph: f64,
+    /// Volume of 88% lactic acid in millilitres to reach the target pH
+    lactic_acid_ml: Option<f64>,
+    /// Weight of acidulated malt in grams to reach the target pH
+    acidulated_malt_grams: Option<f64>,
+}
+
+impl FermentableRequest {
+    pub(crate) fn to_fermentable_addition(&self) -> Option<FermentableAddition> {
+        let weight = MassParser::parse(&self.weight).ok()?;
+        FERMENTABLES
+            .iter()
+            .find(|f| f.name.eq_ignore_ascii_case(&self.name))
+            .map(|f| FermentableAddition::from_fermentable(f, weight))
+    }
+}
+
+#[post("/calculate/mash-ph")]
+#[openapi(
+    id = "calculate.mash-ph",
+    description = "Estimates the mash pH from the grist and the residual alkalinity of the mash water and the lactic acid or acidulated malt needed to reach a target pH.",
+    summary = "Calculate mash pH",
+    tags("calculator")
+)]
+pub fn mash_ph(req: Json<MashPhRequest>) -> Box<dyn Reply> {
+    let value = req.into_inner();
+    let volume = match VolumeParser::parse(&value.volume) {
+        Ok(volume) => volume,
+        Err(_) => return Box::new(http::StatusCode::BAD_REQUEST),
+    };
+
+    let water = match value.water.as_deref().map(str::parse::<WaterProfile>) {
+        Some(Ok(water)) => water,
+        Some(Err(_)) => return Box::new(http::StatusCode::BAD_REQUEST),
+        None => WaterProfile::default(),
+    };
+
+    let grist: Option<Vec<FermentableAddition>> = value
+        .fermentables
+        .iter()
+        .map(FermentableRequest::to_fermentable_addition)
+        .collect();
+    let grist = match grist {
+        Some(grist) => grist,
+        None => return Box::new(http::StatusCode::BAD_REQUEST),
+    };
+
+    let ph = match calculate_mash_ph(&grist, &water, &volume) {
+        Some(ph) => ph,
+        None => return Box::new(http::StatusCode::UNPROCESSABLE_ENTITY),
+    };
+    let acid = value
+        .target_ph
+        .and_then(|target_ph| calculate_acid_additions(&grist, &water, &volume, target_ph));
+
+    Box::new(Json::from(MashPhResponse {
+        residual_alkalinity: water.residual_alkalinity(),
+        ph,
+        lactic_acid_ml: acid.as_ref().map(|a| a.lactic_acid.as_milliliters()),
+        acidulated_malt_grams: acid.as_ref().map(|a| a.acidulated_malt.as_grams()),
+    }))
+}
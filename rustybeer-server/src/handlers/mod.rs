@@ -1,5 +1,11 @@
 pub mod abv;
 pub mod beer_style;
+pub mod color;
+pub mod decoction;
 pub mod hops;
+pub mod ibu;
+pub mod mash_ph;
 pub mod num_bottles;
+pub mod refractometer;
+pub mod water_plan;
 pub mod yeasts;
@@ -20,10 +20,7 @@ pub struct BottleResponse {
 
 impl BottleResponse {
     fn from_tuple(size: String, num: i32) -> BottleResponse {
-        BottleResponse {
-            size: size,
-            num: num,
-        }
+        BottleResponse { size, num }
     }
 }
 
@@ -0,0 +1,72 @@
+use rustybeer::calculators::refractometer::{
+    calculate_refractometer, RefractometerFormula, DEFAULT_WORT_CORRECTION_FACTOR,
+};
+use rweb::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Deserialize, Schema)]
+struct RefractometerRequest {
+    /// Refractometer reading before fermentation in °Bx
+    #[schema(example = "\"12.0\"")]
+    original_brix: f64,
+    /// Current refractometer reading in °Bx
+    #[schema(example = "\"6.5\"")]
+    current_brix: f64,
+    /// Wort correction factor of the refractometer, defaults to 1.04
+    #[schema(example = "\"1.04\"")]
+    wort_correction_factor: Option<f64>,
+    /// Formula to correct the reading with: terrill-cubic (default), terrill-linear or novotny
+    #[schema(example = "\"terrill-cubic\"")]
+    formula: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Schema)]
+pub struct RefractometerResponse {
+    /// Original gravity
+    #[schema(example = "\"1.046\"")]
+    og: f64,
+    /// Current gravity
+    #[schema(example = "\"1.013\"")]
+    fg: f64,
+    /// Alcohol by volume
+    #[schema(example = "\"4.391\"")]
+    abv: f64,
+}
+
+#[post("/calculate/refractometer")]
+#[openapi(
+    id = "calculate.refractometer",
+    description = "Corrects refractometer readings of fermenting wort for the alcohol present and calculates the real gravity and alcohol by volume from the original and current Brix.",
+    summary = "Calculate refractometer correction",
+    tags("calculator")
+)]
+pub fn refractometer(req: Json<RefractometerRequest>) -> Box<dyn Reply> {
+    let value = req.into_inner();
+    let formula = match value
+        .formula
+        .as_deref()
+        .map(str::parse::<RefractometerFormula>)
+    {
+        Some(Ok(formula)) => formula,
+        Some(Err(_)) => return Box::new(http::StatusCode::BAD_REQUEST),
+        None => RefractometerFormula::default(),
+    };
+    let wort_correction_factor = value
+        .wort_correction_factor
+        .unwrap_or(DEFAULT_WORT_CORRECTION_FACTOR);
+    if wort_correction_factor <= 0. {
+        return Box::new(http::StatusCode::BAD_REQUEST);
+    }
+
+    let reading = calculate_refractometer(
+        value.original_brix,
+        value.current_brix,
+        wort_correction_factor,
+        formula,
+    );
+    Box::new(Json::from(RefractometerResponse {
+        og: reading.og.as_specific_gravity(),
+        fg: reading.fg.as_specific_gravity(),
+        abv: reading.abv,
+    }))
+}
@@ -0,0 +1,145 @@
+use crate::handlers::mash_ph::FermentableRequest;
+use rustybeer::calculators::og::FermentableAddition;
+use rustybeer::calculators::water_plan::{calculate_water_plan, SpargeMethod};
+use rustybeer::conversions::VolumeParser;
+use rustybeer::equipment::EquipmentProfile;
+use rustybeer::measurements::Volume;
+use rweb::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Deserialize, Schema)]
+struct EquipmentRequest {
+    /// Volume evaporated from the kettle in an hour in litres
+    #[schema(example = "\"4.0\"")]
+    evaporation_rate: Option<f64>,
+    /// Wort left in the kettle and chiller in litres
+    trub_chiller_loss: Option<f64>,
+    /// Wort left in the mash tun dead space in litres
+    mash_tun_dead_space: Option<f64>,
+    /// Beer left in the fermenter in litres
+    fermenter_loss: Option<f64>,
+    /// Water absorbed by the grain in litres per kilogram
+    grain_absorption: Option<f64>,
+    /// Shrinkage of the wort when cooled in percentage
+    cooling_shrinkage: Option<f64>,
+}
+
+impl EquipmentRequest {
+    fn to_equipment_profile(&self) -> EquipmentProfile {
+        let default = EquipmentProfile::default();
+        EquipmentProfile {
+            evaporation_rate: self
+                .evaporation_rate
+                .map_or(default.evaporation_rate, Volume::from_litres),
+            trub_chiller_loss: self
+                .trub_chiller_loss
+                .map_or(default.trub_chiller_loss, Volume::from_litres),
+            mash_tun_dead_space: self
+                .mash_tun_dead_space
+                .map_or(default.mash_tun_dead_space, Volume::from_litres),
+            fermenter_loss: self
+                .fermenter_loss
+                .map_or(default.fermenter_loss, Volume::from_litres),
+            grain_absorption: self.grain_absorption.unwrap_or(default.grain_absorption),
+            cooling_shrinkage: self.cooling_shrinkage.unwrap_or(default.cooling_shrinkage),
+            ..default
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Schema)]
+struct WaterPlanRequest {
+    /// Volume of the beer to package as string (for example "20l")
+    #[schema(example = "\"20l\"")]
+    volume: String,
+    /// Fermentables of the grist
+    fermentables: Vec<FermentableRequest>,
+    /// Sparge method: fly, batch (default), no-sparge or biab
+    #[schema(example = "\"batch\"")]
+    method: Option<String>,
+    /// Mash thickness in litres per kilogram, defaults to 3
+    #[schema(example = "\"3.0\"")]
+    mash_thickness: Option<f64>,
+    /// Boil time in minutes, defaults to 60
+    #[schema(example = "\"60\"")]
+    boil_time: Option<u32>,
+    /// Mash efficiency in percentage, defaults to 75
+    #[schema(example = "\"75\"")]
+    efficiency: Option<f64>,
+    /// Losses of the equipment, typical home brewing losses are used for missing values
+    equipment: Option<EquipmentRequest>,
+}
+
+#[derive(Debug, Default, Serialize, Schema)]
+pub struct WaterPlanResponse {
+    /// Water to mash in with in litres
+    mash_water: f64,
+    /// Water of each sparge in litres
+    sparges: Vec<f64>,
+    /// Total sparge water in litres
+    sparge_water: f64,
+    /// Total water of the brew day in litres
+    total_water: f64,
+    /// Water absorbed by the grain in litres
+    grain_absorption: f64,
+    /// Volume of the wort before the boil in litres
+    pre_boil_volume: f64,
+    /// Gravity of the wort before the boil
+    pre_boil_gravity: f64,
+}
+
+#[post("/calculate/water-plan")]
+#[openapi(
+    id = "calculate.water-plan",
+    description = "Plans the mash, sparge and total water of a brew day for fly, batch or no-sparge methods from the grist, packaged volume and equipment losses, and estimates the pre-boil gravity.",
+    summary = "Calculate water plan",
+    tags("calculator")
+)]
+pub fn water_plan(req: Json<WaterPlanRequest>) -> Box<dyn Reply> {
+    let value = req.into_inner();
+    let volume = match VolumeParser::parse(&value.volume) {
+        Ok(volume) => volume,
+        Err(_) => return Box::new(http::StatusCode::BAD_REQUEST),
+    };
+
+    let method = match value.method.as_deref().map(str::parse::<SpargeMethod>) {
+        Some(Ok(method)) => method,
+        Some(Err(_)) => return Box::new(http::StatusCode::BAD_REQUEST),
+        None => SpargeMethod::default(),
+    };
+
+    let grist: Option<Vec<FermentableAddition>> = value
+        .fermentables
+        .iter()
+        .map(FermentableRequest::to_fermentable_addition)
+        .collect();
+    let grist = match grist {
+        Some(grist) => grist,
+        None => return Box::new(http::StatusCode::BAD_REQUEST),
+    };
+
+    let equipment = value.equipment.as_ref().map_or_else(
+        EquipmentProfile::default,
+        EquipmentRequest::to_equipment_profile,
+    );
+
+    let plan = calculate_water_plan(
+        &grist,
+        &volume,
+        value.boil_time.unwrap_or(60),
+        value.mash_thickness.unwrap_or(3.),
+        value.efficiency.unwrap_or(75.) / 100.,
+        method,
+        &equipment,
+    );
+
+    Box::new(Json::from(WaterPlanResponse {
+        mash_water: plan.mash_water.as_litres(),
+        sparges: plan.sparges.iter().map(Volume::as_litres).collect(),
+        sparge_water: plan.sparge_water.as_litres(),
+        total_water: plan.total_water.as_litres(),
+        grain_absorption: plan.grain_absorption.as_litres(),
+        pre_boil_volume: plan.pre_boil_volume.as_litres(),
+        pre_boil_gravity: plan.pre_boil_gravity.as_specific_gravity(),
+    }))
+}
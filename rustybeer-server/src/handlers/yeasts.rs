@@ -50,19 +50,15 @@ impl YeastResponse {
         YeastResponse {
             name: yeast.name.clone(),
             company: yeast.company.clone(),
-            id: yeast.id.as_ref().map_or(None, |i| Some(i.to_string())),
+            id: yeast.id.as_ref().map(|i| i.to_string()),
             min_attenuation: yeast.min_attenuation,
             max_attenuation: yeast.max_attenuation,
-            attenuation_level: yeast
-                .attenuation_level
-                .map_or(None, |a| Some(a.to_string())),
-            flocculation: yeast.flocculation.map_or(None, |f| Some(f.to_string())),
-            min_temp: yeast.min_temp.map_or(None, |t| Some(t.to_map())),
-            max_temp: yeast.max_temp.map_or(None, |t| Some(t.to_map())),
+            attenuation_level: yeast.attenuation_level.map(|a| a.to_string()),
+            flocculation: yeast.flocculation.map(|f| f.to_string()),
+            min_temp: yeast.min_temp.map(|t| t.to_map()),
+            max_temp: yeast.max_temp.map(|t| t.to_map()),
             alc_tolerance: yeast.alc_tolerance,
-            alc_tolerance_level: yeast
-                .alc_tolerance_level
-                .map_or(None, |a| Some(a.to_string())),
+            alc_tolerance_level: yeast.alc_tolerance_level.map(|a| a.to_string()),
         }
     }
 }
@@ -85,7 +81,7 @@ pub fn search(q: Query<YeastQuery>) -> Json<Vec<YeastResponse>> {
     let resp: Vec<YeastResponse> = YEASTS
         .iter()
         .filter(|yeast| criteria.matches(yeast))
-        .map(|yeast| YeastResponse::from_yeast(&yeast))
+        .map(YeastResponse::from_yeast)
         .collect();
 
     Json::from(resp)
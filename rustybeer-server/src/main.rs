@@ -23,8 +23,15 @@ async fn main() {
         handlers::abv::abv()
             .or(handlers::abv::fg())
             .or(handlers::beer_style::search())
+            .or(handlers::color::color())
+            .or(handlers::decoction::decoction())
             .or(handlers::hops::search())
+            .or(handlers::ibu::ibu())
+            .or(handlers::ibu::bittering())
+            .or(handlers::mash_ph::mash_ph())
             .or(handlers::num_bottles::bottles())
+            .or(handlers::refractometer::refractometer())
+            .or(handlers::water_plan::water_plan())
             .or(handlers::yeasts::search())
     });
 
@@ -1,9 +1,9 @@
 use crate::strings::contains_case_insensitive;
 use once_cell::sync::Lazy;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// Data about a particular style of beer.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct BeerStyle {
     pub name: String,
     pub original_gravity_min: f32,
@@ -29,6 +29,13 @@ pub static BEER_STYLES: Lazy<Vec<BeerStyle>> = Lazy::new(|| {
     serde_json::from_str(BEER_STYLES_JSON).expect("beer styles data could not be deserialised")
 });
 
+/// Finds a beer style by its name, ignoring case
+pub fn find_beer_style(name: &str) -> Option<&'static BeerStyle> {
+    BEER_STYLES
+        .iter()
+        .find(|style| style.name.eq_ignore_ascii_case(name))
+}
+
 /// Criteria for selecting a beer style.
 ///
 /// If an attribute is `None`, it is ignored.
@@ -46,7 +53,7 @@ impl Criteria {
     /// Whether the given beer style matches **all** criteria that are `Some`.
     pub fn matches(&self, style: &BeerStyle) -> bool {
         if let Some(name) = &self.name {
-            if !contains_case_insensitive(&style.name, &name) {
+            if !contains_case_insensitive(&style.name, name) {
                 return false;
             }
         }
@@ -99,6 +106,13 @@ mod tests {
         description: String::from("Test"),
     });
 
+    #[test]
+    fn find_style() {
+        let style = find_beer_style("american ipa").unwrap();
+        assert_eq!("American IPA", style.name);
+        assert!(find_beer_style("American").is_none());
+    }
+
     #[test]
     fn no_criteria_matches() {
         let criteria = Criteria::default();
@@ -0,0 +1,1656 @@
+//! Reading, writing and validating recipes in BeerJSON 1.0 format,
+//! the successor of BeerXML.
+//!
+//! Only the subset of BeerJSON 1.0 read into `rustybeer` types is supported.
+//! Validation checks every record of the subset: required properties, units,
+//! value ranges and unknown properties, which are records outside the subset.
+//! Errors point at the offending JSON path, for example
+//! `beerjson.recipes[0].batch_size.unit`.
+//!
+//! See: https://github.com/beerjson/beerjson
+//!
+//! # Example
+//! ```
+//! use rustybeer::beerjson;
+//!
+//! let json = r#"{
+//!   "beerjson": {
+//!     "version": 1.0,
+//!     "recipes": [{
+//!       "name": "SMaSH",
+//!       "type": "all grain",
+//!       "author": "Brewer",
+//!       "batch_size": { "unit": "l", "value": 20 },
+//!       "efficiency": { "brewhouse": { "unit": "%", "value": 72 } },
+//!       "ingredients": {
+//!         "fermentable_additions": [{
+//!           "name": "Maris Otter",
+//!           "type": "grain",
+//!           "yield": { "potential": { "unit": "sg", "value": 1.038 } },
+//!           "color": { "unit": "Lovi", "value": 3 },
+//!           "amount": { "unit": "kg", "value": 4.5 }
+//!         }]
+//!       }
+//!     }]
+//!   }
+//! }"#;
+//!
+//! let recipes = beerjson::from_str(json).unwrap();
+//! assert_eq!("SMaSH", recipes[0].name);
+//!
+//! let errors = beerjson::validate(&json.replace("\"kg\"", "\"stone\""));
+//! assert_eq!(
+//!     "beerjson.recipes[0].ingredients.fermentable_additions[0].amount.unit",
+//!     errors[0].path
+//! );
+//! ```
+
+use crate::beer_styles::{BeerStyle, BEER_STYLES};
+use crate::beerxml::AROMA_TEMPERATURE;
+use crate::calculators::color::{ebc_to_srm, lovibond_to_srm, srm_to_lovibond};
+use crate::calculators::ibu::{HopAddition, HopAdditionType, HopUse};
+use crate::calculators::mash::MashStep;
+use crate::calculators::og::FermentableAddition;
+use crate::conversions::RelativeDensity;
+use crate::fermentables::{self, SUCROSE_PPG};
+use crate::recipe::Recipe;
+use crate::water::WaterProfile;
+use crate::yeasts::{Level, Yeast, YeastForm};
+use measurements::{Mass, Temperature, Volume};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Supported version of BeerJSON
+pub const VERSION: f64 = 1.0;
+
+const MINUTES_IN_DAY: f64 = 24. * 60.;
+
+/// Error in BeerJSON document with JSON path to the offending value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BeerJsonError {
+    /// path to the offending value, for example `beerjson.recipes[0].name`
+    pub path: String,
+    pub message: String,
+}
+
+impl BeerJsonError {
+    fn new(path: &str, message: &str) -> Self {
+        Self {
+            path: path.to_owned(),
+            message: message.to_owned(),
+        }
+    }
+}
+
+impl fmt::Display for BeerJsonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+impl std::error::Error for BeerJsonError {}
+
+/// Reads all recipes from BeerJSON document
+///
+/// Fails with the first validation error of the document.
+pub fn from_str(json: &str) -> Result<Vec<Recipe>, BeerJsonError> {
+    let document = parse(json)?;
+    if let Some(error) = check(&document).into_iter().next() {
+        return Err(error);
+    }
+
+    let styles = &document.beerjson.styles;
+    Ok(document
+        .beerjson
+        .recipes
+        .into_iter()
+        .map(|r| r.into_recipe(styles))
+        .collect())
+}
+
+/// Writes recipes as BeerJSON document
+pub fn to_string(recipes: &[Recipe]) -> Result<String, BeerJsonError> {
+    let mut styles: Vec<JsonStyle> = Vec::new();
+    for style in recipes.iter().filter_map(|r| r.style.as_ref()) {
+        if !styles.iter().any(|s| s.name == style.name) {
+            styles.push(JsonStyle::from(style));
+        }
+    }
+
+    let document = JsonDocument {
+        beerjson: JsonBeerJson {
+            version: VERSION,
+            recipes: recipes.iter().map(JsonRecipe::from).collect(),
+            styles,
+        },
+    };
+    serde_json::to_string_pretty(&document).map_err(|e| BeerJsonError::new(".", &e.to_string()))
+}
+
+/// Validates BeerJSON document against the supported subset and returns all
+/// errors found.
+///
+/// Structural errors, like missing or unknown properties, stop the validation
+/// at the first one. Empty list means the document is valid.
+pub fn validate(json: &str) -> Vec<BeerJsonError> {
+    match parse(json) {
+        Ok(document) => check(&document),
+        Err(error) => vec![error],
+    }
+}
+
+fn parse(json: &str) -> Result<JsonDocument, BeerJsonError> {
+    let deserializer = &mut serde_json::Deserializer::from_str(json);
+    serde_path_to_error::deserialize(deserializer).map_err(|e| {
+        let path = e.path().to_string();
+        BeerJsonError {
+            path,
+            message: e.into_inner().to_string(),
+        }
+    })
+}
+
+/// Checks the values which can't be validated by the document structure alone
+fn check(document: &JsonDocument) -> Vec<BeerJsonError> {
+    let mut errors = Vec::new();
+    let mut expect = |condition: bool, path: String, message: &str| {
+        if !condition {
+            errors.push(BeerJsonError {
+                path,
+                message: message.to_owned(),
+            });
+        }
+    };
+
+    let root = &document.beerjson;
+    expect(
+        (root.version - VERSION).abs() < f64::EPSILON,
+        "beerjson.version".to_owned(),
+        "only version 1.0 is supported",
+    );
+
+    for (i, recipe) in root.recipes.iter().enumerate() {
+        let path = format!("beerjson.recipes[{}]", i);
+        expect(
+            !recipe.name.is_empty(),
+            format!("{}.name", path),
+            "must not be empty",
+        );
+        expect(
+            recipe.batch_size.value > 0.,
+            format!("{}.batch_size.value", path),
+            "must be positive",
+        );
+        expect(
+            is_percentage(recipe.efficiency.brewhouse.value),
+            format!("{}.efficiency.brewhouse.value", path),
+            "must be between 0 and 100",
+        );
+
+        let ingredients = &recipe.ingredients;
+        for (j, f) in ingredients.fermentable_additions.iter().enumerate() {
+            let path = format!("{}.ingredients.fermentable_additions[{}]", path, j);
+            expect(
+                f.amount.value >= 0.,
+                format!("{}.amount.value", path),
+                "must not be negative",
+            );
+            expect(
+                f.color.value >= 0.,
+                format!("{}.color.value", path),
+                "must not be negative",
+            );
+            expect(
+                f.fermentable_yield.potential.is_some() || f.fermentable_yield.fine_grind.is_some(),
+                format!("{}.yield", path),
+                "potential or fine_grind is required",
+            );
+            if let Some(potential) = &f.fermentable_yield.potential {
+                expect(
+                    potential.to_relative_density().as_specific_gravity() >= 1.,
+                    format!("{}.yield.potential.value", path),
+                    "must be at least 1.000 sg",
+                );
+            }
+            if let Some(fine_grind) = &f.fermentable_yield.fine_grind {
+                expect(
+                    is_percentage(fine_grind.value),
+                    format!("{}.yield.fine_grind.value", path),
+                    "must be between 0 and 100",
+                );
+            }
+        }
+
+        for (j, h) in ingredients.hop_additions.iter().enumerate() {
+            let path = format!("{}.ingredients.hop_additions[{}]", path, j);
+            expect(
+                h.amount.value >= 0.,
+                format!("{}.amount.value", path),
+                "must not be negative",
+            );
+            expect(
+                is_percentage(h.alpha_acid.value),
+                format!("{}.alpha_acid.value", path),
+                "must be between 0 and 100",
+            );
+            for (name, time) in [("time", &h.timing.time), ("duration", &h.timing.duration)] {
+                if let Some(time) = time {
+                    expect(
+                        time.value >= 0.,
+                        format!("{}.timing.{}.value", path, name),
+                        "must not be negative",
+                    );
+                }
+            }
+        }
+
+        for (j, c) in ingredients.culture_additions.iter().enumerate() {
+            let path = format!("{}.ingredients.culture_additions[{}]", path, j);
+            if let Some(attenuation) = &c.attenuation {
+                expect(
+                    is_percentage(attenuation.value),
+                    format!("{}.attenuation.value", path),
+                    "must be between 0 and 100",
+                );
+            }
+            if let Some(range) = &c.temperature_range {
+                expect(
+                    is_ordered(range, |t| t.to_temperature().as_celsius()),
+                    format!("{}.temperature_range", path),
+                    "minimum must not exceed maximum",
+                );
+            }
+        }
+
+        for (j, w) in ingredients.water_additions.iter().enumerate() {
+            let path = format!("{}.ingredients.water_additions[{}]", path, j);
+            for (name, ion) in [
+                ("calcium", &w.calcium),
+                ("bicarbonate", &w.bicarbonate),
+                ("sulfate", &w.sulfate),
+                ("chloride", &w.chloride),
+                ("sodium", &w.sodium),
+                ("magnesium", &w.magnesium),
+            ] {
+                expect(
+                    ion.value >= 0.,
+                    format!("{}.{}.value", path, name),
+                    "must not be negative",
+                );
+            }
+        }
+
+        if let Some(mash) = &recipe.mash {
+            for (j, step) in mash.mash_steps.iter().enumerate() {
+                expect(
+                    step.step_time.value >= 0.,
+                    format!("{}.mash.mash_steps[{}].step_time.value", path, j),
+                    "must not be negative",
+                );
+            }
+        }
+
+        if let Some(boil) = &recipe.boil {
+            expect(
+                boil.boil_time.value >= 0.,
+                format!("{}.boil.boil_time.value", path),
+                "must not be negative",
+            );
+            if let Some(pre_boil_size) = &boil.pre_boil_size {
+                expect(
+                    pre_boil_size.value > 0.,
+                    format!("{}.boil.pre_boil_size.value", path),
+                    "must be positive",
+                );
+            }
+        }
+    }
+
+    for (i, style) in root.styles.iter().enumerate() {
+        let path = format!("beerjson.styles[{}]", i);
+        expect(
+            !style.name.is_empty(),
+            format!("{}.name", path),
+            "must not be empty",
+        );
+        let ordered = [
+            (
+                "original_gravity",
+                style
+                    .original_gravity
+                    .as_ref()
+                    .map(|r| is_ordered(r, |g| g.to_relative_density().as_specific_gravity())),
+            ),
+            (
+                "final_gravity",
+                style
+                    .final_gravity
+                    .as_ref()
+                    .map(|r| is_ordered(r, |g| g.to_relative_density().as_specific_gravity())),
+            ),
+            (
+                "international_bitterness_units",
+                style
+                    .international_bitterness_units
+                    .as_ref()
+                    .map(|r| is_ordered(r, |ibu| ibu.value)),
+            ),
+            (
+                "color",
+                style
+                    .color
+                    .as_ref()
+                    .map(|r| is_ordered(r, Quantity::as_srm)),
+            ),
+            (
+                "alcohol_by_volume",
+                style
+                    .alcohol_by_volume
+                    .as_ref()
+                    .map(|r| is_ordered(r, |abv| abv.value)),
+            ),
+        ];
+        for (name, ordered) in ordered {
+            expect(
+                ordered.unwrap_or(true),
+                format!("{}.{}", path, name),
+                "minimum must not exceed maximum",
+            );
+        }
+    }
+
+    errors
+}
+
+/// Checks that the minimum of the range doesn't exceed the maximum
+fn is_ordered<U>(range: &Range<U>, value: impl Fn(&Quantity<U>) -> f64) -> bool {
+    value(&range.minimum) <= value(&range.maximum)
+}
+
+fn is_percentage(value: f64) -> bool {
+    (0. ..=100.).contains(&value)
+}
+
+/// A value with a unit, the building block of BeerJSON
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Quantity<U> {
+    unit: U,
+    value: f64,
+}
+
+impl<U> Quantity<U> {
+    fn new(unit: U, value: f64) -> Self {
+        Self { unit, value }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+enum MassUnit {
+    #[serde(rename = "mg")]
+    Milligram,
+    #[serde(rename = "g")]
+    Gram,
+    #[serde(rename = "kg")]
+    Kilogram,
+    #[serde(rename = "lb")]
+    Pound,
+    #[serde(rename = "oz")]
+    Ounce,
+}
+
+impl Quantity<MassUnit> {
+    fn to_mass(&self) -> Mass {
+        match self.unit {
+            MassUnit::Milligram => Mass::from_milligrams(self.value),
+            MassUnit::Gram => Mass::from_grams(self.value),
+            MassUnit::Kilogram => Mass::from_kilograms(self.value),
+            MassUnit::Pound => Mass::from_pounds(self.value),
+            MassUnit::Ounce => Mass::from_ounces(self.value),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+enum VolumeUnit {
+    #[serde(rename = "ml")]
+    Millilitre,
+    #[serde(rename = "l")]
+    Litre,
+    #[serde(rename = "tsp")]
+    Teaspoon,
+    #[serde(rename = "tbsp")]
+    Tablespoon,
+    #[serde(rename = "floz")]
+    FluidOunce,
+    #[serde(rename = "cup")]
+    Cup,
+    #[serde(rename = "pt")]
+    Pint,
+    #[serde(rename = "qt")]
+    Quart,
+    #[serde(rename = "gal")]
+    Gallon,
+    #[serde(rename = "bbl")]
+    Barrel,
+    #[serde(rename = "ifloz")]
+    ImperialFluidOunce,
+    #[serde(rename = "ipt")]
+    ImperialPint,
+    #[serde(rename = "iqt")]
+    ImperialQuart,
+    #[serde(rename = "igal")]
+    ImperialGallon,
+    #[serde(rename = "ibbl")]
+    ImperialBarrel,
+}
+
+impl Quantity<VolumeUnit> {
+    fn to_volume(&self) -> Volume {
+        let v = self.value;
+        match self.unit {
+            VolumeUnit::Millilitre => Volume::from_millilitres(v),
+            VolumeUnit::Litre => Volume::from_litres(v),
+            VolumeUnit::Teaspoon => Volume::from_teaspoons(v),
+            VolumeUnit::Tablespoon => Volume::from_tablespoons(v),
+            VolumeUnit::FluidOunce => Volume::from_fluid_ounces(v),
+            VolumeUnit::Cup => Volume::from_cups(v),
+            VolumeUnit::Pint => Volume::from_pints(v),
+            VolumeUnit::Quart => Volume::from_quarts(v),
+            VolumeUnit::Gallon => Volume::from_gallons(v),
+            VolumeUnit::Barrel => Volume::from_gallons(v * 31.),
+            VolumeUnit::ImperialFluidOunce => Volume::from_fluid_ounces_uk(v),
+            VolumeUnit::ImperialPint => Volume::from_pints_uk(v),
+            VolumeUnit::ImperialQuart => Volume::from_pints_uk(v * 2.),
+            VolumeUnit::ImperialGallon => Volume::from_gallons_uk(v),
+            VolumeUnit::ImperialBarrel => Volume::from_gallons_uk(v * 36.),
+        }
+    }
+
+    fn litres(volume: &Volume) -> Self {
+        Self::new(VolumeUnit::Litre, volume.as_litres())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+enum TemperatureUnit {
+    C,
+    F,
+}
+
+impl Quantity<TemperatureUnit> {
+    fn to_temperature(&self) -> Temperature {
+        match self.unit {
+            TemperatureUnit::C => Temperature::from_celsius(self.value),
+            TemperatureUnit::F => Temperature::from_fahrenheit(self.value),
+        }
+    }
+
+    fn celsius(temperature: &Temperature) -> Self {
+        Self::new(TemperatureUnit::C, temperature.as_celsius())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+enum TimeUnit {
+    #[serde(rename = "sec")]
+    Second,
+    #[serde(rename = "min")]
+    Minute,
+    #[serde(rename = "hr")]
+    Hour,
+    #[serde(rename = "day")]
+    Day,
+    #[serde(rename = "week")]
+    Week,
+}
+
+impl Quantity<TimeUnit> {
+    fn as_minutes(&self) -> f64 {
+        match self.unit {
+            TimeUnit::Second => self.value / 60.,
+            TimeUnit::Minute => self.value,
+            TimeUnit::Hour => self.value * 60.,
+            TimeUnit::Day => self.value * 60. * 24.,
+            TimeUnit::Week => self.value * 60. * 24. * 7.,
+        }
+    }
+
+    fn minutes(minutes: u32) -> Self {
+        Self::new(TimeUnit::Minute, minutes as f64)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+enum ColorUnit {
+    #[serde(rename = "EBC")]
+    Ebc,
+    #[serde(rename = "Lovi")]
+    Lovibond,
+    #[serde(rename = "SRM")]
+    Srm,
+}
+
+impl Quantity<ColorUnit> {
+    fn as_srm(&self) -> f64 {
+        match self.unit {
+            ColorUnit::Ebc => ebc_to_srm(self.value),
+            ColorUnit::Lovibond => lovibond_to_srm(self.value),
+            ColorUnit::Srm => self.value,
+        }
+    }
+
+    fn as_lovibond(&self) -> f64 {
+        match self.unit {
+            ColorUnit::Lovibond => self.value,
+            _ => srm_to_lovibond(self.as_srm()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+enum GravityUnit {
+    #[serde(rename = "sg")]
+    SpecificGravity,
+    #[serde(rename = "plato")]
+    Plato,
+    #[serde(rename = "brix")]
+    Brix,
+}
+
+impl Quantity<GravityUnit> {
+    fn to_relative_density(&self) -> RelativeDensity {
+        match self.unit {
+            GravityUnit::SpecificGravity => RelativeDensity::from_specific_gravity(self.value),
+            GravityUnit::Plato => RelativeDensity::from_plato(self.value),
+            GravityUnit::Brix => RelativeDensity::from_brix(self.value),
+        }
+    }
+
+    fn specific_gravity(value: f64) -> Self {
+        Self::new(GravityUnit::SpecificGravity, value)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+enum PercentUnit {
+    #[serde(rename = "%")]
+    Percent,
+}
+
+impl Quantity<PercentUnit> {
+    fn percent(value: f64) -> Self {
+        Self::new(PercentUnit::Percent, value)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+enum BitternessUnit {
+    #[serde(rename = "IBUs")]
+    Ibu,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+enum ConcentrationUnit {
+    #[serde(rename = "ppm")]
+    PartsPerMillion,
+    #[serde(rename = "ppb")]
+    PartsPerBillion,
+    #[serde(rename = "mg/l")]
+    MilligramsPerLitre,
+}
+
+impl Quantity<ConcentrationUnit> {
+    fn as_ppm(&self) -> f64 {
+        match self.unit {
+            ConcentrationUnit::PartsPerBillion => self.value / 1000.,
+            _ => self.value,
+        }
+    }
+
+    fn ppm(value: f64) -> Self {
+        Self::new(ConcentrationUnit::PartsPerMillion, value)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Range<U> {
+    minimum: Quantity<U>,
+    maximum: Quantity<U>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsonDocument {
+    beerjson: JsonBeerJson,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsonBeerJson {
+    version: f64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    recipes: Vec<JsonRecipe>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    styles: Vec<JsonStyle>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum RecipeType {
+    Cider,
+    Kombucha,
+    Mead,
+    Other,
+    Soda,
+    Wine,
+    Extract,
+    #[serde(rename = "partial mash")]
+    PartialMash,
+    #[serde(rename = "all grain")]
+    AllGrain,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsonRecipe {
+    name: String,
+    #[serde(rename = "type")]
+    recipe_type: RecipeType,
+    author: String,
+    batch_size: Quantity<VolumeUnit>,
+    efficiency: JsonEfficiency,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    style: Option<JsonRecipeStyle>,
+    ingredients: JsonIngredients,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    mash: Option<JsonMash>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    boil: Option<JsonBoil>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsonEfficiency {
+    brewhouse: Quantity<PercentUnit>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsonIngredients {
+    fermentable_additions: Vec<JsonFermentableAddition>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    hop_additions: Vec<JsonHopAddition>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    culture_additions: Vec<JsonCultureAddition>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    water_additions: Vec<JsonWaterAddition>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum FermentableType {
+    #[serde(rename = "dry extract")]
+    DryExtract,
+    Extract,
+    Grain,
+    Sugar,
+    Fruit,
+    Juice,
+    Honey,
+    Other,
+}
+
+impl From<FermentableType> for fermentables::FermentableType {
+    fn from(fermentable_type: FermentableType) -> Self {
+        match fermentable_type {
+            FermentableType::DryExtract => fermentables::FermentableType::DryExtract,
+            FermentableType::Extract => fermentables::FermentableType::Extract,
+            FermentableType::Grain => fermentables::FermentableType::Grain,
+            FermentableType::Sugar | FermentableType::Honey => fermentables::FermentableType::Sugar,
+            FermentableType::Fruit | FermentableType::Juice | FermentableType::Other => {
+                fermentables::FermentableType::Adjunct
+            }
+        }
+    }
+}
+
+impl From<fermentables::FermentableType> for FermentableType {
+    fn from(fermentable_type: fermentables::FermentableType) -> Self {
+        match fermentable_type {
+            fermentables::FermentableType::Grain => FermentableType::Grain,
+            fermentables::FermentableType::Extract => FermentableType::Extract,
+            fermentables::FermentableType::DryExtract => FermentableType::DryExtract,
+            fermentables::FermentableType::Sugar => FermentableType::Sugar,
+            // BeerJSON doesn't distinguish unmalted grains
+            fermentables::FermentableType::Adjunct => FermentableType::Grain,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsonFermentableAddition {
+    name: String,
+    #[serde(rename = "type")]
+    fermentable_type: FermentableType,
+    #[serde(rename = "yield")]
+    fermentable_yield: JsonYield,
+    color: Quantity<ColorUnit>,
+    amount: Quantity<MassUnit>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsonYield {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fine_grind: Option<Quantity<PercentUnit>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    potential: Option<Quantity<GravityUnit>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum HopForm {
+    Extract,
+    Leaf,
+    #[serde(rename = "leaf (wet)")]
+    WetLeaf,
+    Pellet,
+    Powder,
+    Plug,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+enum TimingUse {
+    #[serde(rename = "add_to_mash")]
+    Mash,
+    #[serde(rename = "add_to_boil")]
+    Boil,
+    #[serde(rename = "add_to_fermentation")]
+    Fermentation,
+    #[serde(rename = "add_to_package")]
+    Package,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsonTiming {
+    #[serde(rename = "use", default, skip_serializing_if = "Option::is_none")]
+    timing_use: Option<TimingUse>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    time: Option<Quantity<TimeUnit>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    duration: Option<Quantity<TimeUnit>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsonHopAddition {
+    name: String,
+    alpha_acid: Quantity<PercentUnit>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    form: Option<HopForm>,
+    timing: JsonTiming,
+    amount: Quantity<MassUnit>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum CultureType {
+    Ale,
+    Bacteria,
+    Brett,
+    Champagne,
+    Kveik,
+    Lacto,
+    Lager,
+    Malolactic,
+    #[serde(rename = "mixed-culture")]
+    MixedCulture,
+    Other,
+    Pedio,
+    Spontaneous,
+    Wine,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum CultureForm {
+    Liquid,
+    Dry,
+    Slant,
+    Culture,
+    Dregs,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Flocculation {
+    #[serde(rename = "very low")]
+    VeryLow,
+    Low,
+    #[serde(rename = "medium low")]
+    MediumLow,
+    Medium,
+    #[serde(rename = "medium high")]
+    MediumHigh,
+    High,
+    #[serde(rename = "very high")]
+    VeryHigh,
+}
+
+impl From<Flocculation> for Level {
+    fn from(flocculation: Flocculation) -> Self {
+        match flocculation {
+            Flocculation::VeryLow | Flocculation::Low => Level::Low,
+            Flocculation::MediumLow => Level::MedLow,
+            Flocculation::Medium => Level::Medium,
+            Flocculation::MediumHigh => Level::MedHigh,
+            Flocculation::High => Level::High,
+            Flocculation::VeryHigh => Level::VeryHigh,
+        }
+    }
+}
+
+impl From<Level> for Flocculation {
+    fn from(level: Level) -> Self {
+        match level {
+            Level::Low => Flocculation::Low,
+            Level::MedLow => Flocculation::MediumLow,
+            Level::Medium => Flocculation::Medium,
+            Level::MedHigh => Flocculation::MediumHigh,
+            Level::High => Flocculation::High,
+            Level::VeryHigh => Flocculation::VeryHigh,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsonCultureAddition {
+    name: String,
+    #[serde(rename = "type")]
+    culture_type: CultureType,
+    form: CultureForm,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    producer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    product_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    temperature_range: Option<Range<TemperatureUnit>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    attenuation: Option<Quantity<PercentUnit>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    flocculation: Option<Flocculation>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsonWaterAddition {
+    name: String,
+    calcium: Quantity<ConcentrationUnit>,
+    bicarbonate: Quantity<ConcentrationUnit>,
+    sulfate: Quantity<ConcentrationUnit>,
+    chloride: Quantity<ConcentrationUnit>,
+    sodium: Quantity<ConcentrationUnit>,
+    magnesium: Quantity<ConcentrationUnit>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsonMash {
+    name: String,
+    grain_temperature: Quantity<TemperatureUnit>,
+    mash_steps: Vec<JsonMashStep>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum MashStepType {
+    Infusion,
+    Temperature,
+    Decoction,
+    #[serde(rename = "souring mash")]
+    SouringMash,
+    #[serde(rename = "souring wort")]
+    SouringWort,
+    Drain,
+    Sparge,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsonMashStep {
+    name: String,
+    #[serde(rename = "type")]
+    step_type: MashStepType,
+    step_temperature: Quantity<TemperatureUnit>,
+    step_time: Quantity<TimeUnit>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsonBoil {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pre_boil_size: Option<Quantity<VolumeUnit>>,
+    boil_time: Quantity<TimeUnit>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum StyleType {
+    Beer,
+    Cider,
+    Kombucha,
+    Mead,
+    Other,
+    Soda,
+    Wine,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsonRecipeStyle {
+    name: String,
+    category: String,
+    style_guide: String,
+    #[serde(rename = "type")]
+    style_type: StyleType,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsonStyle {
+    name: String,
+    category: String,
+    style_guide: String,
+    #[serde(rename = "type")]
+    style_type: StyleType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    original_gravity: Option<Range<GravityUnit>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    final_gravity: Option<Range<GravityUnit>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    international_bitterness_units: Option<Range<BitternessUnit>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    color: Option<Range<ColorUnit>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    alcohol_by_volume: Option<Range<PercentUnit>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    notes: Option<String>,
+}
+
+impl JsonRecipe {
+    fn into_recipe(self, styles: &[JsonStyle]) -> Recipe {
+        let batch_size = self.batch_size.to_volume();
+        let boil_size = self
+            .boil
+            .as_ref()
+            .and_then(|b| b.pre_boil_size.as_ref())
+            .map_or(batch_size, |s| s.to_volume());
+
+        let mut recipe = Recipe::new(
+            self.name,
+            batch_size,
+            boil_size,
+            self.efficiency.brewhouse.value / 100.,
+        );
+        if let Some(boil) = &self.boil {
+            recipe.boil_time_mins = boil.boil_time.as_minutes().round() as u32;
+        }
+        recipe.style = self.style.and_then(|recipe_style| {
+            match styles.iter().find(|s| s.name == recipe_style.name) {
+                Some(style) => Some(BeerStyle::from(style)),
+                None => BEER_STYLES
+                    .iter()
+                    .find(|s| s.name.eq_ignore_ascii_case(&recipe_style.name))
+                    .cloned(),
+            }
+        });
+
+        let ingredients = self.ingredients;
+        recipe.fermentables = ingredients
+            .fermentable_additions
+            .into_iter()
+            .map(FermentableAddition::from)
+            .collect();
+        recipe.hops = ingredients
+            .hop_additions
+            .into_iter()
+            .map(|hop| hop.into_hop_addition(recipe.boil_time_mins))
+            .collect();
+        recipe.yeast = ingredients
+            .culture_additions
+            .into_iter()
+            .next()
+            .map(Yeast::from);
+        recipe.water = ingredients
+            .water_additions
+            .into_iter()
+            .next()
+            .map(WaterProfile::from);
+        if let Some(mash) = self.mash {
+            recipe.mash_steps = mash.mash_steps.into_iter().map(MashStep::from).collect();
+        }
+        recipe
+    }
+}
+
+impl From<&Recipe> for JsonRecipe {
+    fn from(recipe: &Recipe) -> Self {
+        JsonRecipe {
+            name: recipe.name.clone(),
+            recipe_type: RecipeType::AllGrain,
+            author: String::new(),
+            batch_size: Quantity::litres(&recipe.batch_size),
+            efficiency: JsonEfficiency {
+                brewhouse: Quantity::percent(recipe.efficiency * 100.),
+            },
+            style: recipe.style.as_ref().map(|s| JsonRecipeStyle {
+                name: s.name.clone(),
+                category: String::new(),
+                style_guide: String::new(),
+                style_type: StyleType::Beer,
+            }),
+            ingredients: JsonIngredients {
+                fermentable_additions: recipe
+                    .fermentables
+                    .iter()
+                    .map(JsonFermentableAddition::from)
+                    .collect(),
+                hop_additions: recipe
+                    .hops
+                    .iter()
+                    .map(|hop| JsonHopAddition::new(hop, recipe.boil_time_mins))
+                    .collect(),
+                culture_additions: recipe.yeast.iter().map(JsonCultureAddition::from).collect(),
+                water_additions: recipe.water.iter().map(JsonWaterAddition::from).collect(),
+            },
+            mash: match recipe.mash_steps.is_empty() {
+                true => None,
+                false => Some(JsonMash {
+                    name: recipe.name.clone(),
+                    grain_temperature: Quantity::new(TemperatureUnit::C, 20.),
+                    mash_steps: recipe.mash_steps.iter().map(JsonMashStep::from).collect(),
+                }),
+            },
+            boil: Some(JsonBoil {
+                pre_boil_size: Some(Quantity::litres(&recipe.boil_size)),
+                boil_time: Quantity::minutes(recipe.boil_time_mins),
+            }),
+        }
+    }
+}
+
+impl From<JsonFermentableAddition> for FermentableAddition {
+    fn from(json: JsonFermentableAddition) -> Self {
+        let potential = match (
+            json.fermentable_yield.potential,
+            json.fermentable_yield.fine_grind,
+        ) {
+            (Some(potential), _) => potential.to_relative_density(),
+            // Fine grind yield is percentage of sucrose potential
+            (None, Some(fine_grind)) => RelativeDensity::from_specific_gravity(
+                1. + fine_grind.value / 100. * SUCROSE_PPG / 1000.,
+            ),
+            (None, None) => RelativeDensity::from_specific_gravity(1.),
+        };
+        let mut fermentable = FermentableAddition::new(
+            json.name,
+            json.amount.to_mass(),
+            potential,
+            json.color.as_lovibond(),
+        );
+        fermentable.fermentable_type = json.fermentable_type.into();
+        fermentable
+    }
+}
+
+impl From<&FermentableAddition> for JsonFermentableAddition {
+    fn from(fermentable: &FermentableAddition) -> Self {
+        JsonFermentableAddition {
+            name: fermentable.name.clone(),
+            fermentable_type: fermentable.fermentable_type.into(),
+            fermentable_yield: JsonYield {
+                fine_grind: None,
+                potential: Some(Quantity::specific_gravity(
+                    fermentable.potential.as_specific_gravity(),
+                )),
+            },
+            color: Quantity::new(ColorUnit::Lovibond, fermentable.color),
+            amount: Quantity::new(MassUnit::Kilogram, fermentable.weight.as_kilograms()),
+        }
+    }
+}
+
+impl JsonHopAddition {
+    /// Reads the hop addition of a recipe with a boil of `boil_time_mins`
+    fn into_hop_addition(self, boil_time_mins: u32) -> HopAddition {
+        let duration = self.timing.duration.as_ref().map_or(0., |d| d.as_minutes());
+        let time = self.timing.time.as_ref().map(|t| t.as_minutes());
+        // Boil additions giving only the time they're added at boil until
+        // the end of the boil
+        let boil_mins = match (&self.timing.duration, time) {
+            (None, Some(time)) => (boil_time_mins as f64 - time).max(0.),
+            _ => duration,
+        };
+        let (time_mins, hop_use) = match self.timing.timing_use {
+            // Hops added after the boil aren't boiled
+            Some(TimingUse::Fermentation) | Some(TimingUse::Package) => (
+                0,
+                HopUse::DryHop {
+                    days: (duration / MINUTES_IN_DAY).round() as u32,
+                },
+            ),
+            Some(TimingUse::Mash) => (
+                0,
+                HopUse::Mash {
+                    time_mins: duration.round() as u32,
+                },
+            ),
+            // Hops added once the boil is over steep for their duration
+            _ if boil_time_mins > 0 && time.is_some_and(|t| t >= boil_time_mins as f64) => (
+                0,
+                HopUse::Whirlpool {
+                    temperature: Temperature::from_celsius(AROMA_TEMPERATURE),
+                    time_mins: duration.round() as u32,
+                },
+            ),
+            _ => (boil_mins.round() as u32, HopUse::Boil),
+        };
+        let hop_type = match self.form {
+            Some(HopForm::Pellet) | Some(HopForm::Powder) => HopAdditionType::Pellet,
+            Some(HopForm::Plug) => HopAdditionType::Plug,
+            _ => HopAdditionType::Whole,
+        };
+        let mut hop = HopAddition::new(
+            self.amount.to_mass(),
+            self.alpha_acid.value / 100.,
+            time_mins,
+            hop_type,
+        );
+        hop.name = Some(self.name);
+        hop.hop_use = hop_use;
+        hop
+    }
+
+    /// Writes the hop addition of a recipe with a boil of `boil_time_mins`
+    fn new(hop: &HopAddition, boil_time_mins: u32) -> Self {
+        JsonHopAddition {
+            name: hop.name.clone().unwrap_or_default(),
+            alpha_acid: Quantity::percent(hop.alpha_acid_percentage * 100.),
+            form: Some(match hop.hop_type {
+                HopAdditionType::Whole => HopForm::Leaf,
+                HopAdditionType::Plug => HopForm::Plug,
+                HopAdditionType::Pellet => HopForm::Pellet,
+            }),
+            // BeerJSON has no first wort hopping or whirlpool temperature.
+            // First wort hops are added at the start of the boil, whirlpool
+            // hops at the end of the boil and steep for their duration.
+            timing: match hop.hop_use {
+                HopUse::Boil => JsonTiming {
+                    timing_use: Some(TimingUse::Boil),
+                    time: None,
+                    duration: Some(Quantity::minutes(hop.time_mins)),
+                },
+                HopUse::Mash { time_mins } => JsonTiming {
+                    timing_use: Some(TimingUse::Mash),
+                    time: None,
+                    duration: Some(Quantity::minutes(time_mins)),
+                },
+                HopUse::FirstWort => JsonTiming {
+                    timing_use: Some(TimingUse::Boil),
+                    time: Some(Quantity::minutes(0)),
+                    duration: Some(Quantity::minutes(boil_time_mins)),
+                },
+                HopUse::Whirlpool { time_mins, .. } => JsonTiming {
+                    timing_use: Some(TimingUse::Boil),
+                    time: Some(Quantity::minutes(boil_time_mins)),
+                    duration: Some(Quantity::minutes(time_mins)),
+                },
+                HopUse::DryHop { days } => JsonTiming {
+                    timing_use: Some(TimingUse::Fermentation),
+                    time: None,
+                    duration: Some(Quantity::new(TimeUnit::Day, days as f64)),
+                },
+            },
+            amount: Quantity::new(MassUnit::Gram, hop.weight.as_grams()),
+        }
+    }
+}
+
+impl From<JsonCultureAddition> for Yeast {
+    fn from(json: JsonCultureAddition) -> Self {
+        let attenuation = json.attenuation.map(|a| a.value.round() as u8);
+        Yeast {
+            company: json.producer.unwrap_or_default(),
+            name: json.name,
+            id: json.product_id,
+            form: match json.form {
+                CultureForm::Dry => YeastForm::Dry,
+                CultureForm::Liquid | CultureForm::Slant => YeastForm::Liquid,
+                CultureForm::Culture | CultureForm::Dregs => YeastForm::Slurry,
+            },
+            min_attenuation: attenuation,
+            max_attenuation: attenuation,
+            flocculation: json.flocculation.map(Level::from),
+            min_temp: json
+                .temperature_range
+                .as_ref()
+                .map(|r| r.minimum.to_temperature()),
+            max_temp: json
+                .temperature_range
+                .as_ref()
+                .map(|r| r.maximum.to_temperature()),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<&Yeast> for JsonCultureAddition {
+    fn from(yeast: &Yeast) -> Self {
+        JsonCultureAddition {
+            name: yeast.name.clone(),
+            culture_type: CultureType::Ale,
+            form: match yeast.form {
+                YeastForm::Dry => CultureForm::Dry,
+                YeastForm::Liquid => CultureForm::Liquid,
+                YeastForm::Slurry => CultureForm::Culture,
+            },
+            producer: Some(yeast.company.clone()),
+            product_id: yeast.id.clone(),
+            temperature_range: match (yeast.min_temp, yeast.max_temp) {
+                (Some(min), Some(max)) => Some(Range {
+                    minimum: Quantity::celsius(&min),
+                    maximum: Quantity::celsius(&max),
+                }),
+                _ => None,
+            },
+            attenuation: yeast
+                .average_attenuation()
+                .map(|a| Quantity::percent(a as f64)),
+            flocculation: yeast.flocculation.map(Flocculation::from),
+        }
+    }
+}
+
+impl From<JsonWaterAddition> for WaterProfile {
+    fn from(json: JsonWaterAddition) -> Self {
+        WaterProfile {
+            name: json.name,
+            calcium: json.calcium.as_ppm(),
+            magnesium: json.magnesium.as_ppm(),
+            sodium: json.sodium.as_ppm(),
+            chloride: json.chloride.as_ppm(),
+            sulfate: json.sulfate.as_ppm(),
+            bicarbonate: json.bicarbonate.as_ppm(),
+        }
+    }
+}
+
+impl From<&WaterProfile> for JsonWaterAddition {
+    fn from(water: &WaterProfile) -> Self {
+        JsonWaterAddition {
+            name: water.name.clone(),
+            calcium: Quantity::ppm(water.calcium),
+            bicarbonate: Quantity::ppm(water.bicarbonate),
+            sulfate: Quantity::ppm(water.sulfate),
+            chloride: Quantity::ppm(water.chloride),
+            sodium: Quantity::ppm(water.sodium),
+            magnesium: Quantity::ppm(water.magnesium),
+        }
+    }
+}
+
+impl From<JsonMashStep> for MashStep {
+    fn from(json: JsonMashStep) -> Self {
+        MashStep::new(
+            json.name,
+            json.step_temperature.to_temperature(),
+            json.step_time.as_minutes().round() as u32,
+        )
+    }
+}
+
+impl From<&MashStep> for JsonMashStep {
+    fn from(step: &MashStep) -> Self {
+        JsonMashStep {
+            name: step.name.clone(),
+            step_type: MashStepType::Infusion,
+            step_temperature: Quantity::celsius(&step.temperature),
+            step_time: Quantity::minutes(step.time_mins),
+        }
+    }
+}
+
+impl From<&JsonStyle> for BeerStyle {
+    fn from(json: &JsonStyle) -> Self {
+        let sg = |r: &Option<Range<GravityUnit>>| {
+            r.as_ref().map_or((0., 0.), |r| {
+                (
+                    r.minimum.to_relative_density().as_specific_gravity() as f32,
+                    r.maximum.to_relative_density().as_specific_gravity() as f32,
+                )
+            })
+        };
+        let (og_min, og_max) = sg(&json.original_gravity);
+        let (fg_min, fg_max) = sg(&json.final_gravity);
+        let (ibu_min, ibu_max) = json
+            .international_bitterness_units
+            .as_ref()
+            .map_or((0, 0), |r| {
+                (r.minimum.value.round() as u8, r.maximum.value.round() as u8)
+            });
+        let (color_min, color_max) = json.color.as_ref().map_or((0., 0.), |r| {
+            (r.minimum.as_srm() as f32, r.maximum.as_srm() as f32)
+        });
+        let (abv_min, abv_max) = json.alcohol_by_volume.as_ref().map_or((0., 0.), |r| {
+            (r.minimum.value as f32, r.maximum.value as f32)
+        });
+
+        BeerStyle {
+            name: json.name.clone(),
+            original_gravity_min: og_min,
+            original_gravity_max: og_max,
+            final_gravity_min: fg_min,
+            final_gravity_max: fg_max,
+            abv_min,
+            abv_max,
+            ibu_min,
+            ibu_max,
+            color_srm_min: color_min,
+            color_srm_max: color_max,
+            description: json.notes.clone().unwrap_or_default(),
+        }
+    }
+}
+
+impl From<&BeerStyle> for JsonStyle {
+    fn from(style: &BeerStyle) -> Self {
+        let range = |min: f32, max: f32| Range {
+            minimum: Quantity::specific_gravity(min as f64),
+            maximum: Quantity::specific_gravity(max as f64),
+        };
+        JsonStyle {
+            name: style.name.clone(),
+            category: String::new(),
+            style_guide: String::new(),
+            style_type: StyleType::Beer,
+            original_gravity: Some(range(
+                style.original_gravity_min,
+                style.original_gravity_max,
+            )),
+            final_gravity: Some(range(style.final_gravity_min, style.final_gravity_max)),
+            international_bitterness_units: Some(Range {
+                minimum: Quantity::new(BitternessUnit::Ibu, style.ibu_min as f64),
+                maximum: Quantity::new(BitternessUnit::Ibu, style.ibu_max as f64),
+            }),
+            color: Some(Range {
+                minimum: Quantity::new(ColorUnit::Srm, style.color_srm_min as f64),
+                maximum: Quantity::new(ColorUnit::Srm, style.color_srm_max as f64),
+            }),
+            alcohol_by_volume: Some(Range {
+                minimum: Quantity::percent(style.abv_min as f64),
+                maximum: Quantity::percent(style.abv_max as f64),
+            }),
+            notes: Some(style.description.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::assert_approx;
+
+    static AMERICAN_PALE_ALE_JSON: &str = include_str!("test_data/beerjson/american_pale_ale.json");
+    static INVALID_JSON: &str = include_str!("test_data/beerjson/invalid.json");
+
+    #[test]
+    fn read_recipe() {
+        let recipes = from_str(AMERICAN_PALE_ALE_JSON).unwrap();
+        assert_eq!(1, recipes.len());
+
+        let recipe = &recipes[0];
+        assert_eq!("American Pale Ale", recipe.name);
+        assert_approx!(5., recipe.batch_size.as_gallons());
+        assert_approx!(6.5, recipe.boil_size.as_gallons());
+        assert_eq!(60, recipe.boil_time_mins);
+        assert_approx!(0.7, recipe.efficiency);
+
+        assert_eq!(3, recipe.fermentables.len());
+        assert_approx!(10., recipe.fermentables[0].weight.as_pounds());
+        assert_approx!(
+            1.036,
+            recipe.fermentables[0].potential.as_specific_gravity()
+        );
+        assert_approx!(1.8, recipe.fermentables[0].color);
+        // EBC converted to Lovibond
+        assert_approx!(20.2346, recipe.fermentables[1].color);
+        // Fine grind yield converted to potential
+        assert_approx!(
+            1.0342,
+            recipe.fermentables[2].potential.as_specific_gravity()
+        );
+
+        assert_eq!(3, recipe.hops.len());
+        assert_eq!(Some("Cascade".to_owned()), recipe.hops[0].name);
+        assert_approx!(1., recipe.hops[0].weight.as_ounces());
+        assert_approx!(0.055, recipe.hops[0].alpha_acid_percentage);
+        assert_eq!(60, recipe.hops[0].time_mins);
+        assert_eq!(HopAdditionType::Pellet, recipe.hops[0].hop_type);
+        assert_eq!(HopAdditionType::Whole, recipe.hops[1].hop_type);
+        // Dry hops aren't boiled
+        assert_eq!(0, recipe.hops[2].time_mins);
+        assert_eq!(HopUse::DryHop { days: 5 }, recipe.hops[2].hop_use);
+
+        let yeast = recipe.yeast.as_ref().unwrap();
+        assert_eq!("American Ale", yeast.name);
+        assert_eq!("Wyeast", yeast.company);
+        assert_eq!(Some(75), yeast.average_attenuation());
+        assert_eq!(Some(Level::MedLow), yeast.flocculation);
+        assert_approx!(60., yeast.min_temp.unwrap().as_fahrenheit());
+
+        let water = recipe.water.as_ref().unwrap();
+        assert_approx!(50., water.calcium);
+        assert_approx!(0.1, water.sodium);
+
+        assert_eq!(2, recipe.mash_steps.len());
+        assert_approx!(66.6667, recipe.mash_steps[0].temperature.as_celsius());
+        assert_eq!(60, recipe.mash_steps[0].time_mins);
+
+        let style = recipe.style.as_ref().unwrap();
+        assert_eq!("American Pale Ale", style.name);
+        assert_eq!(30, style.ibu_min);
+        assert_approx!(1.045, style.original_gravity_min as f64, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn style_from_known_styles() {
+        let mut json: serde_json::Value = serde_json::from_str(AMERICAN_PALE_ALE_JSON).unwrap();
+        json["beerjson"].as_object_mut().unwrap().remove("styles");
+        let recipes = from_str(&json.to_string()).unwrap();
+        let style = recipes[0].style.as_ref().unwrap();
+        assert_eq!("American Pale Ale", style.name);
+    }
+
+    #[test]
+    fn round_trip() {
+        let recipes = from_str(AMERICAN_PALE_ALE_JSON).unwrap();
+        let exported = to_string(&recipes).unwrap();
+        assert!(validate(&exported).is_empty());
+
+        let imported = from_str(&exported).unwrap();
+        assert_eq!(recipes.len(), imported.len());
+        let (original, read) = (&recipes[0], &imported[0]);
+        assert_eq!(original.name, read.name);
+        assert_approx!(original.batch_size.as_litres(), read.batch_size.as_litres());
+        assert_approx!(original.boil_size.as_litres(), read.boil_size.as_litres());
+        assert_eq!(original.hops.len(), read.hops.len());
+        assert_eq!(original.hops[2].hop_use, read.hops[2].hop_use);
+        assert_eq!(original.fermentables.len(), read.fermentables.len());
+        assert_eq!(original.mash_steps.len(), read.mash_steps.len());
+        assert_eq!(original.water, read.water);
+        assert_eq!(
+            original.yeast.as_ref().map(|y| &y.name),
+            read.yeast.as_ref().map(|y| &y.name)
+        );
+        assert_eq!(
+            original.style.as_ref().map(|s| s.ibu_max),
+            read.style.as_ref().map(|s| s.ibu_max)
+        );
+        assert_approx!(
+            original.og().as_specific_gravity(),
+            read.og().as_specific_gravity()
+        );
+        assert_approx!(
+            original.fg().as_specific_gravity(),
+            read.fg().as_specific_gravity()
+        );
+        assert_approx!(original.ibu(), read.ibu());
+        assert_approx!(original.color(), read.color());
+    }
+
+    #[test]
+    fn round_trip_hop_uses() {
+        let mut recipes = from_str(AMERICAN_PALE_ALE_JSON).unwrap();
+        let recipe = &mut recipes[0];
+        recipe.hops[0].time_mins = 0;
+        recipe.hops[0].hop_use = HopUse::Mash { time_mins: 60 };
+        recipe.hops[1] = HopAddition::whirlpool(
+            Mass::from_grams(28.),
+            0.12,
+            Temperature::from_celsius(AROMA_TEMPERATURE),
+            20,
+            HopAdditionType::Pellet,
+        );
+
+        let exported = to_string(&recipes).unwrap();
+        assert!(validate(&exported).is_empty());
+
+        let read = from_str(&exported).unwrap();
+        let (original, read) = (&recipes[0], &read[0]);
+        for (original, read) in original.hops.iter().zip(&read.hops) {
+            assert_eq!(original.hop_use, read.hop_use);
+            assert_eq!(original.time_mins, read.time_mins);
+        }
+        assert_approx!(original.ibu(), read.ibu());
+    }
+
+    #[test]
+    fn first_wort_hops_boil_for_whole_boil() {
+        let mut recipes = from_str(AMERICAN_PALE_ALE_JSON).unwrap();
+        recipes[0].hops[1].hop_use = HopUse::FirstWort;
+
+        let read = from_str(&to_string(&recipes).unwrap()).unwrap();
+        let hop = &read[0].hops[1];
+        assert_eq!(HopUse::Boil, hop.hop_use);
+        assert_eq!(recipes[0].boil_time_mins, hop.time_mins);
+    }
+
+    #[test]
+    fn boil_time_from_addition_time() {
+        let json = AMERICAN_PALE_ALE_JSON.replace(
+            r#""duration": { "unit": "min", "value": 15 }"#,
+            r#""time": { "unit": "min", "value": 45 }"#,
+        );
+        let recipes = from_str(&json).unwrap();
+        let hop = &recipes[0].hops[1];
+        assert_eq!(HopUse::Boil, hop.hop_use);
+        assert_eq!(15, hop.time_mins);
+        assert_approx!(
+            from_str(AMERICAN_PALE_ALE_JSON).unwrap()[0].ibu(),
+            recipes[0].ibu()
+        );
+    }
+
+    #[test]
+    fn structural_error_path() {
+        let errors = validate(INVALID_JSON);
+        assert_eq!(1, errors.len());
+        assert_eq!(
+            "beerjson.recipes[0].ingredients.hop_additions[0].timing.use",
+            errors[0].path
+        );
+
+        let error =
+            from_str(&AMERICAN_PALE_ALE_JSON.replace("\"gal\"", "\"gallons\"")).unwrap_err();
+        assert_eq!("beerjson.recipes[0].batch_size.unit", error.path);
+
+        let error =
+            from_str(r#"{"beerjson": {"version": 1.0, "recipes": [{"name": "x"}]}}"#).unwrap_err();
+        assert_eq!("beerjson.recipes[0]", error.path);
+        assert!(error.message.contains("missing field"));
+    }
+
+    #[test]
+    fn value_error_paths() {
+        let json = AMERICAN_PALE_ALE_JSON
+            .replace("\"value\": 5.5", "\"value\": -5.5")
+            .replace("\"value\": 70", "\"value\": 170")
+            .replace("\"version\": 1.0", "\"version\": 2.0");
+        let errors = validate(&json);
+        let paths: Vec<&str> = errors.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(
+            vec![
+                "beerjson.version",
+                "beerjson.recipes[0].efficiency.brewhouse.value",
+                "beerjson.recipes[0].ingredients.hop_additions[0].alpha_acid.value",
+            ],
+            paths
+        );
+        assert_eq!(
+            "beerjson.version: only version 1.0 is supported",
+            errors[0].to_string()
+        );
+        assert!(from_str(&json).is_err());
+    }
+
+    #[test]
+    fn unknown_property_path() {
+        let errors = validate(
+            &AMERICAN_PALE_ALE_JSON
+                .replace(r#""form": "leaf","#, r#""form": "leaf", "year": 2020,"#),
+        );
+        assert_eq!(1, errors.len());
+        assert_eq!(
+            "beerjson.recipes[0].ingredients.hop_additions[1].year",
+            errors[0].path
+        );
+        assert!(errors[0].message.contains("unknown field"));
+    }
+
+    #[test]
+    fn nested_range_paths() {
+        let json = AMERICAN_PALE_ALE_JSON
+            .replace(
+                r#""duration": { "unit": "min", "value": 15 }"#,
+                r#""duration": { "unit": "min", "value": -15 }"#,
+            )
+            .replace(
+                r#""magnesium": { "unit": "ppm", "value": 8 }"#,
+                r#""magnesium": { "unit": "ppm", "value": -8 }"#,
+            )
+            .replace(
+                r#""maximum": { "unit": "IBUs", "value": 50 }"#,
+                r#""maximum": { "unit": "IBUs", "value": 20 }"#,
+            );
+        let errors = validate(&json);
+        let paths: Vec<&str> = errors.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(
+            vec![
+                "beerjson.recipes[0].ingredients.hop_additions[1].timing.duration.value",
+                "beerjson.recipes[0].ingredients.water_additions[0].magnesium.value",
+                "beerjson.styles[0].international_bitterness_units",
+            ],
+            paths
+        );
+    }
+
+    #[test]
+    fn valid_document() {
+        assert!(validate(AMERICAN_PALE_ALE_JSON).is_empty());
+    }
+}
@@ -0,0 +1,960 @@
+//! Reading and writing recipes in BeerXML 1.0 format used by
+//! for example BeerSmith and Brewfather.
+//!
+//! Only the records that map onto `rustybeer` types are read: HOP,
+//! FERMENTABLE, YEAST, WATER, MASH and STYLE. Other records are ignored.
+//!
+//! See: http://www.beerxml.com/beerxml.htm
+//!
+//! # Example
+//! ```
+//! use rustybeer::beerxml;
+//!
+//! let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+//! <RECIPES>
+//!   <RECIPE>
+//!     <NAME>SMaSH</NAME>
+//!     <VERSION>1</VERSION>
+//!     <TYPE>All Grain</TYPE>
+//!     <BREWER>Brewer</BREWER>
+//!     <BATCH_SIZE>20</BATCH_SIZE>
+//!     <BOIL_SIZE>25</BOIL_SIZE>
+//!     <BOIL_TIME>60</BOIL_TIME>
+//!     <EFFICIENCY>72</EFFICIENCY>
+//!     <HOPS>
+//!       <HOP>
+//!         <NAME>Cascade</NAME>
+//!         <VERSION>1</VERSION>
+//!         <ALPHA>6.4</ALPHA>
+//!         <AMOUNT>0.028</AMOUNT>
+//!         <USE>Boil</USE>
+//!         <TIME>60</TIME>
+//!       </HOP>
+//!     </HOPS>
+//!   </RECIPE>
+//! </RECIPES>"#;
+//!
+//! let recipes = beerxml::from_str(xml).unwrap();
+//! assert_eq!("SMaSH", recipes[0].name);
+//! assert_eq!(Some("Cascade".to_owned()), recipes[0].hops[0].name);
+//!
+//! let exported = beerxml::to_string(&recipes).unwrap();
+//! assert_eq!(recipes[0].name, beerxml::from_str(&exported).unwrap()[0].name);
+//! ```
+
+use crate::beer_styles::BeerStyle;
+use crate::calculators::abv::calculate_abv;
+use crate::calculators::ibu::{HopAddition, HopAdditionType, HopUse};
+use crate::calculators::mash::MashStep;
+use crate::calculators::og::FermentableAddition;
+use crate::conversions::RelativeDensity;
+use crate::fermentables::{FermentableType, SUCROSE_PPG};
+use crate::recipe::Recipe;
+use crate::water::WaterProfile;
+use crate::yeasts::{Level, Yeast, YeastForm};
+use measurements::{Mass, Temperature, Volume};
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use std::fmt;
+
+/// BeerXML and BeerJSON don't record the temperature of aroma hop steeps, so
+/// assume the hops are steeped in wort just off the boil (°C)
+pub(crate) const AROMA_TEMPERATURE: f64 = 90.;
+
+const MINUTES_IN_DAY: f64 = 24. * 60.;
+
+const XML_DECLARATION: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n";
+
+/// Error returned when BeerXML can't be read or written
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BeerXmlError(String);
+
+impl fmt::Display for BeerXmlError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid BeerXML: {}", self.0)
+    }
+}
+
+impl std::error::Error for BeerXmlError {}
+
+/// Reads all recipes from BeerXML document
+///
+/// All Grain and Partial Mash recipes must give their EFFICIENCY, as
+/// required by BeerXML. Extract recipes without it use 100%, so steeped
+/// grains give all their extract.
+pub fn from_str(xml: &str) -> Result<Vec<Recipe>, BeerXmlError> {
+    let recipes: XmlRecipes =
+        quick_xml::de::from_str(xml).map_err(|e| BeerXmlError(e.to_string()))?;
+    recipes.recipe.into_iter().map(Recipe::try_from).collect()
+}
+
+/// Reads all recipes from BeerXML file contents
+///
+/// The bytes are decoded as ISO-8859-1 when the XML declaration says so,
+/// which BeerSmith and other older tools do. Documents declaring any other
+/// encoding are read as UTF-8, falling back to ISO-8859-1 if they aren't
+/// valid UTF-8.
+pub fn from_bytes(bytes: &[u8]) -> Result<Vec<Recipe>, BeerXmlError> {
+    let latin1 = |bytes: &[u8]| bytes.iter().map(|&b| b as char).collect::<String>();
+    let xml = match declared_encoding(bytes).as_deref() {
+        Some("iso-8859-1" | "latin1" | "latin-1") => latin1(bytes),
+        _ => match std::str::from_utf8(bytes) {
+            Ok(xml) => xml.to_owned(),
+            Err(_) => latin1(bytes),
+        },
+    };
+    from_str(&xml)
+}
+
+/// Encoding from the XML declaration in lowercase, if any
+fn declared_encoding(bytes: &[u8]) -> Option<String> {
+    let end = bytes.windows(2).position(|w| w == b"?>")?;
+    let declaration = std::str::from_utf8(&bytes[..end]).ok()?;
+    if !declaration.trim_start().starts_with("<?xml") {
+        return None;
+    }
+    let value = declaration.split("encoding").nth(1)?;
+    let value = value.trim_start().strip_prefix('=')?.trim_start();
+    let quote = value.chars().next()?;
+    let encoding = value[1..].split(quote).next()?;
+    Some(encoding.to_ascii_lowercase())
+}
+
+/// Writes recipes as BeerXML document
+pub fn to_string(recipes: &[Recipe]) -> Result<String, BeerXmlError> {
+    let document = XmlRecipes {
+        recipe: recipes.iter().map(XmlRecipe::from).collect(),
+    };
+
+    let mut xml = String::from(XML_DECLARATION);
+    let mut serializer = quick_xml::se::Serializer::new(&mut xml);
+    serializer.indent(' ', 2);
+    document
+        .serialize(serializer)
+        .map_err(|e| BeerXmlError(e.to_string()))?;
+    Ok(xml)
+}
+
+fn version() -> u32 {
+    1
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename = "RECIPES")]
+struct XmlRecipes {
+    #[serde(rename = "RECIPE", default)]
+    recipe: Vec<XmlRecipe>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+struct XmlRecipe {
+    name: String,
+    #[serde(default = "version")]
+    version: u32,
+    #[serde(rename = "TYPE", default)]
+    recipe_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    style: Option<XmlStyle>,
+    #[serde(default)]
+    brewer: String,
+    batch_size: f64,
+    boil_size: f64,
+    boil_time: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    efficiency: Option<f64>,
+    #[serde(default)]
+    hops: XmlHops,
+    #[serde(default)]
+    fermentables: XmlFermentables,
+    #[serde(default)]
+    yeasts: XmlYeasts,
+    #[serde(default)]
+    waters: XmlWaters,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mash: Option<XmlMash>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct XmlHops {
+    #[serde(rename = "HOP", default)]
+    hop: Vec<XmlHop>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+struct XmlHop {
+    name: String,
+    #[serde(default = "version")]
+    version: u32,
+    alpha: f64,
+    amount: f64,
+    #[serde(rename = "USE", default)]
+    hop_use: String,
+    time: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    form: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct XmlFermentables {
+    #[serde(rename = "FERMENTABLE", default)]
+    fermentable: Vec<XmlFermentable>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+struct XmlFermentable {
+    name: String,
+    #[serde(default = "version")]
+    version: u32,
+    #[serde(rename = "TYPE", default)]
+    fermentable_type: String,
+    amount: f64,
+    #[serde(rename = "YIELD")]
+    fermentable_yield: f64,
+    color: f64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct XmlYeasts {
+    #[serde(rename = "YEAST", default)]
+    yeast: Vec<XmlYeast>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+struct XmlYeast {
+    name: String,
+    #[serde(default = "version")]
+    version: u32,
+    /// Ale, Lager and so on, which `Yeast` doesn't know, so it isn't written
+    #[serde(rename = "TYPE", default, skip_serializing_if = "Option::is_none")]
+    yeast_type: Option<String>,
+    #[serde(default)]
+    form: String,
+    #[serde(default)]
+    amount: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    laboratory: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    product_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_temperature: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_temperature: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    flocculation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    attenuation: Option<f64>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct XmlWaters {
+    #[serde(rename = "WATER", default)]
+    water: Vec<XmlWater>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+struct XmlWater {
+    name: String,
+    #[serde(default = "version")]
+    version: u32,
+    #[serde(default)]
+    amount: f64,
+    calcium: f64,
+    bicarbonate: f64,
+    sulfate: f64,
+    chloride: f64,
+    sodium: f64,
+    magnesium: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+struct XmlMash {
+    #[serde(default)]
+    name: String,
+    #[serde(default = "version")]
+    version: u32,
+    #[serde(default)]
+    grain_temp: f64,
+    #[serde(default)]
+    mash_steps: XmlMashSteps,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct XmlMashSteps {
+    #[serde(rename = "MASH_STEP", default)]
+    mash_step: Vec<XmlMashStep>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+struct XmlMashStep {
+    name: String,
+    #[serde(default = "version")]
+    version: u32,
+    #[serde(rename = "TYPE", default)]
+    step_type: String,
+    step_temp: f64,
+    step_time: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+struct XmlStyle {
+    name: String,
+    #[serde(default)]
+    category: String,
+    #[serde(default = "version")]
+    version: u32,
+    #[serde(default)]
+    category_number: String,
+    #[serde(default)]
+    style_letter: String,
+    #[serde(default)]
+    style_guide: String,
+    /// Ale, Lager and so on, which `BeerStyle` doesn't know, so it isn't written
+    #[serde(rename = "TYPE", default, skip_serializing_if = "Option::is_none")]
+    style_type: Option<String>,
+    og_min: f32,
+    og_max: f32,
+    fg_min: f32,
+    fg_max: f32,
+    ibu_min: f32,
+    ibu_max: f32,
+    color_min: f32,
+    color_max: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    abv_min: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    abv_max: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    notes: Option<String>,
+}
+
+fn potential_from_yield(fermentable_yield: f64) -> RelativeDensity {
+    RelativeDensity::from_specific_gravity(1. + fermentable_yield / 100. * SUCROSE_PPG / 1000.)
+}
+
+fn yield_from_potential(potential: &RelativeDensity) -> f64 {
+    (potential.as_specific_gravity() - 1.) * 1000. / SUCROSE_PPG * 100.
+}
+
+fn style_abv(og: f32, fg: f32) -> f32 {
+    calculate_abv(
+        &RelativeDensity::from_specific_gravity(og as f64),
+        &RelativeDensity::from_specific_gravity(fg as f64),
+    ) as f32
+}
+
+impl TryFrom<XmlRecipe> for Recipe {
+    type Error = BeerXmlError;
+
+    fn try_from(xml: XmlRecipe) -> Result<Self, Self::Error> {
+        let efficiency = match (xml.efficiency, xml.recipe_type.to_lowercase().as_str()) {
+            (Some(efficiency), _) => efficiency,
+            (None, "all grain" | "partial mash") => {
+                return Err(BeerXmlError(format!(
+                    "{} recipe {} has no EFFICIENCY",
+                    xml.recipe_type, xml.name
+                )))
+            }
+            (None, _) => 100.,
+        };
+        let mut recipe = Recipe::new(
+            xml.name,
+            Volume::from_litres(xml.batch_size),
+            Volume::from_litres(xml.boil_size),
+            efficiency / 100.,
+        );
+        recipe.boil_time_mins = xml.boil_time.round() as u32;
+        recipe.style = xml.style.map(BeerStyle::from);
+        recipe.hops = xml.hops.hop.into_iter().map(HopAddition::from).collect();
+        recipe.fermentables = xml
+            .fermentables
+            .fermentable
+            .into_iter()
+            .map(FermentableAddition::from)
+            .collect();
+        recipe.yeast = xml.yeasts.yeast.into_iter().next().map(Yeast::from);
+        recipe.water = xml.waters.water.into_iter().next().map(WaterProfile::from);
+        if let Some(mash) = xml.mash {
+            recipe.mash_steps = mash
+                .mash_steps
+                .mash_step
+                .into_iter()
+                .map(MashStep::from)
+                .collect();
+        }
+        Ok(recipe)
+    }
+}
+
+impl From<&Recipe> for XmlRecipe {
+    fn from(recipe: &Recipe) -> Self {
+        XmlRecipe {
+            name: recipe.name.clone(),
+            version: version(),
+            recipe_type: "All Grain".to_owned(),
+            style: recipe.style.as_ref().map(XmlStyle::from),
+            brewer: String::new(),
+            batch_size: recipe.batch_size.as_litres(),
+            boil_size: recipe.boil_size.as_litres(),
+            boil_time: recipe.boil_time_mins as f64,
+            efficiency: Some(recipe.efficiency * 100.),
+            hops: XmlHops {
+                hop: recipe.hops.iter().map(XmlHop::from).collect(),
+            },
+            fermentables: XmlFermentables {
+                fermentable: recipe
+                    .fermentables
+                    .iter()
+                    .map(XmlFermentable::from)
+                    .collect(),
+            },
+            yeasts: XmlYeasts {
+                yeast: recipe.yeast.iter().map(XmlYeast::from).collect(),
+            },
+            waters: XmlWaters {
+                water: recipe
+                    .water
+                    .iter()
+                    .map(|w| XmlWater::from_water(w, &recipe.batch_size))
+                    .collect(),
+            },
+            mash: match recipe.mash_steps.is_empty() {
+                true => None,
+                false => Some(XmlMash {
+                    name: recipe.name.clone(),
+                    version: version(),
+                    grain_temp: 20.,
+                    mash_steps: XmlMashSteps {
+                        mash_step: recipe.mash_steps.iter().map(XmlMashStep::from).collect(),
+                    },
+                }),
+            },
+        }
+    }
+}
+
+impl From<XmlHop> for HopAddition {
+    fn from(xml: XmlHop) -> Self {
+        let time = xml.time.round() as u32;
+        // Aroma and dry hop TIME is steep or dry hop time, not boil time
+        let (time_mins, hop_use) = match xml.hop_use.to_lowercase().as_str() {
+            "first wort" => (time, HopUse::FirstWort),
+            // Mash TIME is the time in the mash, the hops aren't boiled
+            "mash" => (0, HopUse::Mash { time_mins: time }),
+            "aroma" => (
+                0,
+                HopUse::Whirlpool {
+                    temperature: Temperature::from_celsius(AROMA_TEMPERATURE),
+                    time_mins: time,
+                },
+            ),
+            "dry hop" => (
+                0,
+                HopUse::DryHop {
+                    days: (xml.time / MINUTES_IN_DAY).round() as u32,
+                },
+            ),
+            _ => (time, HopUse::Boil),
+        };
+        let hop_type = match xml.form.map(|form| form.to_lowercase()).as_deref() {
+            Some("pellet") => HopAdditionType::Pellet,
+            Some("plug") => HopAdditionType::Plug,
+            _ => HopAdditionType::Whole,
+        };
+        let mut hop = HopAddition::new(
+            Mass::from_kilograms(xml.amount),
+            xml.alpha / 100.,
+            time_mins,
+            hop_type,
+        );
+        hop.name = Some(xml.name);
+        hop.hop_use = hop_use;
+        hop
+    }
+}
+
+impl From<&HopAddition> for XmlHop {
+    fn from(hop: &HopAddition) -> Self {
+        XmlHop {
+            name: hop.name.clone().unwrap_or_default(),
+            version: version(),
+            alpha: hop.alpha_acid_percentage * 100.,
+            amount: hop.weight.as_kilograms(),
+            hop_use: match hop.hop_use {
+                HopUse::Boil => "Boil",
+                HopUse::Mash { .. } => "Mash",
+                HopUse::FirstWort => "First Wort",
+                HopUse::Whirlpool { .. } => "Aroma",
+                HopUse::DryHop { .. } => "Dry Hop",
+            }
+            .to_owned(),
+            time: match hop.hop_use {
+                HopUse::Boil | HopUse::FirstWort => hop.time_mins as f64,
+                HopUse::Mash { time_mins } | HopUse::Whirlpool { time_mins, .. } => {
+                    time_mins as f64
+                }
+                HopUse::DryHop { days } => days as f64 * MINUTES_IN_DAY,
+            },
+            form: Some(
+                match hop.hop_type {
+                    HopAdditionType::Whole => "Leaf",
+                    HopAdditionType::Plug => "Plug",
+                    HopAdditionType::Pellet => "Pellet",
+                }
+                .to_owned(),
+            ),
+        }
+    }
+}
+
+impl From<XmlFermentable> for FermentableAddition {
+    fn from(xml: XmlFermentable) -> Self {
+        let mut fermentable = FermentableAddition::new(
+            xml.name,
+            Mass::from_kilograms(xml.amount),
+            potential_from_yield(xml.fermentable_yield),
+            xml.color,
+        );
+        fermentable.fermentable_type =
+            FermentableType::from_name(&xml.fermentable_type).unwrap_or_default();
+        fermentable
+    }
+}
+
+impl From<&FermentableAddition> for XmlFermentable {
+    fn from(fermentable: &FermentableAddition) -> Self {
+        XmlFermentable {
+            name: fermentable.name.clone(),
+            version: version(),
+            fermentable_type: fermentable.fermentable_type.name().to_owned(),
+            amount: fermentable.weight.as_kilograms(),
+            fermentable_yield: yield_from_potential(&fermentable.potential),
+            color: fermentable.color,
+        }
+    }
+}
+
+impl From<XmlYeast> for Yeast {
+    fn from(xml: XmlYeast) -> Self {
+        let attenuation = xml.attenuation.map(|a| a.round() as u8);
+        Yeast {
+            company: xml.laboratory.unwrap_or_default(),
+            name: xml.name,
+            id: xml.product_id,
+            form: match xml.form.as_str() {
+                "Dry" => YeastForm::Dry,
+                "Culture" => YeastForm::Slurry,
+                _ => YeastForm::Liquid,
+            },
+            min_attenuation: attenuation,
+            max_attenuation: attenuation,
+            flocculation: xml.flocculation.as_deref().and_then(Level::from_name),
+            min_temp: xml.min_temperature.map(Temperature::from_celsius),
+            max_temp: xml.max_temperature.map(Temperature::from_celsius),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<&Yeast> for XmlYeast {
+    fn from(yeast: &Yeast) -> Self {
+        XmlYeast {
+            name: yeast.name.clone(),
+            version: version(),
+            yeast_type: None,
+            form: match yeast.form {
+                YeastForm::Dry => "Dry",
+                YeastForm::Liquid => "Liquid",
+                YeastForm::Slurry => "Culture",
+            }
+            .to_owned(),
+            amount: 0.,
+            laboratory: Some(yeast.company.clone()),
+            product_id: yeast.id.clone(),
+            min_temperature: yeast.min_temp.map(|t| t.as_celsius()),
+            max_temperature: yeast.max_temp.map(|t| t.as_celsius()),
+            flocculation: yeast.flocculation.map(|f| f.name().to_owned()),
+            attenuation: yeast.average_attenuation().map(|a| a as f64),
+        }
+    }
+}
+
+impl From<XmlWater> for WaterProfile {
+    fn from(xml: XmlWater) -> Self {
+        WaterProfile {
+            name: xml.name,
+            calcium: xml.calcium,
+            magnesium: xml.magnesium,
+            sodium: xml.sodium,
+            chloride: xml.chloride,
+            sulfate: xml.sulfate,
+            bicarbonate: xml.bicarbonate,
+        }
+    }
+}
+
+impl XmlWater {
+    fn from_water(water: &WaterProfile, amount: &Volume) -> Self {
+        XmlWater {
+            name: water.name.clone(),
+            version: version(),
+            amount: amount.as_litres(),
+            calcium: water.calcium,
+            bicarbonate: water.bicarbonate,
+            sulfate: water.sulfate,
+            chloride: water.chloride,
+            sodium: water.sodium,
+            magnesium: water.magnesium,
+        }
+    }
+}
+
+impl From<XmlMashStep> for MashStep {
+    fn from(xml: XmlMashStep) -> Self {
+        MashStep::new(
+            xml.name,
+            Temperature::from_celsius(xml.step_temp),
+            xml.step_time.round() as u32,
+        )
+    }
+}
+
+impl From<&MashStep> for XmlMashStep {
+    fn from(step: &MashStep) -> Self {
+        XmlMashStep {
+            name: step.name.clone(),
+            version: version(),
+            step_type: "Infusion".to_owned(),
+            step_temp: step.temperature.as_celsius(),
+            step_time: step.time_mins as f64,
+        }
+    }
+}
+
+impl From<XmlStyle> for BeerStyle {
+    fn from(xml: XmlStyle) -> Self {
+        BeerStyle {
+            abv_min: xml
+                .abv_min
+                .unwrap_or_else(|| style_abv(xml.og_min, xml.fg_max)),
+            abv_max: xml
+                .abv_max
+                .unwrap_or_else(|| style_abv(xml.og_max, xml.fg_min)),
+            name: xml.name,
+            original_gravity_min: xml.og_min,
+            original_gravity_max: xml.og_max,
+            final_gravity_min: xml.fg_min,
+            final_gravity_max: xml.fg_max,
+            ibu_min: xml.ibu_min.round() as u8,
+            ibu_max: xml.ibu_max.round() as u8,
+            color_srm_min: xml.color_min,
+            color_srm_max: xml.color_max,
+            description: xml.notes.unwrap_or_default(),
+        }
+    }
+}
+
+impl From<&BeerStyle> for XmlStyle {
+    fn from(style: &BeerStyle) -> Self {
+        XmlStyle {
+            name: style.name.clone(),
+            category: String::new(),
+            version: version(),
+            category_number: String::new(),
+            style_letter: String::new(),
+            style_guide: String::new(),
+            style_type: None,
+            og_min: style.original_gravity_min,
+            og_max: style.original_gravity_max,
+            fg_min: style.final_gravity_min,
+            fg_max: style.final_gravity_max,
+            ibu_min: style.ibu_min as f32,
+            ibu_max: style.ibu_max as f32,
+            color_min: style.color_srm_min,
+            color_max: style.color_srm_max,
+            abv_min: Some(style.abv_min),
+            abv_max: Some(style.abv_max),
+            notes: Some(style.description.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::assert_approx;
+
+    static PALE_ALE_XML: &str = include_str!("test_data/beerxml/pale_ale.xml");
+    static MULTIPLE_XML: &str = include_str!("test_data/beerxml/multiple_recipes.xml");
+    static LATIN1_XML: &[u8] = include_bytes!("test_data/beerxml/latin1.xml");
+
+    #[test]
+    fn read_recipe() {
+        let recipes = from_str(PALE_ALE_XML).unwrap();
+        assert_eq!(1, recipes.len());
+
+        let recipe = &recipes[0];
+        assert_eq!("Burton Pale Ale", recipe.name);
+        assert_approx!(20.8198, recipe.batch_size.as_litres());
+        assert_approx!(26.4980, recipe.boil_size.as_litres());
+        assert_eq!(60, recipe.boil_time_mins);
+        assert_approx!(0.72, recipe.efficiency);
+
+        assert_eq!(3, recipe.hops.len());
+        assert_eq!(Some("Challenger".to_owned()), recipe.hops[0].name);
+        assert_approx!(28.35, recipe.hops[0].weight.as_grams());
+        assert_approx!(0.075, recipe.hops[0].alpha_acid_percentage);
+        assert_eq!(60, recipe.hops[0].time_mins);
+        assert!(matches!(recipe.hops[0].hop_type, HopAdditionType::Pellet));
+        assert!(matches!(recipe.hops[1].hop_type, HopAdditionType::Whole));
+        assert_eq!(HopUse::Boil, recipe.hops[0].hop_use);
+        // Dry hops don't boil
+        assert_eq!(0, recipe.hops[2].time_mins);
+        assert_eq!(HopUse::DryHop { days: 3 }, recipe.hops[2].hop_use);
+
+        assert_eq!(2, recipe.fermentables.len());
+        assert_eq!("Pale Malt (2 Row) UK", recipe.fermentables[0].name);
+        assert_approx!(4.5, recipe.fermentables[0].weight.as_kilograms());
+        assert_approx!(
+            1.0360,
+            recipe.fermentables[0].potential.as_specific_gravity()
+        );
+        assert_approx!(3., recipe.fermentables[0].color);
+
+        let yeast = recipe.yeast.as_ref().unwrap();
+        assert_eq!("British Ale", yeast.name);
+        assert_eq!("Wyeast Labs", yeast.company);
+        assert_eq!(Some("1098".to_owned()), yeast.id);
+        assert_eq!(Some(74), yeast.average_attenuation());
+        assert_eq!(Some(Level::High), yeast.flocculation);
+        assert_approx!(18., yeast.min_temp.unwrap().as_celsius());
+
+        let water = recipe.water.as_ref().unwrap();
+        assert_eq!("Burton On Trent, UK", water.name);
+        assert_approx!(295., water.calcium);
+        assert_approx!(725., water.sulfate);
+
+        assert_eq!(2, recipe.mash_steps.len());
+        assert_eq!("Mash In", recipe.mash_steps[0].name);
+        assert_approx!(67.7778, recipe.mash_steps[0].temperature.as_celsius());
+        assert_eq!(10, recipe.mash_steps[1].time_mins);
+
+        let style = recipe.style.as_ref().unwrap();
+        assert_eq!("English Pale Ale", style.name);
+        assert_eq!(30, style.ibu_min);
+        assert_approx!(1.048, style.original_gravity_min as f64);
+        // ABV range is missing, estimated from gravities
+        assert_approx!(4.2, style.abv_min as f64, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn read_multiple_recipes() {
+        let recipes = from_str(MULTIPLE_XML).unwrap();
+        assert_eq!(2, recipes.len());
+        assert_eq!("Dry Stout", recipes[0].name);
+        assert_eq!(HopUse::FirstWort, recipes[0].hops[0].hop_use);
+        assert_eq!(90, recipes[0].hops[0].time_mins);
+        assert_eq!(
+            HopUse::Whirlpool {
+                temperature: Temperature::from_celsius(90.),
+                time_mins: 20
+            },
+            recipes[0].hops[1].hop_use
+        );
+        assert_eq!(0, recipes[0].hops[1].time_mins);
+        assert_eq!("Extract Wheat", recipes[1].name);
+        assert!(recipes[1].mash_steps.is_empty());
+        assert!(recipes[1].style.is_none());
+    }
+
+    #[test]
+    fn hop_use_and_form() {
+        let hop = |hop_use: &str, form: &str| {
+            HopAddition::from(XmlHop {
+                name: String::from("Fuggle"),
+                version: version(),
+                alpha: 4.5,
+                amount: 0.02,
+                hop_use: hop_use.to_owned(),
+                time: 30.,
+                form: Some(form.to_owned()),
+            })
+        };
+
+        let mash = hop("Mash", "Pellet");
+        assert_eq!(HopUse::Mash { time_mins: 30 }, mash.hop_use);
+        assert_eq!(0, mash.time_mins);
+        assert_eq!(HopAdditionType::Pellet, mash.hop_type);
+
+        assert_eq!(
+            HopUse::Mash { time_mins: 30 },
+            hop("MASH", "pellet").hop_use
+        );
+        assert_eq!(HopAdditionType::Pellet, hop("MASH", "pellet").hop_type);
+        assert_eq!(HopUse::FirstWort, hop("first wort", "Leaf").hop_use);
+        assert_eq!(HopAdditionType::Plug, hop("boil", "PLUG").hop_type);
+        assert_eq!(HopUse::DryHop { days: 0 }, hop("DRY HOP", "Leaf").hop_use);
+        assert_eq!(HopUse::Boil, hop("boil", "Leaf").hop_use);
+        assert_eq!(30, hop("boil", "Leaf").time_mins);
+    }
+
+    fn assert_same_recipe(original: &Recipe, read: &Recipe) {
+        assert_eq!(original.name, read.name);
+        assert_approx!(original.batch_size.as_litres(), read.batch_size.as_litres());
+        assert_approx!(original.boil_size.as_litres(), read.boil_size.as_litres());
+        assert_eq!(original.boil_time_mins, read.boil_time_mins);
+        assert_approx!(original.efficiency, read.efficiency);
+
+        assert_eq!(original.hops.len(), read.hops.len());
+        for (a, b) in original.hops.iter().zip(read.hops.iter()) {
+            assert_eq!(a.name, b.name);
+            assert_approx!(a.weight.as_grams(), b.weight.as_grams());
+            assert_approx!(a.alpha_acid_percentage, b.alpha_acid_percentage);
+            assert_eq!(a.time_mins, b.time_mins);
+            assert_eq!(a.hop_type, b.hop_type);
+            assert_eq!(a.hop_use, b.hop_use);
+        }
+
+        assert_eq!(original.fermentables.len(), read.fermentables.len());
+        for (a, b) in original.fermentables.iter().zip(read.fermentables.iter()) {
+            assert_eq!(a.name, b.name);
+            assert_approx!(a.weight.as_grams(), b.weight.as_grams());
+            assert_approx!(
+                a.potential.as_specific_gravity(),
+                b.potential.as_specific_gravity()
+            );
+            assert_approx!(a.color, b.color);
+        }
+
+        assert_eq!(original.yeast.is_some(), read.yeast.is_some());
+        if let (Some(a), Some(b)) = (&original.yeast, &read.yeast) {
+            assert_eq!(a.name, b.name);
+            assert_eq!(a.company, b.company);
+            assert_eq!(a.id, b.id);
+            assert_eq!(a.average_attenuation(), b.average_attenuation());
+            assert_eq!(a.flocculation, b.flocculation);
+        }
+
+        assert_eq!(original.water, read.water);
+
+        assert_eq!(original.mash_steps.len(), read.mash_steps.len());
+        for (a, b) in original.mash_steps.iter().zip(read.mash_steps.iter()) {
+            assert_eq!(a.name, b.name);
+            assert_approx!(a.temperature.as_celsius(), b.temperature.as_celsius());
+            assert_eq!(a.time_mins, b.time_mins);
+        }
+
+        assert_eq!(
+            original.style.as_ref().map(|s| &s.name),
+            read.style.as_ref().map(|s| &s.name)
+        );
+
+        assert_approx!(
+            original.og().as_specific_gravity(),
+            read.og().as_specific_gravity()
+        );
+        assert_approx!(original.ibu(), read.ibu());
+        assert_approx!(original.color(), read.color());
+    }
+
+    #[test]
+    fn round_trip() {
+        for xml in &[PALE_ALE_XML, MULTIPLE_XML] {
+            let recipes = from_str(xml).unwrap();
+            let exported = to_string(&recipes).unwrap();
+            let imported = from_str(&exported).unwrap();
+
+            assert_eq!(recipes.len(), imported.len());
+            for (original, read) in recipes.iter().zip(imported.iter()) {
+                assert_same_recipe(original, read);
+            }
+        }
+    }
+
+    #[test]
+    fn unknown_types_not_written() {
+        let recipes = from_str(PALE_ALE_XML).unwrap();
+        let exported = to_string(&recipes).unwrap();
+        // Yeast and style types are not known, the recipe and fermentable ones are
+        assert!(!exported.contains("<TYPE>Ale</TYPE>"));
+        assert!(exported.contains("<TYPE>All Grain</TYPE>"));
+        assert!(exported.contains("<YEAST>"));
+        assert!(exported.contains("<STYLE>"));
+    }
+
+    #[test]
+    fn read_latin1() {
+        let recipes = from_bytes(LATIN1_XML).unwrap();
+        assert_eq!("Märzen für Jørgen", recipes[0].name);
+        assert_eq!(
+            Some("Hallertauer Mittelfrüh".to_owned()),
+            recipes[0].hops[0].name
+        );
+        assert_eq!("Münchner Malz", recipes[0].fermentables[0].name);
+
+        // Undeclared Latin-1 falls back from UTF-8
+        let undeclared = LATIN1_XML
+            .split(|&b| b == b'\n')
+            .skip(1)
+            .collect::<Vec<_>>()
+            .join(&b'\n');
+        assert_eq!(
+            "Märzen für Jørgen",
+            from_bytes(&undeclared).unwrap()[0].name
+        );
+
+        // UTF-8 documents are read as is
+        let exported = to_string(&recipes).unwrap();
+        assert_eq!(
+            "Märzen für Jørgen",
+            from_bytes(exported.as_bytes()).unwrap()[0].name
+        );
+    }
+
+    #[test]
+    fn encoding_declaration() {
+        assert_eq!(
+            Some("iso-8859-1".to_owned()),
+            declared_encoding(br#"<?xml version="1.0" encoding='ISO-8859-1'?><RECIPES/>"#)
+        );
+        assert_eq!(
+            None,
+            declared_encoding(br#"<?xml version="1.0"?><RECIPES/>"#)
+        );
+        assert_eq!(None, declared_encoding(b"<RECIPES/>"));
+    }
+
+    #[test]
+    fn missing_efficiency() {
+        let without_efficiency = |recipe_type: &str| {
+            PALE_ALE_XML
+                .replace("<EFFICIENCY>72.0</EFFICIENCY>", "")
+                .replace(
+                    "<TYPE>All Grain</TYPE>",
+                    &format!("<TYPE>{}</TYPE>", recipe_type),
+                )
+        };
+        assert_eq!(
+            Some(BeerXmlError(String::from(
+                "All Grain recipe Burton Pale Ale has no EFFICIENCY"
+            ))),
+            from_str(&without_efficiency("All Grain")).err()
+        );
+        assert!(from_str(&without_efficiency("Partial Mash")).is_err());
+        let recipes = from_str(&without_efficiency("Extract")).unwrap();
+        assert_approx!(1., recipes[0].efficiency);
+    }
+
+    #[test]
+    fn invalid_xml() {
+        assert!(from_str("<RECIPES><RECIPE><NAME>Broken</NAME></RECIPE></RECIPES>").is_err());
+        assert!(from_str("not xml at all").is_err());
+    }
+}
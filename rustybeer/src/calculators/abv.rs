@@ -14,13 +14,157 @@
 //!     )
 //! );
 //! ```
+//!
+//! The simple formula underestimates strong beers, for which the other
+//! formulas of `AbvFormula` are more accurate:
+//! ```
+//! use rustybeer::conversions::RelativeDensity;
+//! use rustybeer::calculators::abv::{calculate_alcohol, AbvFormula};
+//!
+//! let result = calculate_alcohol(
+//!     &RelativeDensity::from_specific_gravity(1.100),
+//!     &RelativeDensity::from_specific_gravity(1.020),
+//!     AbvFormula::Advanced,
+//! );
+//! assert_eq!(11.6, (result.abv * 10.).round() / 10.);
+//! assert_eq!(79., result.apparent_attenuation.round());
+//! ```
 
+use crate::calculators::alcohol_volume_weight::calculate_abv_abw_density;
 use crate::conversions::RelativeDensity;
+use std::fmt;
+use std::str::FromStr;
+
+/// Formula used to calculate alcohol by volume
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum AbvFormula {
+    /// (OG - FG) * 131.25
+    #[default]
+    Simple,
+    /// Formula by Michael Hall, more accurate for strong beers
+    Advanced,
+    /// Formula by Karl Balling, based on the real extract
+    Balling,
+    /// Converted from alcohol by weight
+    Abw,
+}
+
+impl AbvFormula {
+    pub fn name(&self) -> &'static str {
+        match self {
+            AbvFormula::Simple => "Simple",
+            AbvFormula::Advanced => "Advanced",
+            AbvFormula::Balling => "Balling",
+            AbvFormula::Abw => "ABW",
+        }
+    }
+}
+
+impl fmt::Display for AbvFormula {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for AbvFormula {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "simple" => Ok(AbvFormula::Simple),
+            "advanced" => Ok(AbvFormula::Advanced),
+            "balling" => Ok(AbvFormula::Balling),
+            "abw" => Ok(AbvFormula::Abw),
+            _ => Err(format!("unknown abv formula {}", s)),
+        }
+    }
+}
+
+/// Alcohol content and attenuation of a beer
+#[derive(Debug, Clone, Copy)]
+pub struct AbvResult {
+    /// alcohol by volume in percentage
+    pub abv: f64,
+    /// alcohol by weight in percentage
+    pub abw: f64,
+    /// apparent attenuation in percentage
+    pub apparent_attenuation: f64,
+    /// real attenuation in percentage
+    pub real_attenuation: f64,
+    /// original extract in °P
+    pub original_extract: f64,
+    /// apparent extract in °P
+    pub apparent_extract: f64,
+    /// real extract in °P
+    pub real_extract: f64,
+}
 
 pub fn calculate_abv(og: &RelativeDensity, fg: &RelativeDensity) -> f64 {
     (og.as_specific_gravity() - fg.as_specific_gravity()) * 131.25
 }
 
+/// Calculates the real extract in °P, the extract left once the effect of
+/// alcohol on the final gravity is removed
+pub fn calculate_real_extract(og: &RelativeDensity, fg: &RelativeDensity) -> f64 {
+    0.1808 * og.as_plato() + 0.8192 * fg.as_plato()
+}
+
+/// Calculates alcohol by volume using the given formula
+///
+/// # Arguments
+///
+/// * `og`: original gravity
+/// * `fg`: final gravity
+/// * `formula`: formula to use
+pub fn calculate_abv_with(og: &RelativeDensity, fg: &RelativeDensity, formula: AbvFormula) -> f64 {
+    let og_sg = og.as_specific_gravity();
+    let fg_sg = fg.as_specific_gravity();
+    match formula {
+        AbvFormula::Simple => calculate_abv(og, fg),
+        AbvFormula::Advanced => 76.08 * (og_sg - fg_sg) / (1.775 - og_sg) * (fg_sg / 0.794),
+        AbvFormula::Balling => {
+            let original_extract = og.as_plato();
+            let abw = (original_extract - calculate_real_extract(og, fg))
+                / (2.0665 - 0.010665 * original_extract);
+            abw * fg_sg / 0.794
+        }
+        AbvFormula::Abw => 1.05 * (og_sg - fg_sg) / fg_sg / 0.79 * 100.,
+    }
+}
+
+/// Calculates alcohol content, extracts and attenuation of a beer
+///
+/// # Arguments
+///
+/// * `og`: original gravity
+/// * `fg`: final gravity
+/// * `formula`: formula to calculate alcohol by volume with
+pub fn calculate_alcohol(
+    og: &RelativeDensity,
+    fg: &RelativeDensity,
+    formula: AbvFormula,
+) -> AbvResult {
+    let abv = calculate_abv_with(og, fg, formula);
+    let original_extract = og.as_plato();
+    let apparent_extract = fg.as_plato();
+    let real_extract = calculate_real_extract(og, fg);
+    // Wort of 1.000 or below has no extract to attenuate
+    let attenuation = |extract: f64| match original_extract > 0. {
+        true => (original_extract - extract) / original_extract * 100.,
+        false => 0.,
+    };
+
+    AbvResult {
+        abv,
+        abw: calculate_abv_abw_density(abv, fg.as_specific_gravity()),
+        apparent_attenuation: attenuation(apparent_extract),
+        real_attenuation: attenuation(real_extract),
+        original_extract,
+        apparent_extract,
+        real_extract,
+    }
+}
+
 pub fn calculate_fg(og: &RelativeDensity, abv: f64) -> f64 {
     og.as_specific_gravity() - (abv / 131.25)
 }
@@ -41,6 +185,52 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn abv_formulas() {
+        let og = RelativeDensity::from_specific_gravity(1.050);
+        let fg = RelativeDensity::from_specific_gravity(1.012);
+        assert_approx!(4.9875, calculate_abv_with(&og, &fg, AbvFormula::Simple));
+        assert_approx!(5.0825, calculate_abv_with(&og, &fg, AbvFormula::Advanced));
+        assert_approx!(5.0303, calculate_abv_with(&og, &fg, AbvFormula::Balling));
+        assert_approx!(4.9907, calculate_abv_with(&og, &fg, AbvFormula::Abw));
+
+        let og = RelativeDensity::from_specific_gravity(1.100);
+        let fg = RelativeDensity::from_specific_gravity(1.020);
+        assert_approx!(10.5, calculate_abv_with(&og, &fg, AbvFormula::Simple));
+        assert_approx!(11.5834, calculate_abv_with(&og, &fg, AbvFormula::Advanced));
+        assert_approx!(10.8476, calculate_abv_with(&og, &fg, AbvFormula::Balling));
+        assert_approx!(10.4244, calculate_abv_with(&og, &fg, AbvFormula::Abw));
+    }
+
+    #[test]
+    fn alcohol() {
+        let result = calculate_alcohol(
+            &RelativeDensity::from_specific_gravity(1.050),
+            &RelativeDensity::from_specific_gravity(1.012),
+            AbvFormula::Balling,
+        );
+        assert_approx!(5.0303, result.abv);
+        assert_approx!(3.9218, result.abw);
+        assert_approx!(12.3876, result.original_extract);
+        assert_approx!(3.0683, result.apparent_extract);
+        assert_approx!(4.7532, result.real_extract);
+        assert_approx!(75.2312, result.apparent_attenuation);
+        assert_approx!(61.6294, result.real_attenuation);
+
+        let water = RelativeDensity::from_specific_gravity(1.);
+        for formula in [
+            AbvFormula::Simple,
+            AbvFormula::Advanced,
+            AbvFormula::Balling,
+            AbvFormula::Abw,
+        ] {
+            let result = calculate_alcohol(&water, &water, formula);
+            assert_approx!(0., result.abv);
+            assert_approx!(0., result.apparent_attenuation);
+            assert_approx!(0., result.real_attenuation);
+        }
+    }
+
     #[test]
     fn fg() {
         assert_approx!(
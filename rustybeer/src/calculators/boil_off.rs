@@ -0,0 +1,96 @@
+//! A calculator used to work backwards from the packaged
+//! volume to the pre-boil volume and gravity
+//!
+//! Losses of the equipment are added to the packaged volume in the reverse
+//! order they happen: fermenter loss, trub and chiller loss, cooling
+//! shrinkage and evaporation during the boil. The gravity points stay the
+//! same through the boil so the pre-boil gravity is diluted by the volume
+//! boiled off.
+//!
+//! ```
+//! use rustybeer::calculators::boil_off::calculate_boil_volumes;
+//! use rustybeer::conversions::RelativeDensity;
+//! use rustybeer::equipment::EquipmentProfile;
+//! use rustybeer::measurements::Volume;
+//!
+//! let volumes = calculate_boil_volumes(
+//!     &Volume::from_litres(20.),
+//!     &RelativeDensity::from_specific_gravity(1.050),
+//!     60,
+//!     &EquipmentProfile::default(),
+//! );
+//! assert_eq!(26.9, (volumes.pre_boil.as_litres() * 10.).round() / 10.);
+//! ```
+
+use crate::calculators::diluting::calculate_new_gravity;
+use crate::conversions::RelativeDensity;
+use crate::equipment::EquipmentProfile;
+use measurements::Volume;
+
+/// Volumes and gravity of the wort around the boil
+#[derive(Debug, Clone, Copy)]
+pub struct BoilVolumes {
+    /// volume of the wort before the boil
+    pub pre_boil: Volume,
+    /// gravity of the wort before the boil
+    pub pre_boil_gravity: RelativeDensity,
+    /// volume of the boiling hot wort at the end of the boil
+    pub post_boil: Volume,
+    /// volume of the wort once cooled, including the trub and chiller loss
+    pub cooled: Volume,
+    /// volume of the wort going into the fermenter
+    pub into_fermenter: Volume,
+}
+
+/// Calculates the volumes and the pre-boil gravity needed to package
+/// the given volume of beer
+///
+/// # Arguments
+///
+/// * `packaged_volume`: volume of the beer to package
+/// * `og`: original gravity of the wort
+/// * `boil_time`: length of the boil in minutes
+/// * `equipment`: losses of the equipment
+pub fn calculate_boil_volumes(
+    packaged_volume: &Volume,
+    og: &RelativeDensity,
+    boil_time: u32,
+    equipment: &EquipmentProfile,
+) -> BoilVolumes {
+    let into_fermenter = *packaged_volume + equipment.fermenter_loss;
+    let cooled = into_fermenter + equipment.trub_chiller_loss;
+    let post_boil = cooled / (1. - equipment.cooling_shrinkage / 100.);
+    let pre_boil = post_boil + equipment.evaporation_rate * (boil_time as f64 / 60.);
+
+    BoilVolumes {
+        pre_boil,
+        pre_boil_gravity: calculate_new_gravity(og, &post_boil, &pre_boil),
+        post_boil,
+        cooled,
+        into_fermenter,
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::assert_approx;
+
+    #[test]
+    fn boil_volumes() {
+        let equipment = EquipmentProfile::default();
+        let og = RelativeDensity::from_specific_gravity(1.050);
+        let packaged = Volume::from_litres(20.);
+
+        let volumes = calculate_boil_volumes(&packaged, &og, 60, &equipment);
+        assert_approx!(21., volumes.into_fermenter.as_litres());
+        assert_approx!(22., volumes.cooled.as_litres());
+        assert_approx!(22.9167, volumes.post_boil.as_litres());
+        assert_approx!(26.9167, volumes.pre_boil.as_litres());
+        assert_approx!(1.04257, volumes.pre_boil_gravity.as_specific_gravity());
+
+        let volumes = calculate_boil_volumes(&packaged, &og, 90, &equipment);
+        assert_approx!(28.9167, volumes.pre_boil.as_litres());
+        assert_approx!(1.03963, volumes.pre_boil_gravity.as_specific_gravity());
+    }
+}
@@ -0,0 +1,199 @@
+//! A calculator used to check that bottle
+//! conditioned beer stays within the rating of the bottles
+//!
+//! Beer bottled before reaching its final gravity keeps fermenting in the
+//! bottle on top of the priming sugar. The extract fermented is estimated
+//! from the remaining apparent gravity points using the real extract relation
+//! by Balling, and converted to CO2 like sucrose in the priming calculator.
+//!
+//! ```
+//! use rustybeer::calculators::bottle_pressure::{calculate_bottle_pressure, BottleType};
+//! use rustybeer::conversions::RelativeDensity;
+//! use rustybeer::measurements::Temperature;
+//!
+//! // Bottled four points above final gravity
+//! let bottle = calculate_bottle_pressure(
+//!     2.5,
+//!     &Temperature::from_celsius(20.),
+//!     &RelativeDensity::from_specific_gravity(1.014),
+//!     &RelativeDensity::from_specific_gravity(1.010),
+//!     &Temperature::from_celsius(20.),
+//!     BottleType::Standard,
+//! );
+//! assert_eq!(4.6, (bottle.co2_volumes * 10.).round() / 10.);
+//! assert!(bottle.exceeds_rating());
+//! ```
+
+use crate::calculators::carbonation::calculate_keg_pressure;
+use crate::calculators::priming::calculate_co2;
+use crate::conversions::RelativeDensity;
+use measurements::{Pressure, Temperature};
+use std::fmt;
+use std::str::FromStr;
+
+/// Sucrose needed for one volume of CO2 (g/l)
+const SUCROSE_PER_VOLUME: f64 = 4.;
+
+/// Type of the bottle, defining the pressure it is rated for
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum BottleType {
+    /// Standard beer bottle, rated for about 3 volumes of CO2
+    #[default]
+    Standard,
+    /// Heavy Belgian style bottle, rated for about 4.5 volumes of CO2
+    Belgian,
+    /// Champagne bottle
+    Champagne,
+}
+
+impl BottleType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            BottleType::Standard => "Standard",
+            BottleType::Belgian => "Belgian",
+            BottleType::Champagne => "Champagne",
+        }
+    }
+
+    /// Highest gauge pressure the bottle is rated for
+    pub fn rated_pressure(&self) -> Pressure {
+        match self {
+            BottleType::Standard => Pressure::from_psi(35.),
+            BottleType::Belgian => Pressure::from_psi(60.),
+            BottleType::Champagne => Pressure::from_psi(90.),
+        }
+    }
+}
+
+impl fmt::Display for BottleType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for BottleType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "standard" => Ok(BottleType::Standard),
+            "belgian" => Ok(BottleType::Belgian),
+            "champagne" => Ok(BottleType::Champagne),
+            _ => Err(format!("unknown bottle type {}", s)),
+        }
+    }
+}
+
+/// Estimated carbonation and pressure in a bottle
+#[derive(Debug, Clone, Copy)]
+pub struct BottlePressure {
+    /// volumes of CO2 once fermentation in the bottle has finished
+    pub co2_volumes: f64,
+    /// gauge pressure at the storage temperature
+    pub pressure: Pressure,
+    /// gauge pressure the bottle is rated for
+    pub rated_pressure: Pressure,
+}
+
+impl BottlePressure {
+    /// Whether the pressure exceeds the rating of the bottle
+    pub fn exceeds_rating(&self) -> bool {
+        self.pressure.as_psi() > self.rated_pressure.as_psi()
+    }
+}
+
+/// Calculates the volumes of CO2 produced by fermenting the beer from the
+/// bottling gravity to the final gravity in the bottle
+///
+/// # Arguments
+///
+/// * `bottling_gravity`: gravity of the beer at bottling
+/// * `final_gravity`: expected final gravity of the beer
+pub fn calculate_remaining_co2(
+    bottling_gravity: &RelativeDensity,
+    final_gravity: &RelativeDensity,
+) -> f64 {
+    let apparent_extract = (bottling_gravity.as_plato() - final_gravity.as_plato()).max(0.);
+    // Real extract fermented in g/l
+    let real_extract = 0.8192 * apparent_extract * bottling_gravity.as_specific_gravity() * 10.;
+    real_extract / SUCROSE_PER_VOLUME
+}
+
+/// Calculates the carbonation and pressure in a bottle once fermentation
+/// has finished
+///
+/// # Arguments
+///
+/// * `co2_volumes`: volumes of CO2 the beer was primed for
+/// * `bottling_temp`: temperature of the beer at bottling, giving the
+///   residual CO2 the carbonation can't fall below
+/// * `bottling_gravity`: gravity of the beer at bottling
+/// * `final_gravity`: expected final gravity of the beer
+/// * `storage_temp`: temperature the bottles are stored at
+/// * `bottle`: type of the bottle
+pub fn calculate_bottle_pressure(
+    co2_volumes: f64,
+    bottling_temp: &Temperature,
+    bottling_gravity: &RelativeDensity,
+    final_gravity: &RelativeDensity,
+    storage_temp: &Temperature,
+    bottle: BottleType,
+) -> BottlePressure {
+    let co2_volumes = co2_volumes.max(calculate_co2(bottling_temp))
+        + calculate_remaining_co2(bottling_gravity, final_gravity);
+
+    BottlePressure {
+        co2_volumes,
+        pressure: calculate_keg_pressure(storage_temp, co2_volumes),
+        rated_pressure: bottle.rated_pressure(),
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::assert_approx;
+
+    #[test]
+    fn remaining_co2() {
+        assert_approx!(
+            2.1042,
+            calculate_remaining_co2(
+                &RelativeDensity::from_specific_gravity(1.014),
+                &RelativeDensity::from_specific_gravity(1.010)
+            )
+        );
+        assert_approx!(
+            0.,
+            calculate_remaining_co2(
+                &RelativeDensity::from_specific_gravity(1.008),
+                &RelativeDensity::from_specific_gravity(1.010)
+            )
+        );
+    }
+
+    #[test]
+    fn bottle_pressure() {
+        let room = Temperature::from_celsius(20.);
+        let fg = RelativeDensity::from_specific_gravity(1.010);
+
+        let bottle = calculate_bottle_pressure(2.5, &room, &fg, &fg, &room, BottleType::Standard);
+        assert_approx!(2.5, bottle.co2_volumes);
+        assert_approx!(27.6496, bottle.pressure.as_psi());
+        assert!(!bottle.exceeds_rating());
+
+        let early = RelativeDensity::from_specific_gravity(1.014);
+        let bottle = calculate_bottle_pressure(2.5, &room, &early, &fg, &room, BottleType::Belgian);
+        assert_approx!(4.6042, bottle.co2_volumes);
+        assert_approx!(60.3584, bottle.pressure.as_psi());
+        assert!(bottle.exceeds_rating());
+        let bottle =
+            calculate_bottle_pressure(2.5, &room, &early, &fg, &room, BottleType::Champagne);
+        assert!(!bottle.exceeds_rating());
+
+        // Unprimed beer keeps its residual CO2
+        let bottle = calculate_bottle_pressure(0.5, &room, &fg, &fg, &room, BottleType::Standard);
+        assert_approx!(0.8615, bottle.co2_volumes);
+        assert_approx!(1.7599, bottle.pressure.as_psi());
+    }
+}
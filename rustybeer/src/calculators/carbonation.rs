@@ -0,0 +1,167 @@
+//! A calculator used to force carbonate
+//! beer in a keg and to balance the serving line
+//!
+//! The regulator pressure needed for a carbonation level comes from a fit of
+//! the CO2 solubility tables, which takes the temperature in Fahrenheit and
+//! returns gauge pressure in psi:
+//!
+//! P = -16.6999 - 0.0101059·T + 0.00116512·T² + 0.173354·T·V + 4.24267·V - 0.0684226·V²
+//!
+//! ```
+//! use rustybeer::calculators::carbonation::calculate_keg_pressure;
+//! use rustybeer::measurements::Temperature;
+//!
+//! // Takes the arguments serving temperature and volumes of CO2
+//! let pressure = calculate_keg_pressure(&Temperature::from_celsius(4.), 2.5);
+//! assert_eq!(11.9, (pressure.as_psi() * 10.).round() / 10.);
+//! ```
+
+use measurements::{Length, Pressure, Temperature};
+
+/// Pressure lost for every foot the beer rises to the tap (psi)
+const GRAVITY_PRESSURE_PER_FOOT: f64 = 0.5;
+
+/// Pressure left at the faucet for a good pour (psi)
+const FAUCET_PRESSURE: f64 = 1.;
+
+/// Resistance of common vinyl beer lines by inner diameter (inches, psi/ft)
+const LINE_RESISTANCES: [(f64, f64); 4] = [
+    (3. / 16., 3.),
+    (1. / 4., 0.85),
+    (5. / 16., 0.4),
+    (3. / 8., 0.2),
+];
+
+/// Calculates the regulator pressure needed to reach the volumes of CO2,
+/// zero if the beer holds that much CO2 without any pressure
+///
+/// # Arguments
+///
+/// * `temp`: temperature of the beer
+/// * `co2_volumes`: target volumes of CO2
+pub fn calculate_keg_pressure(temp: &Temperature, co2_volumes: f64) -> Pressure {
+    let t = temp.as_fahrenheit();
+    let v = co2_volumes;
+    let psi = -16.6999 - 0.0101059 * t + 0.00116512 * t.powi(2) + 0.173354 * t * v + 4.24267 * v
+        - 0.0684226 * v.powi(2);
+    Pressure::from_psi(psi.max(0.))
+}
+
+/// Calculates the volumes of CO2 the beer reaches at the regulator pressure,
+/// the inverse of `calculate_keg_pressure`
+///
+/// # Arguments
+///
+/// * `temp`: temperature of the beer
+/// * `pressure`: regulator pressure
+pub fn calculate_co2_volumes(temp: &Temperature, pressure: &Pressure) -> f64 {
+    let t = temp.as_fahrenheit();
+    let a = -0.0684226;
+    let b = 0.173354 * t + 4.24267;
+    let c = -16.6999 - 0.0101059 * t + 0.00116512 * t.powi(2) - pressure.as_psi();
+    (-b + (b.powi(2) - 4. * a * c).sqrt()) / (2. * a)
+}
+
+/// Resistance of a vinyl beer line in psi per foot, `None` if the inner
+/// diameter is not a common line size
+///
+/// # Arguments
+///
+/// * `inner_diameter`: inner diameter of the line
+pub fn line_resistance(inner_diameter: &Length) -> Option<f64> {
+    let inches = inner_diameter.as_inches();
+    LINE_RESISTANCES
+        .iter()
+        .find(|(diameter, _)| (diameter - inches).abs() < 0.01)
+        .map(|&(_, resistance)| resistance)
+}
+
+/// Calculates the length of the serving line needed to balance the keg
+/// pressure, zero if the rise to the tap alone balances it. Returns `None`
+/// if the resistance is not positive.
+///
+/// # Arguments
+///
+/// * `pressure`: regulator pressure
+/// * `tap_height`: height of the tap above the keg
+/// * `resistance`: resistance of the line in psi per foot
+pub fn calculate_line_length(
+    pressure: &Pressure,
+    tap_height: &Length,
+    resistance: f64,
+) -> Option<Length> {
+    if resistance <= 0. || resistance.is_nan() {
+        return None;
+    }
+    let feet =
+        (pressure.as_psi() - tap_height.as_feet() * GRAVITY_PRESSURE_PER_FOOT - FAUCET_PRESSURE)
+            / resistance;
+    Some(Length::from_feet(feet.max(0.)))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::assert_approx;
+
+    #[test]
+    fn keg_pressure() {
+        assert_approx!(
+            11.862,
+            calculate_keg_pressure(&Temperature::from_celsius(4.), 2.5).as_psi()
+        );
+        assert_approx!(
+            20.0668,
+            calculate_keg_pressure(&Temperature::from_fahrenheit(50.), 2.7).as_psi()
+        );
+        // Cold beer holds 1 volume of CO2 without pressure
+        assert_approx!(
+            0.,
+            calculate_keg_pressure(&Temperature::from_celsius(4.), 1.).as_psi()
+        );
+    }
+
+    #[test]
+    fn co2_volumes() {
+        assert_approx!(
+            2.5,
+            calculate_co2_volumes(&Temperature::from_celsius(4.), &Pressure::from_psi(11.862))
+        );
+        assert_approx!(
+            2.059,
+            calculate_co2_volumes(&Temperature::from_fahrenheit(50.), &Pressure::from_psi(12.))
+        );
+    }
+
+    #[test]
+    fn line_length() {
+        let resistance = line_resistance(&Length::from_inches(0.25)).unwrap();
+        assert_approx!(0.85, resistance);
+        assert!(line_resistance(&Length::from_inches(1.)).is_none());
+
+        assert_approx!(
+            9.2495,
+            calculate_line_length(
+                &Pressure::from_psi(11.862),
+                &Length::from_feet(6.),
+                resistance
+            )
+            .unwrap()
+            .as_feet()
+        );
+        assert_approx!(
+            0.,
+            calculate_line_length(&Pressure::from_psi(2.), &Length::from_feet(6.), resistance)
+                .unwrap()
+                .as_feet()
+        );
+        for resistance in [0., -0.85] {
+            assert!(calculate_line_length(
+                &Pressure::from_psi(11.862),
+                &Length::from_feet(6.),
+                resistance
+            )
+            .is_none());
+        }
+    }
+}
@@ -0,0 +1,194 @@
+//! A calculator used to predict the color of the beer from its grain bill
+//!
+//! The grain colors are combined into malt color units (MCU), which are
+//! converted into SRM with one of the common color models.
+//!
+//! ```
+//! use rustybeer::calculators::color::{calculate_mcu, calculate_srm, srm_to_ebc, srm_to_hex, ColorModel};
+//! use rustybeer::measurements::{Mass, Volume};
+//!
+//! // Grains as weight and color in degrees Lovibond
+//! let grains = vec![(Mass::from_pounds(9.), 2.), (Mass::from_pounds(1.), 40.)];
+//! let mcu = calculate_mcu(&grains, &Volume::from_gallons(5.));
+//! assert_eq!(11.6, mcu);
+//!
+//! let srm = calculate_srm(mcu, ColorModel::Morey);
+//! assert_eq!(8., srm.round());
+//! assert_eq!(16., srm_to_ebc(srm).round());
+//! assert_eq!("#EA8F00", srm_to_hex(srm));
+//! ```
+
+use measurements::{Mass, Volume};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Model to convert malt color units (MCU) to SRM
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum ColorModel {
+    /// Accurate over the whole range, the most commonly used model
+    #[default]
+    Morey,
+    /// Suited for darker beers, predicts too dark colors for pale beers
+    Daniels,
+    /// Suited for darker beers, predicts too dark colors for pale beers
+    Mosher,
+}
+
+impl ColorModel {
+    /// Name of the model
+    pub fn name(&self) -> &'static str {
+        match self {
+            ColorModel::Morey => "Morey",
+            ColorModel::Daniels => "Daniels",
+            ColorModel::Mosher => "Mosher",
+        }
+    }
+}
+
+impl fmt::Display for ColorModel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for ColorModel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "morey" => Ok(ColorModel::Morey),
+            "daniels" => Ok(ColorModel::Daniels),
+            "mosher" => Ok(ColorModel::Mosher),
+            _ => Err(format!("unknown color model {}", s)),
+        }
+    }
+}
+
+/// Approximate sRGB colors for SRM 1 to 40
+const SRM_COLORS: [&str; 40] = [
+    "#FFE699", "#FFD878", "#FFCA5A", "#FFBF42", "#FBB123", "#F8A600", "#F39C00", "#EA8F00",
+    "#E58500", "#DE7C00", "#D77200", "#CF6900", "#CB6200", "#C35900", "#BB5100", "#B54C00",
+    "#B04500", "#A63E00", "#A13700", "#9B3200", "#952D00", "#8E2900", "#882300", "#821E00",
+    "#7B1A00", "#771900", "#701400", "#6A0E00", "#660D00", "#5E0B00", "#5A0A02", "#600903",
+    "#520907", "#4C0505", "#470606", "#440607", "#3F0708", "#3B0607", "#3A070B", "#36080A",
+];
+
+/// Calculates malt color units (MCU)
+///
+/// # Arguments
+///
+/// * `grains`: weight and color in degrees Lovibond of each grain
+/// * `volume`: volume of the wort
+pub fn calculate_mcu(grains: &[(Mass, f64)], volume: &Volume) -> f64 {
+    grains
+        .iter()
+        .map(|(weight, color)| weight.as_pounds() * color)
+        .sum::<f64>()
+        / volume.as_gallons()
+}
+
+/// Converts malt color units (MCU) to SRM using the given model
+pub fn calculate_srm(mcu: f64, model: ColorModel) -> f64 {
+    match model {
+        ColorModel::Morey => 1.4922 * mcu.powf(0.6859),
+        ColorModel::Daniels => 0.2 * mcu + 8.4,
+        ColorModel::Mosher => 0.3 * mcu + 4.7,
+    }
+}
+
+/// Converts SRM to malt color units (MCU) using the given model, the
+/// inverse of `calculate_srm`
+pub fn calculate_mcu_from_srm(srm: f64, model: ColorModel) -> f64 {
+    let mcu = match model {
+        ColorModel::Morey => (srm / 1.4922).powf(1. / 0.6859),
+        ColorModel::Daniels => (srm - 8.4) / 0.2,
+        ColorModel::Mosher => (srm - 4.7) / 0.3,
+    };
+    mcu.max(0.)
+}
+
+/// Converts SRM to EBC
+pub fn srm_to_ebc(srm: f64) -> f64 {
+    srm * 1.97
+}
+
+/// Converts EBC to SRM
+pub fn ebc_to_srm(ebc: f64) -> f64 {
+    ebc / 1.97
+}
+
+/// Converts SRM to degrees Lovibond
+pub fn srm_to_lovibond(srm: f64) -> f64 {
+    (srm + 0.76) / 1.3546
+}
+
+/// Converts degrees Lovibond to SRM
+pub fn lovibond_to_srm(lovibond: f64) -> f64 {
+    1.3546 * lovibond - 0.76
+}
+
+/// Approximate sRGB color of the beer as hex string, for example "#EA8F00"
+pub fn srm_to_hex(srm: f64) -> String {
+    let index = (srm.round() as usize).clamp(1, SRM_COLORS.len()) - 1;
+    SRM_COLORS[index].to_owned()
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::assert_approx;
+
+    #[test]
+    fn mcu() {
+        let grains = vec![
+            (Mass::from_kilograms(4.5), 2.),
+            (Mass::from_kilograms(0.5), 40.),
+        ];
+        assert_approx!(9.6807, calculate_mcu(&grains, &Volume::from_litres(25.)));
+        assert_approx!(0., calculate_mcu(&[], &Volume::from_litres(25.)));
+    }
+
+    #[test]
+    fn srm() {
+        assert_approx!(9.5611, calculate_srm(15., ColorModel::Morey));
+        assert_approx!(11.4, calculate_srm(15., ColorModel::Daniels));
+        assert_approx!(9.2, calculate_srm(15., ColorModel::Mosher));
+        assert_approx!(0., calculate_srm(0., ColorModel::Morey));
+    }
+
+    #[test]
+    fn mcu_from_srm() {
+        for model in [ColorModel::Morey, ColorModel::Daniels, ColorModel::Mosher] {
+            assert_approx!(
+                15.,
+                calculate_mcu_from_srm(calculate_srm(15., model), model)
+            );
+        }
+        assert_approx!(0., calculate_mcu_from_srm(5., ColorModel::Daniels));
+    }
+
+    #[test]
+    fn conversions() {
+        assert_approx!(19.7, srm_to_ebc(10.));
+        assert_approx!(10., ebc_to_srm(19.7));
+        assert_approx!(7.9433, srm_to_lovibond(10.));
+        assert_approx!(10., lovibond_to_srm(srm_to_lovibond(10.)));
+    }
+
+    #[test]
+    fn hex() {
+        assert_eq!("#FFE699", srm_to_hex(0.));
+        assert_eq!("#FFE699", srm_to_hex(1.4));
+        assert_eq!("#DE7C00", srm_to_hex(10.));
+        assert_eq!("#36080A", srm_to_hex(40.));
+        assert_eq!("#36080A", srm_to_hex(80.));
+    }
+
+    #[test]
+    fn model_from_str() {
+        assert_eq!(Ok(ColorModel::Daniels), "daniels".parse());
+        assert_eq!(Ok(ColorModel::Mosher), "Mosher".parse());
+        assert!("lovibond".parse::<ColorModel>().is_err());
+    }
+}
@@ -0,0 +1,213 @@
+//! A calculator used to measure how well the extract of the grain bill
+//! ended up in the wort on a brew day.
+//!
+//! The efficiencies compare the gravity points measured at each step to
+//! the potential of the mashed grain:
+//!
+//! * Conversion efficiency: extract in the first runnings compared to the
+//!   extract the mash would have if all the starch had been converted
+//! * Lauter efficiency: share of the converted extract collected to the kettle
+//! * Mash efficiency: extract in the kettle before the boil
+//! * Brewhouse efficiency: extract in the kettle after the boil
+//! * Overall efficiency: extract going into the fermenter
+//!
+//! Extracts and sugars are assumed to be in the wort at every measurement
+//! and their full potential is excluded. All volumes should be measured at
+//! the same temperature.
+//!
+//! ```
+//! use rustybeer::calculators::efficiency::{calculate_efficiency, GravityReading};
+//! use rustybeer::calculators::og::FermentableAddition;
+//! use rustybeer::conversions::RelativeDensity;
+//! use rustybeer::fermentables::FERMENTABLES;
+//! use rustybeer::measurements::{Mass, Volume};
+//!
+//! let pale_malt = FERMENTABLES.iter().find(|f| f.name == "Pale Malt (2 Row) US").unwrap();
+//! let grist = vec![FermentableAddition::from_fermentable(pale_malt, Mass::from_kilograms(5.))];
+//!
+//! let efficiency = calculate_efficiency(
+//!     &grist,
+//!     &GravityReading::new(Volume::from_litres(27.), RelativeDensity::from_specific_gravity(1.042)),
+//!     &GravityReading::new(Volume::from_litres(23.), RelativeDensity::from_specific_gravity(1.048)),
+//!     &Volume::from_litres(21.),
+//!     None,
+//! )
+//! .unwrap();
+//! assert_eq!(0.72, (efficiency.brewhouse * 100.).round() / 100.);
+//! ```
+
+use crate::calculators::og::FermentableAddition;
+use crate::conversions::RelativeDensity;
+use crate::fermentables::SUCROSE_PPG;
+use measurements::Volume;
+
+/// Volume and gravity of the wort measured on the brew day
+#[derive(Debug, Clone, Copy)]
+pub struct GravityReading {
+    pub volume: Volume,
+    pub gravity: RelativeDensity,
+}
+
+impl GravityReading {
+    pub fn new(volume: Volume, gravity: RelativeDensity) -> Self {
+        Self { volume, gravity }
+    }
+
+    /// Gravity points of the reading per gallon
+    fn points(&self) -> f64 {
+        (self.gravity.as_specific_gravity() - 1.) * 1000. * self.volume.as_gallons()
+    }
+}
+
+/// Efficiencies of a brew day as decimals (0.72 for 72%)
+#[derive(Debug, Clone, Copy)]
+pub struct Efficiency {
+    /// conversion efficiency, if the first runnings were measured
+    pub conversion: Option<f64>,
+    /// lauter efficiency, if the first runnings were measured
+    pub lauter: Option<f64>,
+    pub mash: f64,
+    pub brewhouse: f64,
+    pub overall: f64,
+}
+
+/// Calculates the efficiencies of a brew day, `None` if the grist has
+/// nothing to mash
+///
+/// # Arguments
+///
+/// * `grist`: the grain bill
+/// * `pre_boil`: wort in the kettle before the boil
+/// * `post_boil`: wort in the kettle after the boil
+/// * `into_fermenter`: volume of the wort going into the fermenter
+/// * `first_runnings`: mash water and the first runnings, to calculate the
+///   conversion and lauter efficiencies
+pub fn calculate_efficiency(
+    grist: &[FermentableAddition],
+    pre_boil: &GravityReading,
+    post_boil: &GravityReading,
+    into_fermenter: &Volume,
+    first_runnings: Option<GravityReading>,
+) -> Option<Efficiency> {
+    let (mashed, unmashed): (Vec<&FermentableAddition>, Vec<&FermentableAddition>) =
+        grist.iter().partition(|f| f.fermentable_type.is_mashed());
+    let potential: f64 = mashed.iter().map(|f| f.gravity_points(1.)).sum();
+    if potential <= 0. {
+        return None;
+    }
+    let unmashed_points: f64 = unmashed.iter().map(|f| f.gravity_points(1.)).sum();
+    let efficiency = |points: f64| (points - unmashed_points) / potential;
+
+    let mash = efficiency(pre_boil.points());
+    let conversion = first_runnings.map(|first_runnings| {
+        // Extract of the grain and the mash water in kg
+        let extract: f64 = mashed
+            .iter()
+            .map(|f| {
+                f.weight.as_kilograms() * (f.potential.as_specific_gravity() - 1.) * 1000.
+                    / SUCROSE_PPG
+            })
+            .sum();
+        let water = first_runnings.volume.as_litres();
+        first_runnings.gravity.as_plato() / (extract / (extract + water) * 100.)
+    });
+
+    Some(Efficiency {
+        conversion,
+        lauter: conversion.map(|conversion| mash / conversion),
+        mash,
+        brewhouse: efficiency(post_boil.points()),
+        overall: efficiency(GravityReading::new(*into_fermenter, post_boil.gravity).points()),
+    })
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::assert_approx;
+    use crate::fermentables::{FermentableType, FERMENTABLES};
+    use measurements::Mass;
+
+    /// Grist of US pale malt and crystal malt from the fermentables list
+    fn grist() -> Vec<FermentableAddition> {
+        [
+            ("Pale Malt (2 Row) US", 4.5),
+            ("Caramel/Crystal Malt - 40L", 0.5),
+        ]
+        .iter()
+        .map(|(name, kilograms)| {
+            let fermentable = FERMENTABLES.iter().find(|f| f.name == *name).unwrap();
+            FermentableAddition::from_fermentable(fermentable, Mass::from_kilograms(*kilograms))
+        })
+        .collect()
+    }
+
+    fn reading(litres: f64, sg: f64) -> GravityReading {
+        GravityReading::new(
+            Volume::from_litres(litres),
+            RelativeDensity::from_specific_gravity(sg),
+        )
+    }
+
+    #[test]
+    fn efficiency() {
+        let efficiency = calculate_efficiency(
+            &grist(),
+            &reading(27., 1.042),
+            &reading(23., 1.048),
+            &Volume::from_litres(21.),
+            Some(reading(15., 1.080)),
+        )
+        .unwrap();
+        assert_approx!(0.9235, efficiency.conversion.unwrap());
+        assert_approx!(0.8018, efficiency.lauter.unwrap());
+        assert_approx!(0.7405, efficiency.mash);
+        assert_approx!(0.7209, efficiency.brewhouse);
+        assert_approx!(0.6582, efficiency.overall);
+
+        let efficiency = calculate_efficiency(
+            &grist(),
+            &reading(27., 1.042),
+            &reading(23., 1.048),
+            &Volume::from_litres(21.),
+            None,
+        )
+        .unwrap();
+        assert!(efficiency.conversion.is_none());
+        assert!(efficiency.lauter.is_none());
+    }
+
+    #[test]
+    fn excludes_extract() {
+        let mut grist = grist();
+        let mut sugar = FermentableAddition::new(
+            "Table Sugar".to_owned(),
+            Mass::from_pounds(1.),
+            RelativeDensity::from_specific_gravity(1.046),
+            0.,
+        );
+        sugar.fermentable_type = FermentableType::Sugar;
+        grist.push(sugar);
+
+        // 46 points of sugar in the 27 litres of wort
+        let pre_boil = 42. + 46. / Volume::from_litres(27.).as_gallons();
+        let efficiency = calculate_efficiency(
+            &grist,
+            &reading(27., 1. + pre_boil / 1000.),
+            &reading(23., 1.048),
+            &Volume::from_litres(21.),
+            None,
+        )
+        .unwrap();
+        assert_approx!(0.7405, efficiency.mash);
+
+        assert!(calculate_efficiency(
+            &grist[2..],
+            &reading(27., 1.042),
+            &reading(23., 1.048),
+            &Volume::from_litres(21.),
+            None,
+        )
+        .is_none());
+    }
+}
@@ -2,15 +2,96 @@
 ///
 /// IBUs = decimal alpha acid utilization * mg/l of added alpha acids
 ///
+/// Rager, Garetz, Daniels and mIBU models are available as alternatives
+/// through `IbuModel`.
 ///
 /// See:
 /// https://www.realbeer.com/hops/research.html
 /// http://www.backtoschoolbrewing.com/blog/2016/9/5/how-to-calculate-ibus
 /// https://straighttothepint.com/ibu-calculator/
 /// https://www.brewersfriend.com/2010/02/27/hops-alpha-acid-table-2009/
+/// http://scottjanish.com/wp-content/uploads/2016/04/mIBU.pdf
 ///
 use crate::conversions::RelativeDensity;
-use measurements::{Mass, Volume};
+use measurements::{Length, Mass, Temperature, Volume};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Utilization multiplier of first wort hops compared to the same boil time
+const FIRST_WORT_MULTIPLIER: f64 = 1.1;
+
+/// Share of the added alpha acids estimated to end up as bitterness when dry hopping
+const DRY_HOP_UTILIZATION: f64 = 0.05;
+
+/// Share of the hop weight which is polyphenols
+const HOP_POLYPHENOLS: f64 = 0.04;
+
+/// Share of the polyphenols extracted when dry hopping
+const DRY_HOP_POLYPHENOL_EXTRACTION: f64 = 0.5;
+
+/// Model used to estimate the alpha acid utilization
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub enum IbuModel {
+    /// Glenn Tinseth's model, default
+    #[default]
+    Tinseth,
+    /// Jackie Rager's model, gives higher estimates for long boils
+    Rager,
+    /// Mark Garetz's model, accounts for brewing conditions and gives lower estimates
+    Garetz {
+        /// elevation of the brewery, boiling point drops with elevation
+        elevation: Length,
+        /// yeast factor, 1.0 for typical yeast and higher for highly flocculant yeast
+        yeast_factor: f64,
+        /// filtration factor, 1.0 for unfiltered and 1.015 for filtered beer
+        filtration_factor: f64,
+    },
+    /// Ray Daniels' model using utilization tables for whole and pellet hops
+    Daniels,
+    /// Tinseth model modified to include utilization after flameout
+    MIbu {
+        /// time the wort steeps after flameout before forced cooling (min)
+        steep_mins: u32,
+        /// time to cool the wort from the steep temperature to 20°C (min)
+        cooling_mins: u32,
+    },
+}
+
+impl IbuModel {
+    /// Garetz model with factors for a typical sea level brewery
+    pub fn garetz() -> Self {
+        IbuModel::Garetz {
+            elevation: Length::from_meters(0.),
+            yeast_factor: 1.,
+            filtration_factor: 1.,
+        }
+    }
+
+    /// mIBU model for wort cooled right after flameout
+    pub fn mibu() -> Self {
+        IbuModel::MIbu {
+            steep_mins: 0,
+            cooling_mins: 10,
+        }
+    }
+}
+
+impl FromStr for IbuModel {
+    type Err = String;
+
+    /// Parses model from its name. Garetz and mIBU models get the
+    /// parameters of `IbuModel::garetz` and `IbuModel::mibu`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "tinseth" => Ok(IbuModel::Tinseth),
+            "rager" => Ok(IbuModel::Rager),
+            "garetz" => Ok(IbuModel::garetz()),
+            "daniels" => Ok(IbuModel::Daniels),
+            "mibu" => Ok(IbuModel::mibu()),
+            _ => Err(format!("unknown IBU model {}", s)),
+        }
+    }
+}
 
 /// Internal function to calculate Aplha Acid Utilization (Tinseth formula)
 /// given Boil Time and Wort Original Gravity
@@ -19,41 +100,179 @@ use measurements::{Mass, Volume};
 /// * `wort_gravity`: wort Original Gravity
 /// * `time_mins`: boil time (min)
 ///
-fn _calculate_utilization(wort_gravity: &RelativeDensity, time_mins: u32) -> f64 {
-    let bigness_factor = 1.65 * f64::powf(0.000125, wort_gravity.as_specific_gravity() - 1.0);
-    let boil_time_factor = (1.0 - f64::exp(-0.04 * (time_mins as f64))) / 4.15;
-    bigness_factor * boil_time_factor
+fn _calculate_utilization(wort_gravity: &RelativeDensity, time_mins: f64) -> f64 {
+    let boil_time_factor = (1.0 - f64::exp(-0.04 * time_mins)) / 4.15;
+    _calculate_bigness_factor(wort_gravity) * boil_time_factor
+}
+
+/// Internal function to calculate the Tinseth bigness factor, which lowers
+/// the utilization in high gravity worts
+fn _calculate_bigness_factor(wort_gravity: &RelativeDensity) -> f64 {
+    1.65 * f64::powf(0.000125, wort_gravity.as_specific_gravity() - 1.0)
 }
 
-/// Internal function to calculate IBU contributed by single hop addition,
+/// Internal function to calculate Alpha Acid Utilization (Rager formula)
+fn _calculate_rager_utilization(time_mins: f64) -> f64 {
+    (18.11 + 13.86 * f64::tanh((time_mins - 31.32) / 18.27)) / 100.
+}
+
+/// Internal function to look up Alpha Acid Utilization from the Garetz table
+fn _calculate_garetz_utilization(time_mins: u32) -> f64 {
+    match time_mins {
+        0..=10 => 0.,
+        11..=15 => 0.02,
+        16..=20 => 0.05,
+        21..=25 => 0.08,
+        26..=30 => 0.11,
+        31..=35 => 0.14,
+        36..=40 => 0.16,
+        41..=45 => 0.18,
+        46..=50 => 0.19,
+        51..=60 => 0.2,
+        61..=70 => 0.21,
+        71..=80 => 0.22,
+        _ => 0.23,
+    }
+}
+
+/// Internal function to look up Alpha Acid Utilization from the Daniels table
+fn _calculate_daniels_utilization(time_mins: u32, hop_type: HopAdditionType) -> f64 {
+    let (whole, pellet) = match time_mins {
+        0..=9 => (0.05, 0.06),
+        10..=19 => (0.12, 0.15),
+        20..=29 => (0.15, 0.19),
+        30..=44 => (0.19, 0.24),
+        45..=59 => (0.22, 0.27),
+        60..=74 => (0.24, 0.3),
+        _ => (0.27, 0.34),
+    };
+    match hop_type {
+        HopAdditionType::Whole | HopAdditionType::Plug => whole,
+        HopAdditionType::Pellet => pellet,
+    }
+}
+
+/// Internal function to calculate the gravity adjustment used by Rager and Daniels
+fn _calculate_gravity_adjustment(gravity_boil: &RelativeDensity) -> f64 {
+    let gravity = gravity_boil.as_specific_gravity();
+    match gravity > 1.050 {
+        true => (gravity - 1.050) / 0.2,
+        false => 0.,
+    }
+}
+
+/// Internal function to calculate the relative isomerization rate compared to boiling
+/// at given wort temperature (°C)
+fn _calculate_relative_rate(temp_celsius: f64) -> f64 {
+    2.39e11 * f64::exp(-9773. / (temp_celsius + 273.15))
+}
+
+/// Internal function to calculate the time (min) of boiling equivalent to the
+/// isomerization during the steep and cooling after flameout.
+///
+/// The wort cools down exponentially during the steep and linearly to 20°C during
+/// the forced cooling.
+fn _calculate_post_boil_time(steep_mins: u32, cooling_mins: u32) -> f64 {
+    let steep_temp = |t: f64| 53.70 * f64::exp(-0.0427 * t) + 46.30;
+    let steep_end_temp = steep_temp(steep_mins as f64);
+    let cooling_temp = |t: f64| steep_end_temp - (steep_end_temp - 20.) * t / (cooling_mins as f64);
+
+    // Integrate using midpoint rule with one second steps
+    let step = 1. / 60.;
+    let steep_steps = steep_mins * 60;
+    let cooling_steps = cooling_mins * 60;
+    let steep: f64 = (0..steep_steps)
+        .map(|i| _calculate_relative_rate(steep_temp((i as f64 + 0.5) * step)) * step)
+        .sum();
+    let cooling: f64 = (0..cooling_steps)
+        .map(|i| _calculate_relative_rate(cooling_temp((i as f64 + 0.5) * step)) * step)
+        .sum();
+    steep + cooling
+}
+
+/// Internal function to calculate IBU contributed by single hop addition
+/// without the Garetz hopping rate factor, which depends on the total IBU.
 ///
 /// # Arguments
 ///
 /// * `weight`: weight of the hop addition
 /// * `alpha_acid_percentage`: AA% of the hop variety
-/// * `time_mins`: boil time (min)
+/// * `time_mins`: boil time or equivalent isomerization time (min)
+/// * `hop_type`: type of hop added
 /// * `finished_volume_liters`: volume of the final wort (liters)
 /// * `gravity_boil`: the wort original gravity
+/// * `model`: model to calculate the utilization with
 ///
 fn _calculate_ibu_single_hop(
     weight: &Mass,
     alpha_acid_percentage: f64,
-    time_mins: u32,
+    time_mins: f64,
+    hop_type: HopAdditionType,
     finished_volume: &Volume,
     gravity_boil: &RelativeDensity,
-    utilization_multiplier: f64,
+    model: &IbuModel,
 ) -> f64 {
     let mg_per_liter_added_aa =
         (alpha_acid_percentage * weight.as_grams() * 1000.0) / finished_volume.as_liters();
-    let decimal_alpha_acid_utilization =
-        _calculate_utilization(gravity_boil, time_mins) * utilization_multiplier;
+    let pellet_multiplier = match hop_type {
+        HopAdditionType::Whole | HopAdditionType::Plug => 1.,
+        HopAdditionType::Pellet => 1.1,
+    };
+    let decimal_alpha_acid_utilization = match *model {
+        IbuModel::Tinseth => _calculate_utilization(gravity_boil, time_mins) * pellet_multiplier,
+        IbuModel::Rager => {
+            _calculate_rager_utilization(time_mins) * pellet_multiplier
+                / (1. + _calculate_gravity_adjustment(gravity_boil))
+        }
+        IbuModel::Garetz {
+            elevation,
+            yeast_factor,
+            filtration_factor,
+        } => {
+            let gravity_factor = 1. + _calculate_gravity_adjustment(gravity_boil);
+            let temperature_factor = elevation.as_feet() / 550. * 0.02 + 1.;
+            _calculate_garetz_utilization(time_mins.round() as u32) * pellet_multiplier
+                / (gravity_factor * temperature_factor * yeast_factor * filtration_factor)
+        }
+        // Daniels table has own utilization for pellets
+        IbuModel::Daniels => {
+            _calculate_daniels_utilization(time_mins.round() as u32, hop_type)
+                / (1. + _calculate_gravity_adjustment(gravity_boil))
+        }
+        IbuModel::MIbu {
+            steep_mins,
+            cooling_mins,
+        } => {
+            let time = time_mins + _calculate_post_boil_time(steep_mins, cooling_mins);
+            _calculate_utilization(gravity_boil, time) * pellet_multiplier
+        }
+    };
     mg_per_liter_added_aa * decimal_alpha_acid_utilization
 }
 
+/// Internal function to apply the Garetz hopping rate factor to the sum of
+/// single hop IBUs. The factor depends on the resulting IBU, so the quadratic
+/// `ibu * (1 + ibu / 260) = raw_ibu` is solved for it.
+fn _apply_hopping_rate_factor(raw_ibu: f64, model: &IbuModel) -> f64 {
+    match model {
+        IbuModel::Garetz { .. } => 130. * (f64::sqrt(1. + raw_ibu / 65.) - 1.),
+        _ => raw_ibu,
+    }
+}
+
+/// Internal function to reverse `_apply_hopping_rate_factor`
+fn _remove_hopping_rate_factor(ibu: f64, model: &IbuModel) -> f64 {
+    match model {
+        IbuModel::Garetz { .. } => ibu * (1. + ibu / 260.),
+        _ => ibu,
+    }
+}
+
 /// An enum of hop types
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub enum HopAdditionType {
     /// Whole, default
+    #[default]
     Whole,
     // Plugs, same utilization as whole hops
     Plug,
@@ -61,38 +280,82 @@ pub enum HopAdditionType {
     Pellet,
 }
 
-impl Default for HopAdditionType {
-    fn default() -> Self {
-        HopAdditionType::Whole
+impl FromStr for HopAdditionType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "whole" | "leaf" => Ok(HopAdditionType::Whole),
+            "plug" => Ok(HopAdditionType::Plug),
+            "pellet" => Ok(HopAdditionType::Pellet),
+            _ => Err(format!("unknown hop type {}", s)),
+        }
     }
 }
 
+/// How the hops are used in the brewing process
+#[derive(Debug, Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum HopUse {
+    /// Added to the boil for `time_mins`, default
+    #[default]
+    Boil,
+    /// Added to the mash, the hops stay in the grain bed and don't isomerize
+    Mash {
+        /// time in the mash (min)
+        time_mins: u32,
+    },
+    /// Added to the wort before the boil, 10% higher utilization than a boil addition
+    FirstWort,
+    /// Steeped after flameout in the whirlpool or hop stand
+    Whirlpool {
+        /// temperature of the wort, serialized in Celsius
+        #[serde(with = "crate::serialization::celsius")]
+        temperature: Temperature,
+        /// steep time (min)
+        time_mins: u32,
+    },
+    /// Added to the fermenter, doesn't isomerize
+    DryHop {
+        /// contact time (days)
+        days: u32,
+    },
+}
+
 /// A representation of one hop addition
 ///
 /// Example:
 /// ```
-/// use rustybeer::calculators::ibu::{HopAddition, HopAdditionType};
+/// use rustybeer::calculators::ibu::{HopAddition, HopAdditionType, HopUse};
 /// use rustybeer::measurements::Mass;
 /// // Centennial (8.5% AA) Pellets: 7g - 60 min
 /// HopAddition {
+///     name: Some("Centennial".to_owned()),
 ///     weight: Mass::from_grams(7.),
 ///     alpha_acid_percentage: 0.085,
 ///     time_mins: 60,
-///     hop_type: HopAdditionType::Pellet
+///     hop_type: HopAdditionType::Pellet,
+///     hop_use: HopUse::Boil,
 /// };
 ///```
 ///
-#[derive(Debug, Copy, Clone)]
-// TODO: YAML/JSON serialization
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HopAddition {
-    /// the weight of the hop addition
+    /// name of the hop variety, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// the weight of the hop addition, serialized in grams
+    #[serde(with = "crate::serialization::grams")]
     pub weight: Mass,
     /// AA% of the hop variety
     pub alpha_acid_percentage: f64,
     /// boil time (min)
     pub time_mins: u32,
     /// type of hop added: whole or pellets. [default() = HopAdditionType::Whole]
+    #[serde(default)]
     pub hop_type: HopAdditionType,
+    /// how the hop is used. [default() = HopUse::Boil]
+    #[serde(default)]
+    pub hop_use: HopUse,
 }
 
 impl HopAddition {
@@ -103,12 +366,53 @@ impl HopAddition {
         hop_type: HopAdditionType,
     ) -> Self {
         Self {
+            name: None,
             weight,
             alpha_acid_percentage,
             time_mins,
             hop_type,
+            hop_use: HopUse::Boil,
         }
     }
+
+    /// Hop addition steeped in the whirlpool after flameout
+    pub fn whirlpool(
+        weight: Mass,
+        alpha_acid_percentage: f64,
+        temperature: Temperature,
+        time_mins: u32,
+        hop_type: HopAdditionType,
+    ) -> Self {
+        Self {
+            hop_use: HopUse::Whirlpool {
+                temperature,
+                time_mins,
+            },
+            ..Self::new(weight, alpha_acid_percentage, 0, hop_type)
+        }
+    }
+
+    /// Hop addition added to the fermenter
+    pub fn dry_hop(
+        weight: Mass,
+        alpha_acid_percentage: f64,
+        days: u32,
+        hop_type: HopAdditionType,
+    ) -> Self {
+        Self {
+            hop_use: HopUse::DryHop { days },
+            ..Self::new(weight, alpha_acid_percentage, 0, hop_type)
+        }
+    }
+}
+
+/// Estimated bitterness and polyphenols extracted by dry hopping
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct DryHopEstimate {
+    /// bitterness from oxidized alpha acids (humulinones)
+    pub ibu: f64,
+    /// extracted polyphenols (mg/l)
+    pub polyphenols: f64,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -121,6 +425,7 @@ pub struct NegativeIbuError;
 /// * `hop_additions`: the added hops weights (g), AA%, and boil time (min)
 /// * `finished_volume_liters`: volume of the final wort (liters)
 /// * `gravity_boil`: wort original gravity
+/// * `model`: model to calculate the utilization with
 ///
 /// # Examples
 ///
@@ -129,18 +434,22 @@ pub struct NegativeIbuError;
 /// * Cascade (6.4% AA): 28g - 45 mins
 ///
 /// ```
-/// use rustybeer::calculators::ibu::{HopAddition, calculate_ibu};
+/// use rustybeer::calculators::ibu::{HopAddition, IbuModel, calculate_ibu};
 /// use rustybeer::assert_approx;
 /// use rustybeer::measurements::{Mass, Volume};
 /// use rustybeer::conversions::RelativeDensity;
 ///
+/// let hops = vec![HopAddition::new(Mass::from_grams(28.0), 0.064, 45, Default::default())];
+/// let volume = Volume::from_liters(20.0);
+/// let gravity = RelativeDensity::from_specific_gravity(1.050);
+///
 /// assert_approx!(
 ///     18.9723,
-///     calculate_ibu(
-///         vec![HopAddition::new(Mass::from_grams(28.0), 0.064, 45, Default::default())],
-///         &Volume::from_liters(20.0),
-///         &RelativeDensity::from_specific_gravity(1.050)
-///     )
+///     calculate_ibu(hops.clone(), &volume, &gravity, &IbuModel::Tinseth)
+/// );
+/// assert_approx!(
+///     24.1051,
+///     calculate_ibu(hops, &volume, &gravity, &IbuModel::Rager)
 /// );
 /// ```
 ///
@@ -148,23 +457,88 @@ pub fn calculate_ibu(
     hop_additions: Vec<HopAddition>,
     finished_volume: &Volume,
     gravity_boil: &RelativeDensity,
+    model: &IbuModel,
 ) -> f64 {
-    hop_additions
+    let raw_ibu = hop_additions
         .into_iter()
         .map(|h| {
+            let (time_mins, multiplier, model) = match h.hop_use {
+                HopUse::Boil => (h.time_mins as f64, 1., model),
+                HopUse::FirstWort => (h.time_mins as f64, FIRST_WORT_MULTIPLIER, model),
+                HopUse::Whirlpool {
+                    temperature,
+                    time_mins,
+                } => {
+                    let whirlpool_time =
+                        time_mins as f64 * _calculate_relative_rate(temperature.as_celsius());
+                    // The whirlpool replaces the post boil steep of the mIBU model
+                    let model = match model {
+                        IbuModel::MIbu { .. } => &IbuModel::Tinseth,
+                        _ => model,
+                    };
+                    (h.time_mins as f64 + whirlpool_time, 1., model)
+                }
+                HopUse::Mash { .. } | HopUse::DryHop { .. } => return 0.,
+            };
             _calculate_ibu_single_hop(
                 &h.weight,
                 h.alpha_acid_percentage,
-                h.time_mins,
+                time_mins,
+                h.hop_type,
                 finished_volume,
                 gravity_boil,
-                match h.hop_type {
-                    HopAdditionType::Whole | HopAdditionType::Plug => 1.,
-                    HopAdditionType::Pellet => 1.1,
-                },
-            )
+                model,
+            ) * multiplier
+        })
+        .sum();
+    _apply_hopping_rate_factor(raw_ibu, model)
+}
+
+/// Estimates the bitterness and polyphenols contributed by dry hop additions
+///
+/// Dry hops don't isomerize, so `calculate_ibu` ignores them. Some of the alpha
+/// acids oxidize into bitter humulinones and polyphenols are extracted, both
+/// saturating after a few days of contact time. Other hop additions are ignored.
+///
+/// # Arguments
+///
+/// * `hop_additions`: the hop additions
+/// * `finished_volume`: volume of the beer in the fermenter
+///
+/// # Examples
+///
+/// ```
+/// use rustybeer::calculators::ibu::{calculate_dry_hop_estimate, HopAddition, HopAdditionType};
+/// use rustybeer::assert_approx;
+/// use rustybeer::measurements::{Mass, Volume};
+///
+/// let estimate = calculate_dry_hop_estimate(
+///     &[HopAddition::dry_hop(Mass::from_grams(100.), 0.1, 5, HopAdditionType::Pellet)],
+///     &Volume::from_liters(20.),
+/// );
+/// assert_approx!(24.1, estimate.ibu, epsilon = 0.1);
+/// assert_approx!(96.4, estimate.polyphenols, epsilon = 0.1);
+/// ```
+///
+pub fn calculate_dry_hop_estimate(
+    hop_additions: &[HopAddition],
+    finished_volume: &Volume,
+) -> DryHopEstimate {
+    hop_additions
+        .iter()
+        .fold(DryHopEstimate::default(), |estimate, h| match h.hop_use {
+            HopUse::DryHop { days } => {
+                let contact = 1. - f64::exp(-(days as f64) / 1.5);
+                let mg_per_liter = h.weight.as_grams() * 1000. / finished_volume.as_liters();
+                DryHopEstimate {
+                    ibu: estimate.ibu
+                        + mg_per_liter * h.alpha_acid_percentage * DRY_HOP_UTILIZATION * contact,
+                    polyphenols: estimate.polyphenols
+                        + mg_per_liter * HOP_POLYPHENOLS * DRY_HOP_POLYPHENOL_EXTRACTION * contact,
+                }
+            }
+            _ => estimate,
         })
-        .sum()
 }
 
 /// Calculates the needed amount of bittering hop to reach a target IBU for given variety alpha
@@ -178,6 +552,7 @@ pub fn calculate_ibu(
 /// * `finished_volume_liters`: volume of the final wort (liters)
 /// * `gravity_boil`: wort original gravity
 /// * `target_ibu`: target IBU
+/// * `model`: model to calculate the utilization with
 ///
 /// # Examples
 ///
@@ -187,7 +562,7 @@ pub fn calculate_ibu(
 /// * Centennial (8.5% AA) hops to be added for 60min boil
 /// * No other hops additions
 /// ```
-/// use rustybeer::calculators::ibu::calculate_bittering_weight;
+/// use rustybeer::calculators::ibu::{calculate_bittering_weight, IbuModel};
 /// use rustybeer::assert_approx;
 /// use rustybeer::measurements::Volume;
 /// use rustybeer::conversions::RelativeDensity;
@@ -198,7 +573,8 @@ pub fn calculate_ibu(
 ///     None,
 ///     &Volume::from_liters(22.),
 ///     &RelativeDensity::from_specific_gravity(1.058),
-///     17.
+///     17.,
+///     &IbuModel::Tinseth
 /// );
 /// assert_approx!( 20.4973, bittering.unwrap());
 /// ```
@@ -206,24 +582,27 @@ pub fn calculate_ibu(
 /// With addition of 20gm of Centennial (8.5% AA) for 60min boil,
 /// can't get IBU down to just 10
 ///
-/// ```{.should_panic}
-/// use rustybeer::calculators::ibu::calculate_bittering_weight;
+/// ```should_panic
+/// use rustybeer::calculators::ibu::{calculate_bittering_weight, IbuModel};
 /// use rustybeer::calculators::ibu::HopAddition;
 /// use rustybeer::measurements::{Mass, Volume};
 /// use rustybeer::conversions::RelativeDensity;
 ///
 /// let bittering = calculate_bittering_weight(Some(vec![
 ///     HopAddition {
+///         name: None,
 ///         weight: Mass::from_grams(20.),
 ///         alpha_acid_percentage: 0.085,
 ///         time_mins: 60,
-///         hop_type: Default::default()
+///         hop_type: Default::default(),
+///         hop_use: Default::default(),
 ///     }]),
 ///     0.085,
 ///     None,
 ///     &Volume::from_liters(22.),
 ///     &RelativeDensity::from_specific_gravity(1.058),
-///     10.
+///     10.,
+///     &IbuModel::Tinseth
 /// );
 ///
 /// bittering.expect("Too low IBU target");
@@ -236,37 +615,76 @@ pub fn calculate_bittering_weight(
     finished_volume: &Volume,
     gravity_boil: &RelativeDensity,
     target_ibu: f64,
+    model: &IbuModel,
 ) -> Result<f64, NegativeIbuError> {
+    let target_raw_ibu = _remove_hopping_rate_factor(target_ibu, model);
     let bittering_ibu = match hop_additions {
-        Some(h) => target_ibu - calculate_ibu(h, finished_volume, gravity_boil),
-        None => target_ibu,
+        Some(h) => {
+            target_raw_ibu
+                - _remove_hopping_rate_factor(
+                    calculate_ibu(h, finished_volume, gravity_boil, model),
+                    model,
+                )
+        }
+        None => target_raw_ibu,
     };
 
     match bittering_ibu.is_sign_positive() {
         true => {
             let bittering_time = bittering_time_mins.unwrap_or(60);
-            let bittering_alpha_acid_utilization =
-                _calculate_utilization(gravity_boil, bittering_time);
-
-            let bittering_weight = (bittering_ibu * finished_volume.as_liters())
-                / (bittering_alpha_acid_utilization * bittering_alpha_acid_percentage)
-                / 1000.0;
+            // IBU of one gram of the bittering hop
+            let gram_ibu = _calculate_ibu_single_hop(
+                &Mass::from_grams(1.),
+                bittering_alpha_acid_percentage,
+                bittering_time as f64,
+                HopAdditionType::Whole,
+                finished_volume,
+                gravity_boil,
+                model,
+            );
 
-            Ok(bittering_weight)
+            Ok(bittering_ibu / gram_ibu)
         }
         false => Err(NegativeIbuError),
     }
 }
 
+/// Calculates how many times the hops must be added to wort of another
+/// gravity to get the same IBU, based on the Tinseth bigness factor.
+///
+/// # Arguments
+///
+/// * `from`: gravity of the wort the hops were planned for
+/// * `to`: gravity of the wort the hops are added to
+///
+/// # Examples
+///
+/// ```
+/// use rustybeer::calculators::ibu::calculate_utilization_ratio;
+/// use rustybeer::assert_approx;
+/// use rustybeer::conversions::RelativeDensity;
+///
+/// let ratio = calculate_utilization_ratio(
+///     &RelativeDensity::from_specific_gravity(1.050),
+///     &RelativeDensity::from_specific_gravity(1.080),
+/// );
+/// assert_approx!(1.3095, ratio);
+/// ```
+///
+pub fn calculate_utilization_ratio(from: &RelativeDensity, to: &RelativeDensity) -> f64 {
+    _calculate_bigness_factor(from) / _calculate_bigness_factor(to)
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::{
-        calculate_bittering_weight, calculate_ibu, HopAddition, HopAdditionType, NegativeIbuError,
-        _calculate_ibu_single_hop, _calculate_utilization,
+        _calculate_ibu_single_hop, _calculate_utilization, calculate_bittering_weight,
+        calculate_dry_hop_estimate, calculate_ibu, calculate_utilization_ratio, DryHopEstimate,
+        HopAddition, HopAdditionType, HopUse, IbuModel, NegativeIbuError,
     };
     use crate::assert_approx;
     use crate::conversions::RelativeDensity;
-    use measurements::{Mass, Volume};
+    use measurements::{Length, Mass, Temperature, Volume};
 
     #[test]
     fn utilization() {
@@ -275,7 +693,7 @@ pub mod tests {
             for (boiling_time_idx, boiling_time) in test_vector.boiling_time.iter().enumerate() {
                 let ut = _calculate_utilization(
                     &RelativeDensity::from_specific_gravity(*og),
-                    *boiling_time,
+                    *boiling_time as f64,
                 );
                 // Only three decimals provided in test vector
                 approx::assert_relative_eq!(
@@ -287,6 +705,97 @@ pub mod tests {
         }
     }
 
+    /// Utilization of a model as the IBU of 1 mg/l of added alpha acid
+    fn model_utilization(
+        model: &IbuModel,
+        time_mins: f64,
+        hop_type: HopAdditionType,
+        og: f64,
+    ) -> f64 {
+        _calculate_ibu_single_hop(
+            &Mass::from_grams(1.),
+            0.1,
+            time_mins,
+            hop_type,
+            &Volume::from_liters(100.),
+            &RelativeDensity::from_specific_gravity(og),
+            model,
+        )
+    }
+
+    fn published_gravity_adjustment(og: f64) -> f64 {
+        1. + f64::max(0., (og - 1.050) / 0.2)
+    }
+
+    #[test]
+    fn rager_utilization() {
+        let test_vector =
+            crate::calculators::test_vectors::rager_utilization_test_vector::get_vector();
+        for og in test_vector.og.iter() {
+            for (time_idx, (first, last)) in test_vector.boiling_time.iter().enumerate() {
+                let ut = model_utilization(
+                    &IbuModel::Rager,
+                    (first + last) as f64 / 2.,
+                    HopAdditionType::Whole,
+                    *og,
+                );
+                // The tanh formula is a fit of the table, within 1 percentage
+                // point in the middle of each range
+                approx::assert_relative_eq!(
+                    test_vector.utilization[time_idx] / published_gravity_adjustment(*og),
+                    ut,
+                    epsilon = 1e-2
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn daniels_utilization() {
+        let test_vector =
+            crate::calculators::test_vectors::daniels_utilization_test_vector::get_vector();
+        for og in test_vector.og.iter() {
+            for (time_idx, (first, last)) in test_vector.boiling_time.iter().enumerate() {
+                for time in [*first, *last] {
+                    for (hop_type, table) in [
+                        (HopAdditionType::Whole, &test_vector.whole),
+                        (HopAdditionType::Pellet, &test_vector.pellet),
+                    ] {
+                        let ut = model_utilization(&IbuModel::Daniels, time as f64, hop_type, *og);
+                        approx::assert_relative_eq!(
+                            table[time_idx] / published_gravity_adjustment(*og),
+                            ut,
+                            epsilon = 1e-9
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn garetz_utilization() {
+        let test_vector =
+            crate::calculators::test_vectors::garetz_utilization_test_vector::get_vector();
+        let model = IbuModel::Garetz {
+            elevation: Length::from_meters(0.),
+            yeast_factor: 1.,
+            filtration_factor: 1.,
+        };
+        for og in test_vector.og.iter() {
+            for (time_idx, (first, last)) in test_vector.boiling_time.iter().enumerate() {
+                for time in [*first, *last] {
+                    let ut = model_utilization(&model, time as f64, HopAdditionType::Whole, *og);
+                    approx::assert_relative_eq!(
+                        test_vector.utilization[time_idx] / published_gravity_adjustment(*og),
+                        ut,
+                        epsilon = 1e-9
+                    );
+                }
+            }
+        }
+    }
+
     #[test]
     fn single_hop_ibu() {
         assert_approx!(
@@ -294,10 +803,11 @@ pub mod tests {
             _calculate_ibu_single_hop(
                 &Mass::from_grams(7.0),
                 0.085,
-                15,
+                15.,
+                HopAdditionType::Whole,
                 &Volume::from_liters(22.0),
                 &RelativeDensity::from_specific_gravity(1.058),
-                1.
+                &IbuModel::Tinseth
             )
         );
     }
@@ -312,7 +822,8 @@ pub mod tests {
                     HopAddition::new(Mass::from_grams(7.0), 0.085, 15, HopAdditionType::Whole)
                 ],
                 &Volume::from_liters(22.0),
-                &RelativeDensity::from_specific_gravity(1.058)
+                &RelativeDensity::from_specific_gravity(1.058),
+                &IbuModel::Tinseth
             ),
         );
     }
@@ -328,7 +839,8 @@ pub mod tests {
                     HopAddition::new(Mass::from_grams(7.0), 0.085, 15, HopAdditionType::Pellet)
                 ],
                 &Volume::from_liters(22.0),
-                &RelativeDensity::from_specific_gravity(1.058)
+                &RelativeDensity::from_specific_gravity(1.058),
+                &IbuModel::Tinseth
             ),
         );
     }
@@ -348,6 +860,7 @@ pub mod tests {
             &Volume::from_liters(22.0),
             &RelativeDensity::from_specific_gravity(1.058),
             10.,
+            &IbuModel::Tinseth,
         )
         .expect("too low IBU");
     }
@@ -366,6 +879,7 @@ pub mod tests {
                 &Volume::from_liters(22.0),
                 &RelativeDensity::from_specific_gravity(1.058),
                 16.76,
+                &IbuModel::Tinseth,
             )?,
         );
         Ok(())
@@ -378,8 +892,209 @@ pub mod tests {
             calculate_ibu(
                 vec![],
                 &Volume::from_liters(22.0),
-                &RelativeDensity::from_specific_gravity(1.058)
+                &RelativeDensity::from_specific_gravity(1.058),
+                &IbuModel::Tinseth
+            )
+        );
+    }
+
+    #[test]
+    fn utilization_ratio() {
+        let gravity = |sg| RelativeDensity::from_specific_gravity(sg);
+        assert_approx!(
+            1.,
+            calculate_utilization_ratio(&gravity(1.060), &gravity(1.060))
+        );
+        assert_approx!(
+            0.8355,
+            calculate_utilization_ratio(&gravity(1.060), &gravity(1.040))
+        );
+        // Same IBU with the scaled weight in the other wort
+        let ibu = |grams, sg| {
+            calculate_ibu(
+                vec![HopAddition::new(
+                    Mass::from_grams(grams),
+                    0.1,
+                    60,
+                    HopAdditionType::Pellet,
+                )],
+                &Volume::from_liters(20.),
+                &gravity(sg),
+                &IbuModel::Tinseth,
+            )
+        };
+        let ratio = calculate_utilization_ratio(&gravity(1.045), &gravity(1.090));
+        assert_approx!(ibu(30., 1.045), ibu(30. * ratio, 1.090));
+    }
+
+    #[test]
+    fn ibu_models_regression() {
+        let snapshot = crate::calculators::test_vectors::ibu_models_regression::get_snapshot();
+        let hops: Vec<HopAddition> = snapshot
+            .hops
+            .iter()
+            .map(|&(grams, alpha_acid, time_mins, pellet)| {
+                HopAddition::new(
+                    Mass::from_grams(grams),
+                    alpha_acid,
+                    time_mins,
+                    match pellet {
+                        true => HopAdditionType::Pellet,
+                        false => HopAdditionType::Whole,
+                    },
+                )
+            })
+            .collect();
+        let volume = Volume::from_liters(snapshot.volume_liters);
+        let models = [
+            (IbuModel::Tinseth, &snapshot.tinseth),
+            (IbuModel::Rager, &snapshot.rager),
+            (
+                IbuModel::Garetz {
+                    elevation: Length::from_meters(1500.),
+                    yeast_factor: 1.05,
+                    filtration_factor: 1.015,
+                },
+                &snapshot.garetz,
+            ),
+            (IbuModel::Daniels, &snapshot.daniels),
+            (
+                IbuModel::MIbu {
+                    steep_mins: 20,
+                    cooling_mins: 10,
+                },
+                &snapshot.mibu,
+            ),
+        ];
+
+        for (model, expected) in models.iter() {
+            for (gravity_idx, gravity) in snapshot.gravity.iter().enumerate() {
+                let ibu = calculate_ibu(
+                    hops.clone(),
+                    &volume,
+                    &RelativeDensity::from_specific_gravity(*gravity),
+                    model,
+                );
+                // Post boil isomerization of mIBU is integrated numerically
+                approx::assert_relative_eq!(expected[gravity_idx], ibu, epsilon = 1e-3);
+            }
+        }
+    }
+
+    #[test]
+    fn bitter_hops_weight_models() -> Result<(), NegativeIbuError> {
+        let volume = Volume::from_liters(22.0);
+        let gravity = RelativeDensity::from_specific_gravity(1.058);
+        let aroma = vec![HopAddition::new(
+            Mass::from_grams(15.0),
+            0.085,
+            15,
+            HopAdditionType::Pellet,
+        )];
+
+        for model in [
+            IbuModel::Tinseth,
+            IbuModel::Rager,
+            IbuModel::garetz(),
+            IbuModel::Daniels,
+            IbuModel::MIbu {
+                steep_mins: 10,
+                cooling_mins: 15,
+            },
+        ] {
+            let weight = calculate_bittering_weight(
+                Some(aroma.clone()),
+                0.12,
+                Some(60),
+                &volume,
+                &gravity,
+                40.,
+                &model,
+            )?;
+            let mut hops = aroma.clone();
+            hops.push(HopAddition::new(
+                Mass::from_grams(weight),
+                0.12,
+                60,
+                HopAdditionType::Whole,
+            ));
+            assert_approx!(40., calculate_ibu(hops, &volume, &gravity, &model));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn hop_use_ibu() {
+        let volume = Volume::from_liters(20.0);
+        let gravity = RelativeDensity::from_specific_gravity(1.050);
+        let ibu =
+            |hop: HopAddition| calculate_ibu(vec![hop], &volume, &gravity, &IbuModel::Tinseth);
+
+        let boil = HopAddition::new(Mass::from_grams(28.0), 0.1, 30, HopAdditionType::Whole);
+        assert_approx!(24.8179, ibu(boil.clone()));
+
+        let first_wort = HopAddition {
+            hop_use: HopUse::FirstWort,
+            ..boil.clone()
+        };
+        assert_approx!(24.8179 * 1.1, ibu(first_wort));
+
+        // Mash hops stay in the grain bed
+        let mash = HopAddition {
+            time_mins: 0,
+            hop_use: HopUse::Mash { time_mins: 60 },
+            ..boil.clone()
+        };
+        assert_approx!(0., ibu(mash));
+
+        // Isomerization slows down with lower temperature
+        let whirlpool = |celsius: f64| {
+            HopAddition::whirlpool(
+                Mass::from_grams(28.0),
+                0.1,
+                Temperature::from_celsius(celsius),
+                30,
+                HopAdditionType::Whole,
             )
+        };
+        assert_approx!(24.9358, ibu(whirlpool(100.)));
+        assert_approx!(8.5331, ibu(whirlpool(80.)));
+
+        // Dry hops don't isomerize
+        let dry_hop = HopAddition::dry_hop(Mass::from_grams(28.0), 0.1, 5, HopAdditionType::Whole);
+        assert_approx!(0., ibu(dry_hop));
+    }
+
+    #[test]
+    fn dry_hop_estimate() {
+        let volume = Volume::from_liters(20.0);
+        let hops = vec![
+            HopAddition::new(Mass::from_grams(28.0), 0.1, 60, HopAdditionType::Pellet),
+            HopAddition::dry_hop(Mass::from_grams(50.0), 0.1, 0, HopAdditionType::Pellet),
+        ];
+        assert_eq!(
+            DryHopEstimate::default(),
+            calculate_dry_hop_estimate(&hops, &volume)
         );
+
+        let hops = vec![
+            HopAddition::dry_hop(Mass::from_grams(50.0), 0.1, 2, HopAdditionType::Pellet),
+            HopAddition::dry_hop(Mass::from_grams(50.0), 0.1, 7, HopAdditionType::Pellet),
+        ];
+        let estimate = calculate_dry_hop_estimate(&hops, &volume);
+        assert_approx!(21.5875, estimate.ibu);
+        assert_approx!(86.35, estimate.polyphenols);
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!(Ok(IbuModel::Rager), "Rager".parse());
+        assert_eq!(Ok(IbuModel::garetz()), "garetz".parse());
+        assert_eq!(Ok(IbuModel::mibu()), "mIBU".parse());
+        assert!("hops".parse::<IbuModel>().is_err());
+
+        assert_eq!(Ok(HopAdditionType::Pellet), "pellet".parse());
+        assert_eq!(Ok(HopAdditionType::Whole), "Leaf".parse());
+        assert!("cone".parse::<HopAdditionType>().is_err());
     }
 }
@@ -0,0 +1,533 @@
+//! Calculators for infusion and decoction mashing: strike water temperature
+//! and volume, the boiling water infusions needed to reach the following
+//! rests and the portion of the mash to pull for each decoction.
+//!
+//! Uses the metric forms of the infusion equations with water-to-grist ratio
+//! in litres per kilogram:
+//!
+//! * Strike water temperature: Tw = (0.41 / r)(T2 - T1) + T2
+//! * Infusion volume: Wa = (T2 - T1)(0.41 G + Wm) / (Tw - T2)
+//! * Decoction fraction: F = (T2 - T1) / (Tb - T1 - X), where X accounts for
+//!   the heat lost while the decoction is returned to the mash
+//!
+//! See: http://howtobrew.com/book/section-3/the-methods-of-mashing/calculations-for-boiling-water-additions
+//! and http://howtobrew.com/book/section-3/the-methods-of-mashing/decoction-mashing
+//!
+//! ```
+//! use rustybeer::calculators::mash::{calculate_mash_schedule, MashStep};
+//! use rustybeer::measurements::{Mass, Temperature};
+//!
+//! let steps = vec![
+//!     MashStep::new("Protein rest".to_owned(), Temperature::from_celsius(52.), 15),
+//!     MashStep::new("Saccharification".to_owned(), Temperature::from_celsius(66.), 60),
+//! ];
+//! let schedule = calculate_mash_schedule(
+//!     &Mass::from_kilograms(5.),
+//!     &Temperature::from_celsius(20.),
+//!     3.,
+//!     &steps,
+//! )
+//! .unwrap();
+//!
+//! assert_eq!(15., schedule[0].volume.as_litres());
+//! assert_eq!(56.4, (schedule[0].water_temperature.as_celsius() * 10.).round() / 10.);
+//! assert_eq!(7.0, (schedule[1].volume.as_litres() * 10.).round() / 10.);
+//! ```
+
+use measurements::{Mass, Temperature, Volume};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Specific heat of grain relative to water
+const GRAIN_SPECIFIC_HEAT: f64 = 0.41;
+
+/// Temperature of the boiling water used for infusions (°C)
+const BOILING_WATER_TEMPERATURE: f64 = 100.;
+
+/// Heat lost when the boiled decoction is returned to the mash (°C)
+const DECOCTION_HEAT_LOSS: f64 = 10.;
+
+/// A single rest of the mash schedule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MashStep {
+    /// name of the step, for example "Saccharification"
+    pub name: String,
+    /// rest temperature, serialized in Celsius
+    #[serde(with = "crate::serialization::celsius")]
+    pub temperature: Temperature,
+    /// rest time (min)
+    pub time_mins: u32,
+}
+
+impl MashStep {
+    pub fn new(name: String, temperature: Temperature, time_mins: u32) -> Self {
+        Self {
+            name,
+            temperature,
+            time_mins,
+        }
+    }
+}
+
+/// Water added to reach a mash rest
+#[derive(Debug, Clone)]
+pub struct MashInfusion {
+    /// the rest the infusion is for
+    pub step: MashStep,
+    /// temperature of the added water
+    pub water_temperature: Temperature,
+    /// volume of the added water
+    pub volume: Volume,
+    /// total volume of water in the mash after the infusion
+    pub total_volume: Volume,
+}
+
+/// Portion of the mash pulled and boiled to reach a mash rest
+#[derive(Debug, Clone)]
+pub struct Decoction {
+    /// the rest the decoction is for
+    pub step: MashStep,
+    /// pulled portion of the mash as decimal
+    pub fraction: f64,
+    /// volume of thick mash to pull
+    pub volume: Volume,
+}
+
+/// Traditional decoction schedules
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum DecoctionSchedule {
+    /// Saccharification rest and one decoction to mash out
+    #[default]
+    Single,
+    /// Protein rest and decoctions to saccharification and mash out
+    Double,
+    /// Acid rest and decoctions to protein rest, saccharification and mash out
+    Triple,
+}
+
+impl DecoctionSchedule {
+    /// Name of the schedule
+    pub fn name(&self) -> &'static str {
+        match self {
+            DecoctionSchedule::Single => "Single",
+            DecoctionSchedule::Double => "Double",
+            DecoctionSchedule::Triple => "Triple",
+        }
+    }
+
+    /// Rests of the schedule, the first one being the dough-in rest
+    pub fn steps(&self) -> Vec<MashStep> {
+        let rests: &[(&str, f64, u32)] = match self {
+            DecoctionSchedule::Single => &[("Saccharification", 65., 60), ("Mash out", 76., 10)],
+            DecoctionSchedule::Double => &[
+                ("Protein rest", 50., 20),
+                ("Saccharification", 65., 45),
+                ("Mash out", 76., 10),
+            ],
+            DecoctionSchedule::Triple => &[
+                ("Acid rest", 35., 20),
+                ("Protein rest", 50., 20),
+                ("Saccharification", 65., 45),
+                ("Mash out", 76., 10),
+            ],
+        };
+        rests
+            .iter()
+            .map(|&(name, temperature, time_mins)| {
+                MashStep::new(
+                    name.to_owned(),
+                    Temperature::from_celsius(temperature),
+                    time_mins,
+                )
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for DecoctionSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for DecoctionSchedule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "single" => Ok(DecoctionSchedule::Single),
+            "double" => Ok(DecoctionSchedule::Double),
+            "triple" => Ok(DecoctionSchedule::Triple),
+            _ => Err(format!("unknown decoction schedule {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MashError {
+    /// Added water is not hotter than the target rest temperature
+    InfusionTooCold,
+    /// Infusions can't lower the mash temperature
+    TemperatureDecrease,
+    /// The whole mash would need to be boiled to reach the rest temperature
+    DecoctionTooLarge,
+}
+
+impl fmt::Display for MashError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MashError::InfusionTooCold => {
+                write!(f, "infusion water must be hotter than the rest temperature")
+            }
+            MashError::TemperatureDecrease => {
+                write!(f, "rest temperatures must not decrease")
+            }
+            MashError::DecoctionTooLarge => {
+                write!(f, "rest temperature can't be reached with a decoction")
+            }
+        }
+    }
+}
+
+impl std::error::Error for MashError {}
+
+/// Calculates the strike water temperature to reach the first rest
+///
+/// # Arguments
+///
+/// * `grain_temperature`: temperature of the grain
+/// * `rest_temperature`: target temperature of the first rest
+/// * `ratio`: water-to-grist ratio (l/kg)
+pub fn calculate_strike_temperature(
+    grain_temperature: &Temperature,
+    rest_temperature: &Temperature,
+    ratio: f64,
+) -> Temperature {
+    let target = rest_temperature.as_celsius();
+    Temperature::from_celsius(
+        GRAIN_SPECIFIC_HEAT / ratio * (target - grain_temperature.as_celsius()) + target,
+    )
+}
+
+/// Calculates the strike water volume
+///
+/// # Arguments
+///
+/// * `grain_weight`: weight of the grain
+/// * `ratio`: water-to-grist ratio (l/kg)
+pub fn calculate_strike_volume(grain_weight: &Mass, ratio: f64) -> Volume {
+    Volume::from_litres(grain_weight.as_kilograms() * ratio)
+}
+
+/// Calculates the volume of hot water needed to raise the mash temperature
+///
+/// # Arguments
+///
+/// * `mash_temperature`: current temperature of the mash
+/// * `rest_temperature`: target temperature of the next rest
+/// * `grain_weight`: weight of the grain
+/// * `mash_volume`: volume of water in the mash
+/// * `water_temperature`: temperature of the added water
+pub fn calculate_infusion_volume(
+    mash_temperature: &Temperature,
+    rest_temperature: &Temperature,
+    grain_weight: &Mass,
+    mash_volume: &Volume,
+    water_temperature: &Temperature,
+) -> Result<Volume, MashError> {
+    let target = rest_temperature.as_celsius();
+    let difference = target - mash_temperature.as_celsius();
+    if difference < 0. {
+        return Err(MashError::TemperatureDecrease);
+    }
+    if water_temperature.as_celsius() <= target {
+        return Err(MashError::InfusionTooCold);
+    }
+
+    Ok(Volume::from_litres(
+        difference * (GRAIN_SPECIFIC_HEAT * grain_weight.as_kilograms() + mash_volume.as_litres())
+            / (water_temperature.as_celsius() - target),
+    ))
+}
+
+/// Calculates strike water for the first step and boiling water infusions
+/// for the following steps
+///
+/// # Arguments
+///
+/// * `grain_weight`: weight of the grain
+/// * `grain_temperature`: temperature of the grain
+/// * `ratio`: water-to-grist ratio of the strike water (l/kg)
+/// * `steps`: mash rests in order
+pub fn calculate_mash_schedule(
+    grain_weight: &Mass,
+    grain_temperature: &Temperature,
+    ratio: f64,
+    steps: &[MashStep],
+) -> Result<Vec<MashInfusion>, MashError> {
+    let mut schedule: Vec<MashInfusion> = Vec::with_capacity(steps.len());
+
+    for step in steps {
+        let infusion = match schedule.last() {
+            None => {
+                let volume = calculate_strike_volume(grain_weight, ratio);
+                MashInfusion {
+                    step: step.clone(),
+                    water_temperature: calculate_strike_temperature(
+                        grain_temperature,
+                        &step.temperature,
+                        ratio,
+                    ),
+                    volume,
+                    total_volume: volume,
+                }
+            }
+            Some(previous) => {
+                let water_temperature = Temperature::from_celsius(BOILING_WATER_TEMPERATURE);
+                let volume = calculate_infusion_volume(
+                    &previous.step.temperature,
+                    &step.temperature,
+                    grain_weight,
+                    &previous.total_volume,
+                    &water_temperature,
+                )?;
+                MashInfusion {
+                    step: step.clone(),
+                    water_temperature,
+                    volume,
+                    total_volume: previous.total_volume + volume,
+                }
+            }
+        };
+        schedule.push(infusion);
+    }
+
+    Ok(schedule)
+}
+
+/// Calculates the portion of thick mash to pull and boil to raise the
+/// temperature of the mash
+///
+/// # Arguments
+///
+/// * `mash_temperature`: current temperature of the mash
+/// * `rest_temperature`: target temperature of the next rest
+/// * `mash_volume`: total volume of the mash
+pub fn calculate_decoction_volume(
+    mash_temperature: &Temperature,
+    rest_temperature: &Temperature,
+    mash_volume: &Volume,
+) -> Result<Volume, MashError> {
+    let current = mash_temperature.as_celsius();
+    let difference = rest_temperature.as_celsius() - current;
+    if difference < 0. {
+        return Err(MashError::TemperatureDecrease);
+    }
+
+    let fraction = difference / (BOILING_WATER_TEMPERATURE - current - DECOCTION_HEAT_LOSS);
+    if !(0. ..1.).contains(&fraction) {
+        return Err(MashError::DecoctionTooLarge);
+    }
+
+    Ok(*mash_volume * fraction)
+}
+
+/// Calculates the decoctions to reach each rest after the first one
+///
+/// # Arguments
+///
+/// * `mash_volume`: total volume of the mash
+/// * `steps`: mash rests in order, the first one being the dough-in rest
+pub fn calculate_decoction_schedule(
+    mash_volume: &Volume,
+    steps: &[MashStep],
+) -> Result<Vec<Decoction>, MashError> {
+    steps
+        .windows(2)
+        .map(|rests| {
+            let volume = calculate_decoction_volume(
+                &rests[0].temperature,
+                &rests[1].temperature,
+                mash_volume,
+            )?;
+            Ok(Decoction {
+                step: rests[1].clone(),
+                fraction: volume.as_litres() / mash_volume.as_litres(),
+                volume,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::assert_approx;
+
+    #[test]
+    fn strike_temperature() {
+        assert_approx!(
+            74.708,
+            calculate_strike_temperature(
+                &Temperature::from_celsius(20.),
+                &Temperature::from_celsius(67.),
+                2.5
+            )
+            .as_celsius()
+        );
+        assert_approx!(
+            67.,
+            calculate_strike_temperature(
+                &Temperature::from_celsius(67.),
+                &Temperature::from_celsius(67.),
+                2.5
+            )
+            .as_celsius()
+        );
+    }
+
+    #[test]
+    fn strike_volume() {
+        assert_approx!(
+            13.5,
+            calculate_strike_volume(&Mass::from_kilograms(4.5), 3.).as_litres()
+        );
+    }
+
+    #[test]
+    fn infusion_volume() {
+        assert_approx!(
+            4.3234,
+            calculate_infusion_volume(
+                &Temperature::from_celsius(52.),
+                &Temperature::from_celsius(65.),
+                &Mass::from_kilograms(4.),
+                &Volume::from_litres(10.),
+                &Temperature::from_celsius(100.),
+            )
+            .unwrap()
+            .as_litres()
+        );
+        assert_eq!(
+            Err(MashError::InfusionTooCold),
+            calculate_infusion_volume(
+                &Temperature::from_celsius(52.),
+                &Temperature::from_celsius(65.),
+                &Mass::from_kilograms(4.),
+                &Volume::from_litres(10.),
+                &Temperature::from_celsius(65.),
+            )
+            .map(|v| v.as_litres())
+        );
+    }
+
+    #[test]
+    fn mash_schedule() {
+        let steps = vec![
+            MashStep::new(
+                "Protein rest".to_owned(),
+                Temperature::from_celsius(50.),
+                20,
+            ),
+            MashStep::new("Beta".to_owned(), Temperature::from_celsius(63.), 30),
+            MashStep::new("Alpha".to_owned(), Temperature::from_celsius(72.), 20),
+            MashStep::new("Mash out".to_owned(), Temperature::from_celsius(78.), 10),
+        ];
+        let schedule = calculate_mash_schedule(
+            &Mass::from_kilograms(5.),
+            &Temperature::from_celsius(18.),
+            2.5,
+            &steps,
+        )
+        .unwrap();
+
+        assert_eq!(4, schedule.len());
+        assert_approx!(55.248, schedule[0].water_temperature.as_celsius());
+        assert_approx!(12.5, schedule[0].volume.as_litres());
+        assert_approx!(5.1122, schedule[1].volume.as_litres());
+        assert_approx!(100., schedule[1].water_temperature.as_celsius());
+        assert_approx!(17.6122, schedule[1].total_volume.as_litres());
+        assert_approx!(6.32, schedule[2].volume.as_litres());
+        assert_approx!(7.086, schedule[3].volume.as_litres());
+        assert_approx!(31.0182, schedule[3].total_volume.as_litres());
+
+        let steps = vec![steps[1].clone(), steps[0].clone()];
+        assert_eq!(
+            MashError::TemperatureDecrease,
+            calculate_mash_schedule(
+                &Mass::from_kilograms(5.),
+                &Temperature::from_celsius(18.),
+                2.5,
+                &steps,
+            )
+            .unwrap_err()
+        );
+    }
+
+    #[test]
+    fn decoction_volume() {
+        assert_approx!(
+            11.25,
+            calculate_decoction_volume(
+                &Temperature::from_celsius(50.),
+                &Temperature::from_celsius(65.),
+                &Volume::from_litres(30.),
+            )
+            .unwrap()
+            .as_litres()
+        );
+        assert_eq!(
+            Err(MashError::DecoctionTooLarge),
+            calculate_decoction_volume(
+                &Temperature::from_celsius(70.),
+                &Temperature::from_celsius(95.),
+                &Volume::from_litres(30.),
+            )
+            .map(|v| v.as_litres())
+        );
+        assert_eq!(
+            Err(MashError::TemperatureDecrease),
+            calculate_decoction_volume(
+                &Temperature::from_celsius(65.),
+                &Temperature::from_celsius(50.),
+                &Volume::from_litres(30.),
+            )
+            .map(|v| v.as_litres())
+        );
+    }
+
+    #[test]
+    fn decoction_schedule() {
+        let volume = Volume::from_litres(30.);
+        let single =
+            calculate_decoction_schedule(&volume, &DecoctionSchedule::Single.steps()).unwrap();
+        assert_eq!(1, single.len());
+        assert_approx!(0.44, single[0].fraction);
+
+        let triple =
+            calculate_decoction_schedule(&volume, &DecoctionSchedule::Triple.steps()).unwrap();
+        assert_eq!(3, triple.len());
+        assert_eq!("Protein rest", triple[0].step.name);
+        assert_approx!(8.1818, triple[0].volume.as_litres());
+        assert_approx!(11.25, triple[1].volume.as_litres());
+        assert_approx!(13.2, triple[2].volume.as_litres());
+
+        assert_eq!(
+            2,
+            calculate_decoction_schedule(&volume, &DecoctionSchedule::Double.steps())
+                .unwrap()
+                .len()
+        );
+        assert!(calculate_decoction_schedule(&volume, &[])
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn decoction_schedule_from_str() {
+        assert_eq!(Ok(DecoctionSchedule::Triple), "triple".parse());
+        assert_eq!(Ok(DecoctionSchedule::Double), "Double".parse());
+        assert!("quadruple".parse::<DecoctionSchedule>().is_err());
+    }
+}
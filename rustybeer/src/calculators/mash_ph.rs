@@ -0,0 +1,271 @@
+//! A calculator used to predict mash pH from the grist and the brewing water
+//! and the acid needed to reach a target pH.
+//!
+//! Each malt has a pH it reaches when mashed in distilled water and a
+//! buffering capacity which resists changing it. The mash settles to the pH
+//! where the acidity of the malts balances the residual alkalinity of the
+//! water:
+//!
+//! pH = (Σ m·b·pH_DI + RA - acid) / Σ m·b
+//!
+//! where m is the malt weight, b its buffering capacity, RA the residual
+//! alkalinity of the mash water and acid the acidity added by acidulated malt.
+//! Malt parameters are approximated from the measurements by Kai Troester:
+//! http://braukaiser.com/wiki/index.php/Beer_color_and_mash_pH
+//!
+//! ```
+//! use rustybeer::calculators::mash_ph::calculate_mash_ph;
+//! use rustybeer::calculators::og::FermentableAddition;
+//! use rustybeer::fermentables::FERMENTABLES;
+//! use rustybeer::measurements::{Mass, Volume};
+//! use rustybeer::water::find_water_profile;
+//!
+//! let pilsner = FERMENTABLES.iter().find(|f| f.name == "Pilsner Malt").unwrap();
+//! let grist = vec![FermentableAddition::from_fermentable(pilsner, Mass::from_kilograms(5.))];
+//! let pilsen = find_water_profile("Pilsen").unwrap();
+//!
+//! let ph = calculate_mash_ph(&grist, pilsen, &Volume::from_litres(15.)).unwrap();
+//! assert_eq!(5.68, (ph * 100.).round() / 100.);
+//! ```
+
+use crate::calculators::og::FermentableAddition;
+use crate::water::WaterProfile;
+use measurements::{Mass, Volume};
+
+/// Acidity of acidulated malt with 3% lactic acid (mEq/kg)
+const ACIDULATED_MALT_ACIDITY: f64 = 333.;
+
+/// Acidity of 88% lactic acid (mEq/ml)
+const LACTIC_ACID_ACIDITY: f64 = 11.78;
+
+/// Malts grouped by how they affect the mash pH
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MaltCategory {
+    /// Base malts, toasted malts and unmalted adjuncts
+    Base,
+    /// Caramel and crystal malts
+    Crystal,
+    /// Roasted malts and barley
+    Roast,
+    /// Malt containing lactic acid
+    Acidulated,
+}
+
+impl MaltCategory {
+    /// Categorizes the fermentable by its name and color, `None` if the
+    /// fermentable is not mashed
+    pub fn from_fermentable(fermentable: &FermentableAddition) -> Option<MaltCategory> {
+        if !fermentable.fermentable_type.is_mashed() {
+            return None;
+        }
+
+        let name = fermentable.name.to_lowercase();
+        if name.contains("acid") {
+            Some(MaltCategory::Acidulated)
+        } else if ["crystal", "caramel", "cara-pils", "special b"]
+            .iter()
+            .any(|crystal| name.contains(crystal))
+        {
+            Some(MaltCategory::Crystal)
+        } else if fermentable.color >= 200. {
+            Some(MaltCategory::Roast)
+        } else {
+            Some(MaltCategory::Base)
+        }
+    }
+
+    /// pH of the malt mashed in distilled water
+    ///
+    /// # Arguments
+    ///
+    /// * `color`: color of the malt in degrees Lovibond
+    pub fn distilled_water_ph(&self, color: f64) -> f64 {
+        match self {
+            MaltCategory::Base | MaltCategory::Acidulated => 5.72 - 0.02 * color,
+            MaltCategory::Crystal => 5.22 - 0.00504 * color,
+            MaltCategory::Roast => 4.68,
+        }
+    }
+
+    /// Buffering capacity of the malt (mEq/kg·pH)
+    pub fn buffering_capacity(&self) -> f64 {
+        match self {
+            MaltCategory::Base | MaltCategory::Acidulated => 40.,
+            MaltCategory::Crystal => 55.,
+            MaltCategory::Roast => 45.,
+        }
+    }
+
+    /// Acidity of the malt (mEq/kg)
+    pub fn acidity(&self) -> f64 {
+        match self {
+            MaltCategory::Acidulated => ACIDULATED_MALT_ACIDITY,
+            _ => 0.,
+        }
+    }
+}
+
+/// Acid additions needed to reach a target mash pH
+#[derive(Debug, Clone)]
+pub struct AcidAdditions {
+    /// volume of 88% lactic acid
+    pub lactic_acid: Volume,
+    /// weight of acidulated malt, used instead of lactic acid
+    pub acidulated_malt: Mass,
+}
+
+/// Total buffering capacity (mEq/pH), buffer weighted distilled water pH
+/// and acidity (mEq) of the grist
+fn grist_buffer(grist: &[FermentableAddition]) -> (f64, f64, f64) {
+    grist.iter().fold(
+        (0., 0., 0.),
+        |(buffer, weighted_ph, acidity), fermentable| match MaltCategory::from_fermentable(
+            fermentable,
+        ) {
+            Some(category) => {
+                let kg = fermentable.weight.as_kilograms();
+                let malt_buffer = kg * category.buffering_capacity();
+                (
+                    buffer + malt_buffer,
+                    weighted_ph + malt_buffer * category.distilled_water_ph(fermentable.color),
+                    acidity + kg * category.acidity(),
+                )
+            }
+            None => (buffer, weighted_ph, acidity),
+        },
+    )
+}
+
+/// Calculates the mash pH, `None` if the grist has nothing to mash
+///
+/// # Arguments
+///
+/// * `grist`: the grain bill
+/// * `water`: profile of the mash water
+/// * `mash_volume`: volume of the mash water
+pub fn calculate_mash_ph(
+    grist: &[FermentableAddition],
+    water: &WaterProfile,
+    mash_volume: &Volume,
+) -> Option<f64> {
+    let (buffer, weighted_ph, acidity) = grist_buffer(grist);
+    if buffer <= 0. {
+        return None;
+    }
+
+    // Residual alkalinity as CaCO3 has 50 mg/mEq
+    let alkalinity = water.residual_alkalinity() / 50. * mash_volume.as_litres();
+    Some((weighted_ph + alkalinity - acidity) / buffer)
+}
+
+/// Calculates the lactic acid or, alternatively, the acidulated malt needed
+/// to lower the mash pH to the target, ignoring the buffering of the added
+/// malt. `None` if the grist has nothing to mash.
+///
+/// # Arguments
+///
+/// * `grist`: the grain bill
+/// * `water`: profile of the mash water
+/// * `mash_volume`: volume of the mash water
+/// * `target_ph`: target mash pH
+pub fn calculate_acid_additions(
+    grist: &[FermentableAddition],
+    water: &WaterProfile,
+    mash_volume: &Volume,
+    target_ph: f64,
+) -> Option<AcidAdditions> {
+    let ph = calculate_mash_ph(grist, water, mash_volume)?;
+    let (buffer, _, _) = grist_buffer(grist);
+    let acid = (buffer * (ph - target_ph)).max(0.);
+
+    Some(AcidAdditions {
+        lactic_acid: Volume::from_milliliters(acid / LACTIC_ACID_ACIDITY),
+        acidulated_malt: Mass::from_kilograms(acid / ACIDULATED_MALT_ACIDITY),
+    })
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::assert_approx;
+    use crate::conversions::RelativeDensity;
+    use crate::fermentables::FermentableType;
+
+    fn malt(name: &str, kg: f64, color: f64) -> FermentableAddition {
+        FermentableAddition::new(
+            name.to_owned(),
+            Mass::from_kilograms(kg),
+            RelativeDensity::from_specific_gravity(1.037),
+            color,
+        )
+    }
+
+    fn stout() -> Vec<FermentableAddition> {
+        vec![
+            malt("Pale Ale Malt", 4., 3.5),
+            malt("Caramel/Crystal Malt - 60L", 0.5, 60.),
+            malt("Roasted Barley", 0.5, 300.),
+        ]
+    }
+
+    #[test]
+    fn categories() {
+        assert_eq!(
+            Some(MaltCategory::Base),
+            MaltCategory::from_fermentable(&malt("Munich Malt", 1., 9.))
+        );
+        assert_eq!(
+            Some(MaltCategory::Crystal),
+            MaltCategory::from_fermentable(&malt("Special B Malt", 1., 180.))
+        );
+        assert_eq!(
+            Some(MaltCategory::Roast),
+            MaltCategory::from_fermentable(&malt("Chocolate Malt", 1., 350.))
+        );
+        assert_eq!(
+            Some(MaltCategory::Acidulated),
+            MaltCategory::from_fermentable(&malt("Acidulated Malt", 1., 3.))
+        );
+        let mut sugar = malt("Table Sugar", 1., 0.);
+        sugar.fermentable_type = FermentableType::Sugar;
+        assert_eq!(None, MaltCategory::from_fermentable(&sugar));
+    }
+
+    #[test]
+    fn mash_ph() {
+        let distilled = WaterProfile::default();
+        let volume = Volume::from_litres(15.);
+        assert_approx!(
+            5.4502,
+            calculate_mash_ph(&stout(), &distilled, &volume).unwrap()
+        );
+
+        let dublin = crate::water::find_water_profile("Dublin").unwrap();
+        assert_approx!(
+            5.6998,
+            calculate_mash_ph(&stout(), dublin, &volume).unwrap()
+        );
+
+        let mut grist = stout();
+        grist.push(malt("Acidulated Malt", 0.1, 3.));
+        assert_approx!(
+            5.2985,
+            calculate_mash_ph(&grist, &distilled, &volume).unwrap()
+        );
+
+        assert!(calculate_mash_ph(&[], &distilled, &volume).is_none());
+    }
+
+    #[test]
+    fn acid_additions() {
+        let dublin = crate::water::find_water_profile("Dublin").unwrap();
+        let volume = Volume::from_litres(15.);
+        let acid = calculate_acid_additions(&stout(), dublin, &volume, 5.4).unwrap();
+        assert_approx!(5.3449, acid.lactic_acid.as_milliliters());
+        assert_approx!(189.0786, acid.acidulated_malt.as_grams());
+
+        let acid = calculate_acid_additions(&stout(), dublin, &volume, 5.8).unwrap();
+        assert_approx!(0., acid.lactic_acid.as_milliliters());
+        assert_approx!(0., acid.acidulated_malt.as_grams());
+    }
+}
@@ -1,12 +1,24 @@
 pub mod abv;
 pub mod alcohol_volume_weight;
+pub mod boil_off;
+pub mod bottle_pressure;
 pub mod calorie_counter;
+pub mod carbonation;
+pub mod color;
 pub mod diluting;
+pub mod efficiency;
 pub mod fg;
 pub mod ibu;
+pub mod mash;
+pub mod mash_ph;
 pub mod num_bottles;
+pub mod og;
+pub mod pitch_rate;
 pub mod priming;
+pub mod refractometer;
+pub mod salts;
 pub mod sg_correction;
+pub mod water_plan;
 pub mod yeast_viability;
 
 #[cfg(test)]
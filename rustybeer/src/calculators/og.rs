@@ -0,0 +1,216 @@
+//! A calculator used to predict original gravity of the wort from the
+//! grain bill, batch size and brewhouse efficiency.
+//!
+//! Extracts and sugars don't need to be mashed, so the efficiency only
+//! applies to grains and adjuncts.
+//!
+//! ```
+//! use rustybeer::calculators::og::{calculate_og, FermentableAddition};
+//! use rustybeer::fermentables::FERMENTABLES;
+//! use rustybeer::measurements::{Mass, Volume};
+//!
+//! let pale_malt = FERMENTABLES
+//!     .iter()
+//!     .find(|f| f.name == "Pale Malt (2 Row) US")
+//!     .unwrap();
+//! let grain_bill = vec![FermentableAddition::from_fermentable(
+//!     pale_malt,
+//!     Mass::from_pounds(10.),
+//! )];
+//!
+//! let og = calculate_og(&grain_bill, &Volume::from_gallons(5.), 0.75);
+//! assert_eq!(1.0555, (og.as_specific_gravity() * 10000.).round() / 10000.);
+//! ```
+
+use crate::conversions::RelativeDensity;
+use crate::fermentables::{Fermentable, FermentableType};
+use measurements::{Mass, Volume};
+use serde::{Deserialize, Serialize};
+
+/// A fermentable (grain, extract or sugar) added to the recipe
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FermentableAddition {
+    /// name of the fermentable
+    pub name: String,
+    /// type of the fermentable, defaults to grain
+    #[serde(rename = "type", default)]
+    pub fermentable_type: FermentableType,
+    /// weight of the fermentable, serialized in grams
+    #[serde(with = "crate::serialization::grams")]
+    pub weight: Mass,
+    /// potential extract of the fermentable as gravity of 1 lb dissolved in 1 gal
+    pub potential: RelativeDensity,
+    /// color of the fermentable in degrees Lovibond
+    pub color: f64,
+}
+
+impl FermentableAddition {
+    /// Creates a grain addition
+    pub fn new(name: String, weight: Mass, potential: RelativeDensity, color: f64) -> Self {
+        Self {
+            name,
+            fermentable_type: FermentableType::Grain,
+            weight,
+            potential,
+            color,
+        }
+    }
+
+    /// Creates an addition of a fermentable from the fermentables list
+    pub fn from_fermentable(fermentable: &Fermentable, weight: Mass) -> Self {
+        Self {
+            name: fermentable.name.clone(),
+            fermentable_type: fermentable.fermentable_type,
+            weight,
+            potential: fermentable.potential(),
+            color: fermentable.color,
+        }
+    }
+
+    /// Gravity points the addition contributes to 1 gal of wort
+    pub fn gravity_points(&self, efficiency: f64) -> f64 {
+        let points = (self.potential.as_specific_gravity() - 1.) * 1000. * self.weight.as_pounds();
+        match self.fermentable_type.is_mashed() {
+            true => points * efficiency,
+            false => points,
+        }
+    }
+}
+
+/// Calculates the original gravity
+///
+/// # Arguments
+///
+/// * `fermentables`: the grain bill
+/// * `batch_size`: volume of wort into the fermenter
+/// * `efficiency`: brewhouse efficiency as decimal (0.72 for 72%)
+pub fn calculate_og(
+    fermentables: &[FermentableAddition],
+    batch_size: &Volume,
+    efficiency: f64,
+) -> RelativeDensity {
+    let points: f64 = fermentables
+        .iter()
+        .map(|f| f.gravity_points(efficiency))
+        .sum();
+    RelativeDensity::from_specific_gravity(1. + points / batch_size.as_gallons() / 1000.)
+}
+
+/// Calculates the weights of the fermentables needed for the target original
+/// gravity, keeping their relative weights. `None` if the fermentables have no
+/// gravity points.
+///
+/// # Arguments
+///
+/// * `fermentables`: the grain bill, weights are used as the proportions
+/// * `batch_size`: volume of wort into the fermenter
+/// * `efficiency`: brewhouse efficiency as decimal (0.72 for 72%)
+/// * `target_og`: target original gravity
+pub fn calculate_fermentable_weights(
+    fermentables: &[FermentableAddition],
+    batch_size: &Volume,
+    efficiency: f64,
+    target_og: &RelativeDensity,
+) -> Option<Vec<FermentableAddition>> {
+    let points =
+        (calculate_og(fermentables, batch_size, efficiency).as_specific_gravity() - 1.) * 1000.;
+    if points <= 0. {
+        return None;
+    }
+    let ratio = (target_og.as_specific_gravity() - 1.) * 1000. / points;
+    Some(
+        fermentables
+            .iter()
+            .map(|f| FermentableAddition {
+                weight: f.weight * ratio,
+                ..f.clone()
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::assert_approx;
+
+    #[test]
+    fn og() {
+        let grain_bill = vec![
+            FermentableAddition::new(
+                "Pale Malt".to_owned(),
+                Mass::from_pounds(9.),
+                RelativeDensity::from_specific_gravity(1.037),
+                1.8,
+            ),
+            FermentableAddition::new(
+                "Crystal 40L".to_owned(),
+                Mass::from_pounds(1.),
+                RelativeDensity::from_specific_gravity(1.034),
+                40.,
+            ),
+        ];
+        assert_approx!(
+            1.0514,
+            calculate_og(&grain_bill, &Volume::from_gallons(5.), 0.7).as_specific_gravity()
+        );
+        assert_approx!(
+            1.0697,
+            calculate_og(&grain_bill, &Volume::from_gallons(5.), 0.95).as_specific_gravity()
+        );
+        assert_approx!(
+            1.,
+            calculate_og(&[], &Volume::from_gallons(5.), 0.7).as_specific_gravity()
+        );
+    }
+
+    #[test]
+    fn fermentable_weights() {
+        let grain_bill = vec![
+            FermentableAddition::new(
+                "Pale Malt".to_owned(),
+                Mass::from_kilograms(0.9),
+                RelativeDensity::from_specific_gravity(1.037),
+                1.8,
+            ),
+            FermentableAddition::new(
+                "Crystal 40L".to_owned(),
+                Mass::from_kilograms(0.1),
+                RelativeDensity::from_specific_gravity(1.034),
+                40.,
+            ),
+        ];
+        let weights = calculate_fermentable_weights(
+            &grain_bill,
+            &Volume::from_gallons(5.),
+            0.7,
+            &RelativeDensity::from_specific_gravity(1.07707),
+        )
+        .unwrap();
+        assert_approx!(13.5, weights[0].weight.as_pounds());
+        assert_approx!(1.5, weights[1].weight.as_pounds());
+
+        assert!(calculate_fermentable_weights(
+            &[],
+            &Volume::from_gallons(5.),
+            0.7,
+            &RelativeDensity::from_specific_gravity(1.050)
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn extract_ignores_efficiency() {
+        let mut extract = FermentableAddition::new(
+            "Light Dry Extract".to_owned(),
+            Mass::from_pounds(5.),
+            RelativeDensity::from_specific_gravity(1.044),
+            4.,
+        );
+        extract.fermentable_type = FermentableType::DryExtract;
+        assert_approx!(
+            1.044,
+            calculate_og(&[extract], &Volume::from_gallons(5.), 0.7).as_specific_gravity()
+        );
+    }
+}
@@ -0,0 +1,407 @@
+//! Calculators for the amount of yeast to pitch and for the yeast starters
+//! needed to grow it.
+//!
+//! Pitch rates are given in million cells per millilitre of wort per degree
+//! Plato. Starter growth can be estimated with two models:
+//!
+//! * White: a power-law fit to the growth factors in Yeast: The Practical
+//!   Guide to Beer Fermentation by Chris White, where growth depends on the
+//!   inoculation rate of the starter
+//! * Braukaiser: stir plate growth measured by Kai Troester, where growth
+//!   depends on the amount of extract per cell
+//!   (http://braukaiser.com/blog/blog/2012/11/03/a-new-yeast-growth-model/)
+//!
+//! ```
+//! use rustybeer::calculators::pitch_rate::{
+//!     calculate_packs, calculate_required_cells, calculate_starter_volume, GrowthModel,
+//!     PitchRate,
+//! };
+//! use rustybeer::conversions::RelativeDensity;
+//! use rustybeer::measurements::Volume;
+//!
+//! let og = RelativeDensity::from_specific_gravity(1.060);
+//! let cells = calculate_required_cells(&og, &Volume::from_litres(20.), PitchRate::Lager);
+//! assert_eq!(442., cells.round());
+//! assert_eq!(5, calculate_packs(cells, 100., 90.));
+//!
+//! let starter = calculate_starter_volume(
+//!     90.,
+//!     cells,
+//!     &RelativeDensity::from_specific_gravity(1.036),
+//!     GrowthModel::Braukaiser,
+//! )
+//! .unwrap();
+//! assert_eq!(2.7, (starter.as_litres() * 10.).round() / 10.);
+//! ```
+
+use crate::conversions::RelativeDensity;
+use measurements::Volume;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Largest starter considered when solving for the starter volume (l)
+const MAX_STARTER_VOLUME: f64 = 20.;
+
+/// Target pitch rate of the fermentation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum PitchRate {
+    /// 0.75 million cells/ml/°P
+    #[default]
+    Ale,
+    /// 1.0 million cells/ml/°P
+    Hybrid,
+    /// 1.5 million cells/ml/°P
+    Lager,
+}
+
+impl PitchRate {
+    /// Name of the pitch rate
+    pub fn name(&self) -> &'static str {
+        match self {
+            PitchRate::Ale => "Ale",
+            PitchRate::Hybrid => "Hybrid",
+            PitchRate::Lager => "Lager",
+        }
+    }
+
+    /// Pitch rate in million cells/ml/°P
+    pub fn rate(&self) -> f64 {
+        match self {
+            PitchRate::Ale => 0.75,
+            PitchRate::Hybrid => 1.,
+            PitchRate::Lager => 1.5,
+        }
+    }
+}
+
+impl fmt::Display for PitchRate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for PitchRate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "ale" => Ok(PitchRate::Ale),
+            "hybrid" => Ok(PitchRate::Hybrid),
+            "lager" => Ok(PitchRate::Lager),
+            _ => Err(format!("unknown pitch rate {}", s)),
+        }
+    }
+}
+
+/// Model for the yeast growth in a starter
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum GrowthModel {
+    /// Growth by inoculation rate, for starters without a stir plate
+    White,
+    /// Growth by extract per cell, for starters on a stir plate
+    #[default]
+    Braukaiser,
+}
+
+impl GrowthModel {
+    /// Name of the model
+    pub fn name(&self) -> &'static str {
+        match self {
+            GrowthModel::White => "White",
+            GrowthModel::Braukaiser => "Braukaiser",
+        }
+    }
+}
+
+impl fmt::Display for GrowthModel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for GrowthModel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "white" => Ok(GrowthModel::White),
+            "braukaiser" => Ok(GrowthModel::Braukaiser),
+            _ => Err(format!("unknown growth model {}", s)),
+        }
+    }
+}
+
+/// A single step of a yeast starter
+#[derive(Debug, Clone)]
+pub struct StarterStep {
+    /// volume of the starter wort
+    pub volume: Volume,
+    /// gravity of the starter wort
+    pub gravity: RelativeDensity,
+}
+
+impl StarterStep {
+    pub fn new(volume: Volume, gravity: RelativeDensity) -> Self {
+        Self { volume, gravity }
+    }
+}
+
+/// Yeast growth in a starter step
+#[derive(Debug, Clone)]
+pub struct StarterGrowth {
+    /// cells pitched into the step (billion)
+    pub initial_cells: f64,
+    /// cells pitched per volume of the starter (million cells/ml)
+    pub inoculation_rate: f64,
+    /// new cells grown during the step (billion)
+    pub new_cells: f64,
+    /// cells at the end of the step (billion)
+    pub total_cells: f64,
+}
+
+/// Calculates the cells needed for the fermentation (billion)
+///
+/// # Arguments
+///
+/// * `og`: original gravity of the wort
+/// * `volume`: volume of the wort
+/// * `pitch_rate`: target pitch rate
+pub fn calculate_required_cells(
+    og: &RelativeDensity,
+    volume: &Volume,
+    pitch_rate: PitchRate,
+) -> f64 {
+    pitch_rate.rate() * volume.as_millilitres() * og.as_plato() / 1000.
+}
+
+/// Calculates the number of packs or vials needed
+///
+/// # Arguments
+///
+/// * `required_cells`: cells needed (billion)
+/// * `cells_per_pack`: cells in a fresh pack (billion)
+/// * `viability`: viability of the yeast in percentage
+pub fn calculate_packs(required_cells: f64, cells_per_pack: f64, viability: f64) -> u32 {
+    let viable_cells = cells_per_pack * viability / 100.;
+    match viable_cells > 0. {
+        true => (required_cells / viable_cells).ceil().max(0.) as u32,
+        false => 0,
+    }
+}
+
+/// Calculates the yeast growth in a starter step
+///
+/// # Arguments
+///
+/// * `initial_cells`: cells pitched into the starter (billion)
+/// * `step`: the starter wort
+/// * `model`: model for the growth
+pub fn calculate_starter_growth(
+    initial_cells: f64,
+    step: &StarterStep,
+    model: GrowthModel,
+) -> StarterGrowth {
+    let millilitres = step.volume.as_millilitres();
+    let inoculation_rate = match millilitres > 0. {
+        true => initial_cells * 1000. / millilitres,
+        false => f64::INFINITY,
+    };
+
+    let new_cells = match model {
+        GrowthModel::White => {
+            let growth = 12.54793776 * inoculation_rate.powf(-0.4594858324) - 0.9994994906;
+            initial_cells * growth.max(0.)
+        }
+        GrowthModel::Braukaiser => {
+            // Grams of extract, as °P is grams of extract per 100 g of wort
+            let extract =
+                millilitres * step.gravity.as_specific_gravity() * step.gravity.as_plato() / 100.;
+            let cells_per_gram = initial_cells / extract;
+            if cells_per_gram < 1.4 {
+                1.4 * extract
+            } else if cells_per_gram < 3.5 {
+                (2.33 - 0.67 * cells_per_gram) * extract
+            } else {
+                0.
+            }
+        }
+    };
+
+    StarterGrowth {
+        initial_cells,
+        inoculation_rate,
+        new_cells,
+        total_cells: initial_cells + new_cells,
+    }
+}
+
+/// Calculates the yeast growth of a multi-step starter, where all cells
+/// of a step are pitched into the next one
+///
+/// # Arguments
+///
+/// * `initial_cells`: cells pitched into the first step (billion)
+/// * `steps`: starter steps in order
+/// * `model`: model for the growth
+pub fn calculate_starter(
+    initial_cells: f64,
+    steps: &[StarterStep],
+    model: GrowthModel,
+) -> Vec<StarterGrowth> {
+    let mut cells = initial_cells;
+    steps
+        .iter()
+        .map(|step| {
+            let growth = calculate_starter_growth(cells, step, model);
+            cells = growth.total_cells;
+            growth
+        })
+        .collect()
+}
+
+/// Calculates the volume of a single step starter needed to grow the
+/// required cells, `None` if it would be larger than 20 l
+///
+/// # Arguments
+///
+/// * `initial_cells`: cells pitched into the starter (billion)
+/// * `required_cells`: cells needed (billion)
+/// * `gravity`: gravity of the starter wort
+/// * `model`: model for the growth
+pub fn calculate_starter_volume(
+    initial_cells: f64,
+    required_cells: f64,
+    gravity: &RelativeDensity,
+    model: GrowthModel,
+) -> Option<Volume> {
+    let total_cells = |litres: f64| {
+        let step = StarterStep::new(Volume::from_litres(litres), *gravity);
+        calculate_starter_growth(initial_cells, &step, model).total_cells
+    };
+
+    if initial_cells >= required_cells {
+        return Some(Volume::from_litres(0.));
+    }
+    if total_cells(MAX_STARTER_VOLUME) < required_cells {
+        return None;
+    }
+
+    let (mut low, mut high) = (0., MAX_STARTER_VOLUME);
+    while high - low > 1e-6 {
+        let middle = (low + high) / 2.;
+        match total_cells(middle) < required_cells {
+            true => low = middle,
+            false => high = middle,
+        }
+    }
+    Some(Volume::from_litres(high))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::assert_approx;
+
+    #[test]
+    fn required_cells() {
+        let og = RelativeDensity::from_specific_gravity(1.048);
+        let volume = Volume::from_litres(20.);
+        assert_approx!(
+            178.6812,
+            calculate_required_cells(&og, &volume, PitchRate::Ale)
+        );
+        assert_approx!(
+            238.2416,
+            calculate_required_cells(&og, &volume, PitchRate::Hybrid)
+        );
+        assert_approx!(
+            357.3624,
+            calculate_required_cells(&og, &volume, PitchRate::Lager)
+        );
+    }
+
+    #[test]
+    fn packs() {
+        assert_eq!(2, calculate_packs(180., 100., 97.));
+        assert_eq!(3, calculate_packs(180., 100., 60.));
+        assert_eq!(1, calculate_packs(100., 100., 100.));
+        assert_eq!(0, calculate_packs(180., 100., 0.));
+    }
+
+    #[test]
+    fn white_growth() {
+        let step = StarterStep::new(
+            Volume::from_litres(2.),
+            RelativeDensity::from_specific_gravity(1.036),
+        );
+        let growth = calculate_starter_growth(100., &step, GrowthModel::White);
+        assert_approx!(50., growth.inoculation_rate);
+        assert_approx!(107.9813, growth.new_cells);
+        assert_approx!(207.9813, growth.total_cells);
+
+        let small = StarterStep::new(Volume::from_litres(0.1), step.gravity);
+        assert_approx!(
+            0.,
+            calculate_starter_growth(100., &small, GrowthModel::White).new_cells
+        );
+    }
+
+    #[test]
+    fn braukaiser_growth() {
+        let gravity = RelativeDensity::from_specific_gravity(1.036);
+        let step = StarterStep::new(Volume::from_litres(2.), gravity);
+        let growth = calculate_starter_growth(100., &step, GrowthModel::Braukaiser);
+        assert_approx!(261.7798, growth.new_cells);
+
+        let step = StarterStep::new(Volume::from_litres(1.), gravity);
+        let growth = calculate_starter_growth(200., &step, GrowthModel::Braukaiser);
+        assert_approx!(83.8382, growth.new_cells);
+
+        let step = StarterStep::new(Volume::from_litres(0.5), gravity);
+        let growth = calculate_starter_growth(400., &step, GrowthModel::Braukaiser);
+        assert_approx!(0., growth.new_cells);
+    }
+
+    #[test]
+    fn multi_step_starter() {
+        let gravity = RelativeDensity::from_specific_gravity(1.036);
+        let steps = vec![
+            StarterStep::new(Volume::from_litres(1.), gravity),
+            StarterStep::new(Volume::from_litres(2.), gravity),
+        ];
+        let growth = calculate_starter(50., &steps, GrowthModel::Braukaiser);
+        assert_eq!(2, growth.len());
+        assert_approx!(growth[0].total_cells, growth[1].initial_cells);
+        assert_approx!(180.8899, growth[0].total_cells);
+        assert_approx!(442.6697, growth[1].total_cells);
+    }
+
+    #[test]
+    fn starter_volume() {
+        let gravity = RelativeDensity::from_specific_gravity(1.036);
+        for model in [GrowthModel::White, GrowthModel::Braukaiser] {
+            let volume = calculate_starter_volume(100., 300., &gravity, model).unwrap();
+            let step = StarterStep::new(volume, gravity);
+            assert_approx!(
+                300.,
+                calculate_starter_growth(100., &step, model).total_cells
+            );
+        }
+        assert_approx!(
+            0.,
+            calculate_starter_volume(300., 200., &gravity, GrowthModel::White)
+                .unwrap()
+                .as_litres()
+        );
+        assert!(calculate_starter_volume(1., 5000., &gravity, GrowthModel::White).is_none());
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!(Ok(PitchRate::Lager), "lager".parse());
+        assert_eq!(Ok(GrowthModel::White), "White".parse());
+        assert!("wine".parse::<PitchRate>().is_err());
+        assert!("monod".parse::<GrowthModel>().is_err());
+    }
+}
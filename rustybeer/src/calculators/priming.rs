@@ -19,8 +19,14 @@
 //! }
 //! ```
 
+use crate::conversions::RelativeDensity;
+use crate::priming_sugars::{PrimingSugar, PRIMING_SUGARS};
 use measurements::{Mass, Temperature, Volume};
 
+/// Share of the bottle priming sugar needed in a keg, as the small headspace
+/// holds little CO2 compared to the beer
+pub const KEG_PRIMING_RATE: f64 = 0.5;
+
 /// A calculator to determine how much
 /// priming sugar should be added at
 /// bottling time for brewed beer,
@@ -31,6 +37,14 @@ pub fn calculate_co2(temp: &Temperature) -> f64 {
     3.0378 - 0.050062 * temp.as_fahrenheit() + 0.00026555 * temp.as_fahrenheit().powf(2.0)
 }
 
+/// Calculates the amount of sucrose that should be
+/// added at bottling time, based off a given
+/// temperature, amount of beer and the volume of c02.
+pub fn calculate_sucrose(temp: &Temperature, amount: &Volume, co2_volumes: f64) -> Mass {
+    let beer_co2 = calculate_co2(temp);
+    Mass::from_grams(((co2_volumes * 2.0) - (beer_co2 * 2.0)) * 2.0 * amount.as_liters())
+}
+
 /// Calculates the amount of each sugar that
 /// should be added at bottling time, based
 /// off a given temperature,
@@ -39,52 +53,76 @@ pub fn calculate_co2(temp: &Temperature) -> f64 {
 /// The returned Vec of [Sugars](struct.Sugar.html)
 /// can be printed or indexed as desired
 pub fn calculate_sugars(temp: &Temperature, amount: &Volume, co2_volumes: f64) -> Vec<Sugar> {
-    let mut sugars = vec![
-        Sugar::new(String::from("Table Sugar (sucrose)"), Mass::from_grams(1.0)),
-        Sugar::new(
-            String::from("Corn Sugar (dextrose)"),
-            Mass::from_grams(1.0 / 0.91),
-        ),
-        Sugar::new(
-            String::from("DME - All Varieties"),
-            Mass::from_grams(1.0 / 0.68),
-        ),
-        Sugar::new(
-            String::from("DME - Laaglander"),
-            Mass::from_grams(1.0 / 0.5),
-        ),
-        Sugar::new(String::from("Turbinado"), Mass::from_grams(1.0)),
-        Sugar::new(String::from("Demarara"), Mass::from_grams(1.0)),
-        Sugar::new(String::from("Corn Syrup"), Mass::from_grams(1.0 / 0.69)),
-        Sugar::new(String::from("Brown Sugar"), Mass::from_grams(1.0 / 0.89)),
-        Sugar::new(String::from("Molasses"), Mass::from_grams(1.0 / 0.71)),
-        Sugar::new(String::from("Maple Syrup"), Mass::from_grams(1.0 / 0.77)),
-        Sugar::new(String::from("Sorghum Syrup"), Mass::from_grams(1.0 / 0.69)),
-        Sugar::new(String::from("Honey"), Mass::from_grams(1.0 / 0.74)),
-        Sugar::new(
-            String::from("Belgian Candy Syrup"),
-            Mass::from_grams(1.0 / 0.63),
-        ),
-        Sugar::new(
-            String::from("Belgian Candy Sugar"),
-            Mass::from_grams(1.0 / 0.75),
-        ),
-        Sugar::new(
-            String::from("Invert Sugar Syrup"),
-            Mass::from_grams(1.0 / 0.91),
-        ),
-        Sugar::new(String::from("Black Treacle"), Mass::from_grams(1.0 / 0.87)),
-        Sugar::new(String::from("Rice Solids"), Mass::from_grams(1.0 / 0.79)),
-    ];
+    calculate_sugars_with(temp, amount, co2_volumes, &PRIMING_SUGARS)
+}
 
-    let beer_co2 = calculate_co2(temp);
-    let sucrose = ((co2_volumes * 2.0) - (beer_co2 * 2.0)) * 2.0 * amount.as_liters();
+/// Calculates the amount of the given sugars that
+/// should be added at bottling time, for example
+/// to use only some sugars of
+/// [PRIMING_SUGARS](../../priming_sugars/static.PRIMING_SUGARS.html)
+/// or sugars of your own.
+pub fn calculate_sugars_with(
+    temp: &Temperature,
+    amount: &Volume,
+    co2_volumes: f64,
+    sugars: &[PrimingSugar],
+) -> Vec<Sugar> {
+    let sucrose = calculate_sucrose(temp, amount, co2_volumes).as_grams();
+    sugars
+        .iter()
+        .map(|sugar| {
+            Sugar::new(
+                sugar.name.clone(),
+                Mass::from_grams(sucrose / sugar.sucrose_equivalent()),
+            )
+        })
+        .collect()
+}
 
-    for sugar in sugars.iter_mut() {
-        sugar.ratio = Mass::from_grams(sugar.ratio.as_grams() * sucrose);
-    }
+/// Calculates the amount of the given sugars that
+/// should be added to a keg, which needs less
+/// sugar than bottles.
+pub fn calculate_keg_sugars(
+    temp: &Temperature,
+    amount: &Volume,
+    co2_volumes: f64,
+    sugars: &[PrimingSugar],
+) -> Vec<Sugar> {
+    calculate_sugars_with(temp, amount, co2_volumes, sugars)
+        .into_iter()
+        .map(|sugar| Sugar::new(sugar.name, sugar.ratio * KEG_PRIMING_RATE))
+        .collect()
+}
 
-    sugars
+/// Calculates the volume of unfermented wort (speise or gyle)
+/// that should be added at bottling time instead of sugar.
+///
+/// # Arguments
+///
+/// * `temp`: temperature of the beer
+/// * `amount`: amount of beer
+/// * `co2_volumes`: wanted volumes of CO2
+/// * `gravity`: gravity of the wort
+/// * `attenuation`: apparent attenuation of the yeast in percentage
+///
+/// Returns `None` if the wort has no extract to ferment, i.e. the gravity is
+/// not above 1.000 or the attenuation is not positive.
+pub fn calculate_speise(
+    temp: &Temperature,
+    amount: &Volume,
+    co2_volumes: f64,
+    gravity: &RelativeDensity,
+    attenuation: f64,
+) -> Option<Volume> {
+    if gravity.as_specific_gravity() <= 1. || attenuation <= 0. {
+        return None;
+    }
+    let sucrose = calculate_sucrose(temp, amount, co2_volumes).as_grams();
+    // Extract of the wort in g/l
+    let extract = gravity.as_plato() * gravity.as_specific_gravity() * 10.;
+    Some(Volume::from_litres(
+        sucrose / (extract * attenuation / 100.),
+    ))
 }
 
 /// A sugar added at bottling time for
@@ -187,6 +225,65 @@ pub mod tests {
             ),
         ];
 
-        assert_eq!(expected, stream);
+        assert_eq!(expected.len(), stream.len());
+        for (expected, sugar) in expected.iter().zip(&stream) {
+            assert_eq!(expected.name, sugar.name);
+            assert_approx!(expected.ratio.as_grams(), sugar.ratio.as_grams());
+        }
+    }
+
+    #[test]
+    fn custom_sugars() {
+        let sugars = vec![PrimingSugar::new(String::from("Candi"), 0.8, 0.9)];
+        let temp = Temperature::from_fahrenheit(77.);
+        let amount = Volume::from_liters(5.);
+
+        let stream = calculate_sugars_with(&temp, &amount, 2., &sugars);
+        assert_eq!(1, stream.len());
+        assert_approx!(34.5146, stream[0].ratio.as_grams());
+
+        let stream = calculate_keg_sugars(&temp, &amount, 2., &sugars);
+        assert_approx!(17.2573, stream[0].ratio.as_grams());
+    }
+
+    #[test]
+    fn speise() {
+        assert_approx!(
+            0.2654,
+            calculate_speise(
+                &Temperature::from_fahrenheit(77.),
+                &Volume::from_liters(5.),
+                2.,
+                &RelativeDensity::from_specific_gravity(1.048),
+                75.
+            )
+            .unwrap()
+            .as_litres()
+        );
+        assert_approx!(
+            1.3146,
+            calculate_speise(
+                &Temperature::from_celsius(20.),
+                &Volume::from_liters(20.),
+                2.4,
+                &RelativeDensity::from_specific_gravity(1.048),
+                75.
+            )
+            .unwrap()
+            .as_litres()
+        );
+    }
+
+    #[test]
+    fn speise_without_fermentable_extract() {
+        let temp = Temperature::from_celsius(20.);
+        let amount = Volume::from_liters(20.);
+        let gravity = RelativeDensity::from_specific_gravity(1.050);
+        assert!(calculate_speise(&temp, &amount, 2.4, &gravity, 0.).is_none());
+        assert!(calculate_speise(&temp, &amount, 2.4, &gravity, -10.).is_none());
+        for sg in [1.000, 0.990] {
+            let gravity = RelativeDensity::from_specific_gravity(sg);
+            assert!(calculate_speise(&temp, &amount, 2.4, &gravity, 75.).is_none());
+        }
     }
 }
@@ -0,0 +1,203 @@
+//! A calculator used to correct refractometer readings
+//! of fermenting wort.
+//!
+//! Refractometers measure the refraction of sugar in the wort but alcohol
+//! refracts light too, so readings taken after the fermentation has started
+//! have to be corrected using the original reading. Readings are first
+//! divided by the wort correction factor of the refractometer, which
+//! accounts for the other dissolved solids in wort.
+//!
+//! ```
+//! use rustybeer::calculators::refractometer::{
+//!     calculate_refractometer, RefractometerFormula, DEFAULT_WORT_CORRECTION_FACTOR,
+//! };
+//!
+//! let reading = calculate_refractometer(
+//!     12.,
+//!     6.5,
+//!     DEFAULT_WORT_CORRECTION_FACTOR,
+//!     RefractometerFormula::TerrillCubic,
+//! );
+//! assert_eq!(1.013, (reading.fg.as_specific_gravity() * 1000.).round() / 1000.);
+//! assert_eq!(4.4, (reading.abv * 10.).round() / 10.);
+//! ```
+
+use crate::calculators::abv::calculate_abv;
+use crate::conversions::RelativeDensity;
+use std::fmt;
+use std::str::FromStr;
+
+/// Wort correction factor of a typical refractometer
+pub const DEFAULT_WORT_CORRECTION_FACTOR: f64 = 1.04;
+
+/// Formula used to calculate the final gravity from refractometer readings
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum RefractometerFormula {
+    /// Cubic formula by Sean Terrill
+    #[default]
+    TerrillCubic,
+    /// Linear formula by Sean Terrill
+    TerrillLinear,
+    /// Quadratic formula by Petr Novotný
+    Novotny,
+}
+
+impl RefractometerFormula {
+    pub const ALL: [RefractometerFormula; 3] = [
+        RefractometerFormula::TerrillCubic,
+        RefractometerFormula::TerrillLinear,
+        RefractometerFormula::Novotny,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            RefractometerFormula::TerrillCubic => "Terrill cubic",
+            RefractometerFormula::TerrillLinear => "Terrill linear",
+            RefractometerFormula::Novotny => "Novotny",
+        }
+    }
+
+    /// Final gravity from the corrected original and final Brix
+    fn final_gravity(&self, original: f64, current: f64) -> f64 {
+        match self {
+            RefractometerFormula::TerrillCubic => {
+                1. - 0.0044993 * original + 0.011774 * current + 0.00027581 * original.powi(2)
+                    - 0.0012717 * current.powi(2)
+                    - 0.0000072800 * original.powi(3)
+                    + 0.000063293 * current.powi(3)
+            }
+            RefractometerFormula::TerrillLinear => 1. - 0.00085683 * original + 0.0034941 * current,
+            RefractometerFormula::Novotny => {
+                1. + 0.00001335 * original.powi(2) - 0.00003239 * original * current
+                    + 0.00002916 * current.powi(2)
+                    - 0.002421 * original
+                    + 0.006219 * current
+            }
+        }
+    }
+}
+
+impl fmt::Display for RefractometerFormula {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for RefractometerFormula {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "terrill-cubic" => Ok(RefractometerFormula::TerrillCubic),
+            "terrill-linear" => Ok(RefractometerFormula::TerrillLinear),
+            "novotny" => Ok(RefractometerFormula::Novotny),
+            _ => Err(format!("unknown refractometer formula {}", s)),
+        }
+    }
+}
+
+/// Gravities and alcohol calculated from refractometer readings
+#[derive(Debug, Clone, Copy)]
+pub struct RefractometerReading {
+    pub og: RelativeDensity,
+    pub fg: RelativeDensity,
+    /// alcohol by volume in percentage
+    pub abv: f64,
+}
+
+/// Calculates the original gravity from the original refractometer reading
+///
+/// # Arguments
+///
+/// * `original_brix`: refractometer reading before fermentation
+/// * `wort_correction_factor`: wort correction factor of the refractometer
+pub fn calculate_og(original_brix: f64, wort_correction_factor: f64) -> RelativeDensity {
+    RelativeDensity::from_brix(original_brix / wort_correction_factor)
+}
+
+/// Calculates the real gravity of fermenting wort
+///
+/// # Arguments
+///
+/// * `original_brix`: refractometer reading before fermentation
+/// * `current_brix`: refractometer reading of the fermenting wort
+/// * `wort_correction_factor`: wort correction factor of the refractometer
+/// * `formula`: formula to correct the reading with
+pub fn calculate_fg(
+    original_brix: f64,
+    current_brix: f64,
+    wort_correction_factor: f64,
+    formula: RefractometerFormula,
+) -> RelativeDensity {
+    RelativeDensity::from_specific_gravity(formula.final_gravity(
+        original_brix / wort_correction_factor,
+        current_brix / wort_correction_factor,
+    ))
+}
+
+/// Calculates the original gravity, real gravity and alcohol by volume of
+/// fermenting wort
+///
+/// # Arguments
+///
+/// * `original_brix`: refractometer reading before fermentation
+/// * `current_brix`: refractometer reading of the fermenting wort
+/// * `wort_correction_factor`: wort correction factor of the refractometer
+/// * `formula`: formula to correct the reading with
+pub fn calculate_refractometer(
+    original_brix: f64,
+    current_brix: f64,
+    wort_correction_factor: f64,
+    formula: RefractometerFormula,
+) -> RefractometerReading {
+    let og = calculate_og(original_brix, wort_correction_factor);
+    let fg = calculate_fg(original_brix, current_brix, wort_correction_factor, formula);
+    RefractometerReading {
+        og,
+        fg,
+        abv: calculate_abv(&og, &fg),
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::assert_approx;
+
+    #[test]
+    fn og() {
+        assert_approx!(1.046442, calculate_og(12., 1.04).as_specific_gravity());
+        assert_approx!(1.048378, calculate_og(12., 1.).as_specific_gravity());
+    }
+
+    #[test]
+    fn fg() {
+        assert_approx!(
+            1.012986,
+            calculate_fg(12., 6.5, 1.04, RefractometerFormula::TerrillCubic).as_specific_gravity()
+        );
+        assert_approx!(
+            1.011952,
+            calculate_fg(12., 6.5, 1.04, RefractometerFormula::TerrillLinear).as_specific_gravity()
+        );
+        assert_approx!(
+            1.011515,
+            calculate_fg(12., 6.5, 1.04, RefractometerFormula::Novotny).as_specific_gravity()
+        );
+        assert_approx!(
+            1.013329,
+            calculate_fg(12., 6.5, 1., RefractometerFormula::TerrillCubic).as_specific_gravity()
+        );
+    }
+
+    #[test]
+    fn refractometer() {
+        let reading = calculate_refractometer(12., 6.5, 1.04, RefractometerFormula::TerrillCubic);
+        assert_approx!(1.046442, reading.og.as_specific_gravity());
+        assert_approx!(1.012986, reading.fg.as_specific_gravity());
+        assert_approx!(4.391, reading.abv);
+
+        let reading = calculate_refractometer(12., 6.5, 1., RefractometerFormula::Novotny);
+        assert_approx!(4.7747, reading.abv);
+    }
+}
@@ -0,0 +1,376 @@
+//! Calculators for the ion profile of brewing water after salt additions
+//! and for the salt additions that get closest to a target profile.
+//!
+//! Each salt dissolves into its ions in proportion to their share of the
+//! molar mass of the salt. Chalk is assumed to be dissolved with the help
+//! of carbon dioxide, so each carbonate becomes two bicarbonates.
+//!
+//! ```
+//! use rustybeer::calculators::salts::{calculate_water_profile, Salt, SaltAddition};
+//! use rustybeer::measurements::{Mass, Volume};
+//! use rustybeer::water::find_water_profile;
+//!
+//! let pilsen = find_water_profile("Pilsen").unwrap();
+//! let additions = vec![SaltAddition::new(Salt::Gypsum, Mass::from_grams(5.))];
+//! let water = calculate_water_profile(pilsen, &Volume::from_litres(20.), &additions);
+//!
+//! assert_eq!(68., water.calcium.round());
+//! assert_eq!(143., water.sulfate.round());
+//! assert_eq!(36., water.sulfate_chloride_ratio().round());
+//! ```
+
+use crate::least_squares::solve_bounded;
+use crate::water::WaterProfile;
+use measurements::{Mass, Volume};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Molar masses (g/mol) of the ions and salts
+const CALCIUM: f64 = 40.078;
+const MAGNESIUM: f64 = 24.305;
+const SODIUM: f64 = 22.990;
+const CHLORIDE: f64 = 35.453;
+const SULFATE: f64 = 96.06;
+const BICARBONATE: f64 = 61.017;
+const CARBONATE: f64 = 60.008;
+const WATER: f64 = 18.015;
+
+/// Brewing salts
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Salt {
+    /// Calcium sulfate dihydrate, CaSO4·2H2O
+    Gypsum,
+    /// Calcium chloride dihydrate, CaCl2·2H2O
+    CalciumChloride,
+    /// Magnesium sulfate heptahydrate, MgSO4·7H2O
+    EpsomSalt,
+    /// Sodium bicarbonate, NaHCO3
+    BakingSoda,
+    /// Calcium carbonate, CaCO3
+    Chalk,
+    /// Sodium chloride, NaCl
+    TableSalt,
+}
+
+impl Salt {
+    /// All salts
+    pub const ALL: [Salt; 6] = [
+        Salt::Gypsum,
+        Salt::CalciumChloride,
+        Salt::EpsomSalt,
+        Salt::BakingSoda,
+        Salt::Chalk,
+        Salt::TableSalt,
+    ];
+
+    /// Name of the salt
+    pub fn name(&self) -> &'static str {
+        match self {
+            Salt::Gypsum => "Gypsum",
+            Salt::CalciumChloride => "Calcium Chloride",
+            Salt::EpsomSalt => "Epsom Salt",
+            Salt::BakingSoda => "Baking Soda",
+            Salt::Chalk => "Chalk",
+            Salt::TableSalt => "Table Salt",
+        }
+    }
+
+    /// Ion concentrations (ppm) of 1 g of the salt dissolved in 1 l of water
+    pub fn ions_per_gram_litre(&self) -> WaterProfile {
+        let ppm = |ion: f64, salt: f64| ion / salt * 1000.;
+        let mut ions = WaterProfile {
+            name: self.name().to_owned(),
+            ..Default::default()
+        };
+        match self {
+            Salt::Gypsum => {
+                let salt = CALCIUM + SULFATE + 2. * WATER;
+                ions.calcium = ppm(CALCIUM, salt);
+                ions.sulfate = ppm(SULFATE, salt);
+            }
+            Salt::CalciumChloride => {
+                let salt = CALCIUM + 2. * CHLORIDE + 2. * WATER;
+                ions.calcium = ppm(CALCIUM, salt);
+                ions.chloride = ppm(2. * CHLORIDE, salt);
+            }
+            Salt::EpsomSalt => {
+                let salt = MAGNESIUM + SULFATE + 7. * WATER;
+                ions.magnesium = ppm(MAGNESIUM, salt);
+                ions.sulfate = ppm(SULFATE, salt);
+            }
+            Salt::BakingSoda => {
+                let salt = SODIUM + BICARBONATE;
+                ions.sodium = ppm(SODIUM, salt);
+                ions.bicarbonate = ppm(BICARBONATE, salt);
+            }
+            Salt::Chalk => {
+                // CaCO3 + CO2 + H2O -> Ca + 2 HCO3
+                let salt = CALCIUM + CARBONATE;
+                ions.calcium = ppm(CALCIUM, salt);
+                ions.bicarbonate = ppm(2. * BICARBONATE, salt);
+            }
+            Salt::TableSalt => {
+                let salt = SODIUM + CHLORIDE;
+                ions.sodium = ppm(SODIUM, salt);
+                ions.chloride = ppm(CHLORIDE, salt);
+            }
+        }
+        ions
+    }
+}
+
+impl fmt::Display for Salt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Salt {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace(['-', '_', ' '], "").as_str() {
+            "gypsum" => Ok(Salt::Gypsum),
+            "calciumchloride" | "cacl2" => Ok(Salt::CalciumChloride),
+            "epsomsalt" | "epsom" => Ok(Salt::EpsomSalt),
+            "bakingsoda" => Ok(Salt::BakingSoda),
+            "chalk" => Ok(Salt::Chalk),
+            "tablesalt" | "salt" => Ok(Salt::TableSalt),
+            _ => Err(format!("unknown salt {}", s)),
+        }
+    }
+}
+
+/// A salt added to the brewing water
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaltAddition {
+    pub salt: Salt,
+    /// weight of the salt, serialized in grams
+    #[serde(with = "crate::serialization::grams")]
+    pub weight: Mass,
+}
+
+impl SaltAddition {
+    pub fn new(salt: Salt, weight: Mass) -> Self {
+        Self { salt, weight }
+    }
+}
+
+/// Calculates the ion profile of the water after the salt additions
+///
+/// # Arguments
+///
+/// * `source`: profile of the source water
+/// * `volume`: volume of the water
+/// * `additions`: salts added to the water
+pub fn calculate_water_profile(
+    source: &WaterProfile,
+    volume: &Volume,
+    additions: &[SaltAddition],
+) -> WaterProfile {
+    additions
+        .iter()
+        .fold(source.clone(), |mut water, addition| {
+            let ions = addition.salt.ions_per_gram_litre();
+            let concentration = addition.weight.as_grams() / volume.as_litres();
+            water.calcium += ions.calcium * concentration;
+            water.magnesium += ions.magnesium * concentration;
+            water.sodium += ions.sodium * concentration;
+            water.chloride += ions.chloride * concentration;
+            water.sulfate += ions.sulfate * concentration;
+            water.bicarbonate += ions.bicarbonate * concentration;
+            water
+        })
+}
+
+/// Salt additions solved for a target water profile
+#[derive(Debug, Clone)]
+pub struct SaltSolution {
+    /// weights of the salts, in the order they were allowed
+    pub additions: Vec<SaltAddition>,
+    /// profile of the water after the additions
+    pub water: WaterProfile,
+    /// target minus resulting concentration of each ion (ppm)
+    pub residual: WaterProfile,
+}
+
+fn ions(water: &WaterProfile) -> [f64; 6] {
+    [
+        water.calcium,
+        water.magnesium,
+        water.sodium,
+        water.chloride,
+        water.sulfate,
+        water.bicarbonate,
+    ]
+}
+
+/// Calculates the non-negative salt additions which minimize the squared
+/// error between the resulting and target ion concentrations
+///
+/// Ions which are already above the target in the source water can't be
+/// reduced with salts, so they show up as negative residuals.
+///
+/// # Arguments
+///
+/// * `source`: profile of the source water
+/// * `target`: profile to aim for
+/// * `volume`: volume of the water
+/// * `salts`: salts which may be added
+pub fn calculate_salt_additions(
+    source: &WaterProfile,
+    target: &WaterProfile,
+    volume: &Volume,
+    salts: &[Salt],
+) -> SaltSolution {
+    let columns: Vec<[f64; 6]> = salts
+        .iter()
+        .map(|salt| ions(&salt.ions_per_gram_litre()))
+        .collect();
+    let a: Vec<Vec<f64>> = (0..6)
+        .map(|i| columns.iter().map(|column| column[i]).collect())
+        .collect();
+    let b: Vec<f64> = ions(target)
+        .iter()
+        .zip(ions(source))
+        .map(|(target, source)| target - source)
+        .collect();
+    let concentrations = solve_bounded(
+        &a,
+        &b,
+        &vec![0.; salts.len()],
+        &vec![f64::INFINITY; salts.len()],
+    );
+
+    let additions: Vec<SaltAddition> = salts
+        .iter()
+        .zip(concentrations)
+        .map(|(&salt, concentration)| {
+            SaltAddition::new(salt, Mass::from_grams(concentration * volume.as_litres()))
+        })
+        .collect();
+    let water = calculate_water_profile(source, volume, &additions);
+    let residual = WaterProfile {
+        name: "Residual".to_owned(),
+        calcium: target.calcium - water.calcium,
+        magnesium: target.magnesium - water.magnesium,
+        sodium: target.sodium - water.sodium,
+        chloride: target.chloride - water.chloride,
+        sulfate: target.sulfate - water.sulfate,
+        bicarbonate: target.bicarbonate - water.bicarbonate,
+    };
+
+    SaltSolution {
+        additions,
+        water,
+        residual,
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::assert_approx;
+
+    #[test]
+    fn ions_per_gram_litre() {
+        let gypsum = Salt::Gypsum.ions_per_gram_litre();
+        assert_approx!(232.7843, gypsum.calcium);
+        assert_approx!(557.9434, gypsum.sulfate);
+        let chalk = Salt::Chalk.ions_per_gram_litre();
+        assert_approx!(400.4356, chalk.calcium);
+        assert_approx!(1219.2914, chalk.bicarbonate);
+        let salt = Salt::TableSalt.ions_per_gram_litre();
+        assert_approx!(393.3747, salt.sodium);
+        assert_approx!(606.6253, salt.chloride);
+    }
+
+    #[test]
+    fn water_profile() {
+        let source = WaterProfile {
+            name: "Tap".to_owned(),
+            calcium: 20.,
+            magnesium: 5.,
+            sodium: 10.,
+            chloride: 15.,
+            sulfate: 10.,
+            bicarbonate: 50.,
+        };
+        let additions = vec![
+            SaltAddition::new(Salt::Gypsum, Mass::from_grams(4.)),
+            SaltAddition::new(Salt::CalciumChloride, Mass::from_grams(2.)),
+            SaltAddition::new(Salt::EpsomSalt, Mass::from_grams(1.)),
+        ];
+        let water = calculate_water_profile(&source, &Volume::from_litres(25.), &additions);
+        assert_eq!("Tap", water.name);
+        assert_approx!(79.0546, water.calcium);
+        assert_approx!(8.9445, water.magnesium);
+        assert_approx!(10., water.sodium);
+        assert_approx!(53.5846, water.chloride);
+        assert_approx!(114.8607, water.sulfate);
+        assert_approx!(50., water.bicarbonate);
+        assert_approx!(2.1435, water.sulfate_chloride_ratio());
+
+        assert_eq!(
+            source,
+            calculate_water_profile(&source, &Volume::from_litres(25.), &[])
+        );
+    }
+
+    #[test]
+    fn salt_from_str() {
+        assert_eq!(Ok(Salt::CalciumChloride), "calcium-chloride".parse());
+        assert_eq!(Ok(Salt::EpsomSalt), "Epsom Salt".parse());
+        assert_eq!(Ok(Salt::BakingSoda), "baking_soda".parse());
+        assert!("sugar".parse::<Salt>().is_err());
+        for salt in Salt::ALL {
+            assert_eq!(Ok(salt), salt.name().parse());
+        }
+    }
+
+    #[test]
+    fn salt_additions_exact() {
+        let source = WaterProfile::default();
+        let volume = Volume::from_litres(20.);
+        let additions = vec![
+            SaltAddition::new(Salt::Gypsum, Mass::from_grams(6.)),
+            SaltAddition::new(Salt::TableSalt, Mass::from_grams(1.)),
+        ];
+        let target = calculate_water_profile(&source, &volume, &additions);
+
+        let solution = calculate_salt_additions(&source, &target, &volume, &Salt::ALL);
+        assert_eq!(Salt::ALL.len(), solution.additions.len());
+        assert_approx!(6., solution.additions[0].weight.as_grams());
+        assert_approx!(0., solution.additions[1].weight.as_grams());
+        assert_approx!(1., solution.additions[5].weight.as_grams());
+        assert_approx!(target.sulfate, solution.water.sulfate);
+        assert_approx!(0., solution.residual.calcium);
+    }
+
+    #[test]
+    fn salt_additions_non_negative() {
+        let burton = crate::water::find_water_profile("Burton").unwrap();
+        let pilsen = crate::water::find_water_profile("Pilsen").unwrap();
+        let solution =
+            calculate_salt_additions(burton, pilsen, &Volume::from_litres(20.), &Salt::ALL);
+        for addition in &solution.additions {
+            assert_approx!(0., addition.weight.as_grams());
+        }
+        assert_approx!(-721., solution.residual.sulfate);
+
+        let solution = calculate_salt_additions(
+            pilsen,
+            burton,
+            &Volume::from_litres(20.),
+            &[Salt::Gypsum, Salt::EpsomSalt],
+        );
+        assert_eq!(2, solution.additions.len());
+        assert!(solution.additions[0].weight.as_grams() > 20.);
+        assert!(solution.additions[1].weight.as_grams() > 0.);
+        assert_approx!(
+            burton.sulfate - solution.water.sulfate,
+            solution.residual.sulfate
+        );
+    }
+}
@@ -1 +1,5 @@
+pub(crate) mod daniels_utilization_test_vector;
+pub(crate) mod garetz_utilization_test_vector;
+pub(crate) mod ibu_models_regression;
+pub(crate) mod rager_utilization_test_vector;
 pub(crate) mod utilization_test_vector;
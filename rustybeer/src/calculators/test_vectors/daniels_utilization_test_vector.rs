@@ -0,0 +1,31 @@
+/// Utilization table of whole and pellet hops published by Ray Daniels in
+/// "Designing Great Beers", 1996.
+///
+/// The table is given for boil gravity up to 1.050. Above it the utilization
+/// is divided by the gravity adjustment `1 + (G - 1.050) / 0.2`.
+pub(crate) struct TestVector {
+    pub og: Vec<f64>,
+    /// first and last minute of each boil time range of the table
+    pub boiling_time: Vec<(u32, u32)>,
+    pub whole: Vec<f64>,
+    pub pellet: Vec<f64>,
+}
+
+pub(crate) fn get_vector() -> TestVector {
+    TestVector {
+        og: vec![
+            1.030, 1.040, 1.050, 1.060, 1.070, 1.080, 1.090, 1.100, 1.110, 1.120, 1.130,
+        ],
+        boiling_time: vec![
+            (0, 9),
+            (10, 19),
+            (20, 29),
+            (30, 44),
+            (45, 59),
+            (60, 74),
+            (75, 120),
+        ],
+        whole: vec![0.05, 0.12, 0.15, 0.19, 0.22, 0.24, 0.27],
+        pellet: vec![0.06, 0.15, 0.19, 0.24, 0.27, 0.30, 0.34],
+    }
+}
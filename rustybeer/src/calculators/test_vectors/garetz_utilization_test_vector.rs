@@ -0,0 +1,37 @@
+/// Utilization table published by Mark Garetz in "Using Hops", 1994.
+///
+/// The table is given for boil gravity up to 1.050 at sea level. Above it the
+/// utilization is divided by the gravity factor `1 + (G - 1.050) / 0.2`.
+pub(crate) struct TestVector {
+    pub og: Vec<f64>,
+    /// first and last minute of each boil time range of the table
+    pub boiling_time: Vec<(u32, u32)>,
+    pub utilization: Vec<f64>,
+}
+
+pub(crate) fn get_vector() -> TestVector {
+    TestVector {
+        og: vec![
+            1.030, 1.040, 1.050, 1.060, 1.070, 1.080, 1.090, 1.100, 1.110, 1.120, 1.130,
+        ],
+        boiling_time: vec![
+            (0, 5),
+            (6, 10),
+            (11, 15),
+            (16, 20),
+            (21, 25),
+            (26, 30),
+            (31, 35),
+            (36, 40),
+            (41, 45),
+            (46, 50),
+            (51, 60),
+            (61, 70),
+            (71, 80),
+            (81, 90),
+        ],
+        utilization: vec![
+            0.00, 0.00, 0.02, 0.05, 0.08, 0.11, 0.14, 0.16, 0.18, 0.19, 0.20, 0.21, 0.22, 0.23,
+        ],
+    }
+}
@@ -0,0 +1,41 @@
+/// Regression snapshot of the IBU of a fixed hop schedule in 20 l of wort
+/// estimated with each model.
+///
+/// The values were recorded from this implementation, not taken from
+/// published worked examples, so they only catch unintended changes and
+/// can't detect an error in the formulas themselves. The utilization tables of
+/// Rager, Daniels and Garetz are checked against the published values in their
+/// own test vectors; the Garetz hopping rate factor and the mIBU post-boil
+/// isomerization are only covered here.
+///
+/// Garetz is calculated at 1500 m elevation with yeast factor 1.05 and
+/// filtration factor 1.015, mIBU with 20 min steep and 10 min cooling.
+pub(crate) struct RegressionSnapshot {
+    /// weight (g), alpha acid, boil time (min) and whether the hops are pellets
+    pub hops: Vec<(f64, f64, u32, bool)>,
+    pub volume_liters: f64,
+    pub gravity: Vec<f64>,
+    pub tinseth: Vec<f64>,
+    pub rager: Vec<f64>,
+    pub garetz: Vec<f64>,
+    pub daniels: Vec<f64>,
+    pub mibu: Vec<f64>,
+}
+
+pub(crate) fn get_snapshot() -> RegressionSnapshot {
+    RegressionSnapshot {
+        hops: vec![
+            (28., 0.12, 60, true),
+            (20., 0.065, 15, false),
+            (30., 0.055, 5, true),
+            (40., 0.07, 0, true),
+        ],
+        volume_liters: 20.,
+        gravity: vec![1.040, 1.060, 1.080],
+        tinseth: vec![59.3398, 49.5774, 41.4212],
+        rager: vec![75.3809, 71.7914, 65.5486],
+        garetz: vec![27.5344, 26.3333, 24.2220],
+        daniels: vec![71.5500, 68.1429, 62.2174],
+        mibu: vec![78.5373, 65.6167, 54.8217],
+    }
+}
@@ -0,0 +1,35 @@
+/// Utilization table of whole hops published by Jackie Rager in
+/// "Calculating Hop Bitterness in Beer", Zymurgy Special Issue 1990.
+///
+/// The table is given for boil gravity up to 1.050. Above it the utilization
+/// is divided by the published gravity adjustment `1 + (G - 1.050) / 0.2`.
+pub(crate) struct TestVector {
+    pub og: Vec<f64>,
+    /// first and last minute of each boil time range of the table
+    pub boiling_time: Vec<(u32, u32)>,
+    pub utilization: Vec<f64>,
+}
+
+pub(crate) fn get_vector() -> TestVector {
+    TestVector {
+        og: vec![
+            1.030, 1.040, 1.050, 1.060, 1.070, 1.080, 1.090, 1.100, 1.110, 1.120, 1.130,
+        ],
+        boiling_time: vec![
+            (0, 5),
+            (6, 10),
+            (11, 15),
+            (16, 20),
+            (21, 25),
+            (26, 30),
+            (31, 35),
+            (36, 40),
+            (41, 45),
+            (46, 50),
+            (51, 60),
+        ],
+        utilization: vec![
+            0.050, 0.060, 0.080, 0.101, 0.121, 0.153, 0.188, 0.228, 0.269, 0.281, 0.300,
+        ],
+    }
+}
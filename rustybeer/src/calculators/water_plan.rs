@@ -0,0 +1,256 @@
+//! A calculator used to plan the water needed for a brew day
+//!
+//! The pre-boil volume comes from the packaged volume and the equipment
+//! losses like in the boil-off calculator. On top of it the grain absorbs
+//! water and some wort is left in the dead space of the mash tun. The total
+//! water is split between the mash and the sparge depending on the sparge
+//! method:
+//!
+//! * Fly sparge mashes with the given mash thickness and sparges the rest
+//! * Batch sparge tops up the mash so that both runnings are equal
+//! * No sparge (or brew in a bag) mashes with all of the water
+//!
+//! ```
+//! use rustybeer::calculators::og::FermentableAddition;
+//! use rustybeer::calculators::water_plan::{calculate_water_plan, SpargeMethod};
+//! use rustybeer::equipment::EquipmentProfile;
+//! use rustybeer::fermentables::FERMENTABLES;
+//! use rustybeer::measurements::{Mass, Volume};
+//!
+//! let pale_malt = FERMENTABLES.iter().find(|f| f.name == "Pale Malt (2 Row) US").unwrap();
+//! let grist = vec![FermentableAddition::from_fermentable(pale_malt, Mass::from_kilograms(5.))];
+//!
+//! let plan = calculate_water_plan(
+//!     &grist,
+//!     &Volume::from_litres(20.),
+//!     60,
+//!     3.,
+//!     0.75,
+//!     SpargeMethod::Fly,
+//!     &EquipmentProfile::default(),
+//! );
+//! assert_eq!(15., plan.mash_water.as_litres().round());
+//! assert_eq!(33., plan.total_water.as_litres().round());
+//! ```
+
+use crate::calculators::boil_off::calculate_boil_volumes;
+use crate::calculators::og::{calculate_og, FermentableAddition};
+use crate::conversions::RelativeDensity;
+use crate::equipment::EquipmentProfile;
+use measurements::Volume;
+use std::fmt;
+use std::str::FromStr;
+
+/// Method used to rinse the sugars from the grain after the mash
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum SpargeMethod {
+    /// Continuous sparge while draining the mash
+    Fly,
+    /// Sparge in batches, draining the mash in between
+    #[default]
+    Batch,
+    /// No sparge, including brew in a bag
+    NoSparge,
+}
+
+impl SpargeMethod {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SpargeMethod::Fly => "Fly",
+            SpargeMethod::Batch => "Batch",
+            SpargeMethod::NoSparge => "No sparge",
+        }
+    }
+}
+
+impl fmt::Display for SpargeMethod {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for SpargeMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "fly" => Ok(SpargeMethod::Fly),
+            "batch" => Ok(SpargeMethod::Batch),
+            "no-sparge" | "biab" => Ok(SpargeMethod::NoSparge),
+            _ => Err(format!("unknown sparge method {}", s)),
+        }
+    }
+}
+
+/// Water volumes of a brew day
+#[derive(Debug, Clone)]
+pub struct WaterPlan {
+    /// water to mash in with
+    pub mash_water: Volume,
+    /// water added after the mash, one volume for each addition
+    pub sparges: Vec<Volume>,
+    /// total sparge water
+    pub sparge_water: Volume,
+    /// total water of the brew day
+    pub total_water: Volume,
+    /// water absorbed by the grain
+    pub grain_absorption: Volume,
+    /// volume of the wort before the boil
+    pub pre_boil_volume: Volume,
+    /// gravity of the wort before the boil
+    pub pre_boil_gravity: RelativeDensity,
+}
+
+/// Calculates the water needed to package the given volume of beer
+///
+/// # Arguments
+///
+/// * `grist`: the grain bill
+/// * `packaged_volume`: volume of the beer to package
+/// * `boil_time`: length of the boil in minutes
+/// * `mash_thickness`: water to grain ratio of the mash in litres per kilogram
+/// * `efficiency`: mash efficiency as decimal (0.72 for 72%)
+/// * `method`: sparge method
+/// * `equipment`: losses of the equipment
+pub fn calculate_water_plan(
+    grist: &[FermentableAddition],
+    packaged_volume: &Volume,
+    boil_time: u32,
+    mash_thickness: f64,
+    efficiency: f64,
+    method: SpargeMethod,
+    equipment: &EquipmentProfile,
+) -> WaterPlan {
+    let grain_weight: f64 = grist
+        .iter()
+        .filter(|f| f.fermentable_type.is_mashed())
+        .map(|f| f.weight.as_kilograms())
+        .sum();
+    let grain_absorption = Volume::from_litres(grain_weight * equipment.grain_absorption);
+    let mash_losses = grain_absorption + equipment.mash_tun_dead_space;
+
+    let pre_boil_volume = calculate_boil_volumes(
+        packaged_volume,
+        &RelativeDensity::from_specific_gravity(1.),
+        boil_time,
+        equipment,
+    )
+    .pre_boil;
+    let total_water = pre_boil_volume + mash_losses;
+    // A mash thicker than the whole brew day's water leaves nothing to sparge
+    let thickness_water =
+        Volume::from_litres((grain_weight * mash_thickness).min(total_water.as_litres()));
+
+    let (mash_water, sparges) = match method {
+        SpargeMethod::NoSparge => (total_water, vec![]),
+        SpargeMethod::Fly => (thickness_water, vec![total_water - thickness_water]),
+        SpargeMethod::Batch => {
+            let runnings = pre_boil_volume / 2.;
+            let first_runnings_water = runnings + mash_losses;
+            if first_runnings_water.as_litres() > thickness_water.as_litres() {
+                (
+                    thickness_water,
+                    vec![first_runnings_water - thickness_water, runnings],
+                )
+            } else {
+                (thickness_water, vec![total_water - thickness_water])
+            }
+        }
+    };
+    // Mashing with all of the water leaves nothing to sparge
+    let sparges: Vec<Volume> = sparges
+        .into_iter()
+        .filter(|sparge| sparge.as_litres() > 0.)
+        .collect();
+
+    WaterPlan {
+        mash_water,
+        sparge_water: Volume::from_litres(sparges.iter().map(Volume::as_litres).sum()),
+        sparges,
+        total_water,
+        grain_absorption,
+        pre_boil_volume,
+        pre_boil_gravity: calculate_og(grist, &pre_boil_volume, efficiency),
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::assert_approx;
+    use crate::fermentables::FERMENTABLES;
+    use measurements::Mass;
+
+    /// Grist of US pale malt and crystal malt from the fermentables list
+    fn grist() -> Vec<FermentableAddition> {
+        [
+            ("Pale Malt (2 Row) US", 4.5),
+            ("Caramel/Crystal Malt - 40L", 0.5),
+        ]
+        .iter()
+        .map(|(name, kilograms)| {
+            let fermentable = FERMENTABLES.iter().find(|f| f.name == *name).unwrap();
+            FermentableAddition::from_fermentable(fermentable, Mass::from_kilograms(*kilograms))
+        })
+        .collect()
+    }
+
+    fn brew_day(method: SpargeMethod, mash_thickness: f64) -> WaterPlan {
+        calculate_water_plan(
+            &grist(),
+            &Volume::from_litres(20.),
+            60,
+            mash_thickness,
+            0.75,
+            method,
+            &EquipmentProfile::default(),
+        )
+    }
+
+    #[test]
+    fn no_sparge() {
+        let plan = brew_day(SpargeMethod::NoSparge, 3.);
+        assert_approx!(32.9167, plan.mash_water.as_litres());
+        assert_approx!(0., plan.sparge_water.as_litres());
+        assert!(plan.sparges.is_empty());
+        assert_approx!(32.9167, plan.total_water.as_litres());
+        assert_approx!(5., plan.grain_absorption.as_litres());
+        assert_approx!(26.9167, plan.pre_boil_volume.as_litres());
+        assert_approx!(1.04267, plan.pre_boil_gravity.as_specific_gravity());
+    }
+
+    #[test]
+    fn fly_sparge() {
+        let plan = brew_day(SpargeMethod::Fly, 3.);
+        assert_approx!(15., plan.mash_water.as_litres());
+        assert_approx!(17.9167, plan.sparge_water.as_litres());
+        assert_approx!(32.9167, plan.total_water.as_litres());
+    }
+
+    #[test]
+    fn batch_sparge() {
+        let plan = brew_day(SpargeMethod::Batch, 3.);
+        assert_approx!(15., plan.mash_water.as_litres());
+        assert_eq!(2, plan.sparges.len());
+        assert_approx!(4.4583, plan.sparges[0].as_litres());
+        assert_approx!(13.4583, plan.sparges[1].as_litres());
+        assert_approx!(17.9167, plan.sparge_water.as_litres());
+
+        // Thin mash gives larger first runnings
+        let plan = brew_day(SpargeMethod::Batch, 4.);
+        assert_approx!(20., plan.mash_water.as_litres());
+        assert_eq!(1, plan.sparges.len());
+        assert_approx!(12.9167, plan.sparge_water.as_litres());
+    }
+
+    #[test]
+    fn mash_water_capped_at_total() {
+        for method in [SpargeMethod::Fly, SpargeMethod::Batch] {
+            let plan = brew_day(method, 10.);
+            assert_approx!(32.9167, plan.mash_water.as_litres());
+            assert_approx!(0., plan.sparge_water.as_litres());
+            assert!(plan.sparges.is_empty());
+            assert_approx!(32.9167, plan.total_water.as_litres());
+        }
+    }
+}
@@ -1,5 +1,33 @@
 //! A calculator used to estimate
 //! yeast viability
+//!
+//! Viability decays exponentially from the production date. Dry yeast
+//! keeps far longer than liquid yeast or harvested slurry, and all forms
+//! decay faster when stored warm: the decay rate doubles for every 10 °C
+//! above refrigerator temperature.
+//!
+//! ```
+//! use chrono::NaiveDate;
+//! use rustybeer::calculators::yeast_viability::calculate_viability;
+//! use rustybeer::measurements::Temperature;
+//! use rustybeer::yeasts::YeastForm;
+//!
+//! let produced = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+//! let today = NaiveDate::from_ymd_opt(2021, 3, 2).unwrap();
+//! let fridge = Temperature::from_celsius(4.);
+//!
+//! let liquid = calculate_viability(YeastForm::Liquid, produced, today, &fridge);
+//! let dry = calculate_viability(YeastForm::Dry, produced, today, &fridge);
+//! assert_eq!(60., liquid.round());
+//! assert_eq!(99., dry.round());
+//! ```
+
+use crate::yeasts::YeastForm;
+use chrono::NaiveDate;
+use measurements::Temperature;
+
+/// Temperature the decay rates are given for (°C)
+const REFERENCE_TEMPERATURE: f64 = 4.;
 
 pub fn calculate_yv(days: f32) -> f32 {
     let n_of_days = if days > 0.0 { days } else { 0.0 };
@@ -10,6 +38,57 @@ pub fn calculate_cc(cc: f32, days: f32) -> f32 {
     cc * (calculate_yv(days) / 100.0)
 }
 
+/// Viability at production (%) and daily decay rate at 4 °C of the form
+fn decay_model(form: YeastForm) -> (f64, f64) {
+    match form {
+        // Loses about 4% a year refrigerated
+        YeastForm::Dry => (100., 0.000112),
+        YeastForm::Liquid => (97., 0.008),
+        // Loses about 15% a week refrigerated
+        YeastForm::Slurry => (90., 0.023),
+    }
+}
+
+/// Calculates yeast viability in percentage
+///
+/// # Arguments
+///
+/// * `form`: form of the yeast
+/// * `production_date`: date the yeast was produced or harvested
+/// * `date`: date of pitching
+/// * `storage_temperature`: temperature the yeast was stored at
+pub fn calculate_viability(
+    form: YeastForm,
+    production_date: NaiveDate,
+    date: NaiveDate,
+    storage_temperature: &Temperature,
+) -> f64 {
+    let days = (date - production_date).num_days().max(0) as f64;
+    let (initial, rate) = decay_model(form);
+    let temperature_factor =
+        2_f64.powf((storage_temperature.as_celsius() - REFERENCE_TEMPERATURE) / 10.);
+    initial * (-rate * temperature_factor * days).exp()
+}
+
+/// Calculates the viable cells left of the given cell count
+///
+/// # Arguments
+///
+/// * `cells`: cell count at production
+/// * `form`: form of the yeast
+/// * `production_date`: date the yeast was produced or harvested
+/// * `date`: date of pitching
+/// * `storage_temperature`: temperature the yeast was stored at
+pub fn calculate_viable_cells(
+    cells: f64,
+    form: YeastForm,
+    production_date: NaiveDate,
+    date: NaiveDate,
+    storage_temperature: &Temperature,
+) -> f64 {
+    cells * calculate_viability(form, production_date, date, storage_temperature) / 100.
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
@@ -23,4 +102,62 @@ pub mod tests {
         assert_approx!(65.004_616, calculate_yv(50.0));
         assert_approx!(0.0, calculate_yv(3650.0));
     }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn viability_by_form() {
+        let fridge = Temperature::from_celsius(4.);
+        let produced = date(2021, 1, 1);
+        let year_later = date(2022, 1, 1);
+
+        assert_approx!(
+            97.,
+            calculate_viability(YeastForm::Liquid, produced, produced, &fridge)
+        );
+        assert_approx!(
+            65.021,
+            calculate_viability(YeastForm::Liquid, produced, date(2021, 2, 20), &fridge)
+        );
+        assert_approx!(
+            95.9944,
+            calculate_viability(YeastForm::Dry, produced, year_later, &fridge)
+        );
+        assert_approx!(
+            76.6163,
+            calculate_viability(YeastForm::Slurry, produced, date(2021, 1, 8), &fridge)
+        );
+        // Future production date counts as fresh
+        assert_approx!(
+            100.,
+            calculate_viability(YeastForm::Dry, year_later, produced, &fridge)
+        );
+    }
+
+    #[test]
+    fn viability_by_temperature() {
+        let produced = date(2021, 1, 1);
+        let year_later = date(2022, 1, 1);
+        assert_approx!(
+            84.915,
+            calculate_viability(
+                YeastForm::Dry,
+                produced,
+                year_later,
+                &Temperature::from_celsius(24.)
+            )
+        );
+        assert_approx!(
+            43.5849,
+            calculate_viable_cells(
+                100.,
+                YeastForm::Liquid,
+                produced,
+                date(2021, 2, 20),
+                &Temperature::from_celsius(14.)
+            )
+        );
+    }
 }
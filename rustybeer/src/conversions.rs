@@ -1,4 +1,4 @@
-use measurements::{Energy, Mass, Temperature, Volume};
+use measurements::{Energy, Length, Mass, Pressure, Temperature, Volume};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -213,6 +213,99 @@ impl ToMap for Volume {
     }
 }
 
+/// Used to build new measurements::Pressure structs.
+///
+/// To be removed if the dependency some time allows creating measurement units from
+/// strings.
+pub struct PressureParser;
+
+impl PressureParser {
+    /// Creates measurements::Pressure from string
+    ///
+    /// Tries to figure out the pressure unit from the string. If the string value is plain
+    /// number, it will be considered as psi. Also empty strings are considered as
+    /// zero psi in Pressure.
+    pub fn parse(val: &str) -> Result<Pressure, ParseFloatError> {
+        if val.is_empty() {
+            return Ok(Pressure::from_psi(0.0));
+        }
+
+        let re = Regex::new(r"([0-9.]*)\s?([a-zA-Z]{1,3})$").unwrap();
+        if let Some(caps) = re.captures(val) {
+            let float_val = caps.get(1).unwrap().as_str();
+            return Ok(
+                match caps.get(2).unwrap().as_str().to_lowercase().as_str() {
+                    "psi" => Pressure::from_psi(float_val.parse::<f64>()?),
+                    "bar" => Pressure::from_bars(float_val.parse::<f64>()?),
+                    "kpa" => Pressure::from_kilopascals(float_val.parse::<f64>()?),
+                    "pa" => Pressure::from_pascals(float_val.parse::<f64>()?),
+                    "atm" => Pressure::from_atmospheres(float_val.parse::<f64>()?),
+                    _ => Pressure::from_psi(val.parse::<f64>()?),
+                },
+            );
+        }
+
+        Ok(Pressure::from_psi(val.parse::<f64>()?))
+    }
+}
+
+impl ToMap for Pressure {
+    fn to_map(&self) -> HashMap<String, f64> {
+        let mut map = HashMap::new();
+        map.insert("psi".to_owned(), self.as_psi());
+        map.insert("bar".to_owned(), self.as_bars());
+        map.insert("kPa".to_owned(), self.as_kilopascals());
+        map
+    }
+}
+
+/// Used to build new measurements::Length structs.
+///
+/// To be removed if the dependency some time allows creating measurement units from
+/// strings.
+pub struct LengthParser;
+
+impl LengthParser {
+    /// Creates measurements::Length from string
+    ///
+    /// Tries to figure out the length unit from the string. If the string value is plain
+    /// number, it will be considered as metres. Also empty strings are considered as
+    /// zero metres in Length.
+    pub fn parse(val: &str) -> Result<Length, ParseFloatError> {
+        if val.is_empty() {
+            return Ok(Length::from_metres(0.0));
+        }
+
+        let re = Regex::new(r"([0-9.]*)\s?([a-zA-Z]{1,2})$").unwrap();
+        if let Some(caps) = re.captures(val) {
+            let float_val = caps.get(1).unwrap().as_str();
+            return Ok(
+                match caps.get(2).unwrap().as_str().to_lowercase().as_str() {
+                    "mm" => Length::from_millimetres(float_val.parse::<f64>()?),
+                    "cm" => Length::from_centimetres(float_val.parse::<f64>()?),
+                    "m" => Length::from_metres(float_val.parse::<f64>()?),
+                    "in" => Length::from_inches(float_val.parse::<f64>()?),
+                    "ft" => Length::from_feet(float_val.parse::<f64>()?),
+                    _ => Length::from_metres(val.parse::<f64>()?),
+                },
+            );
+        }
+
+        Ok(Length::from_metres(val.parse::<f64>()?))
+    }
+}
+
+impl ToMap for Length {
+    fn to_map(&self) -> HashMap<String, f64> {
+        let mut map = HashMap::new();
+        map.insert("m".to_owned(), self.as_metres());
+        map.insert("cm".to_owned(), self.as_centimetres());
+        map.insert("ft".to_owned(), self.as_feet());
+        map.insert("in".to_owned(), self.as_inches());
+        map
+    }
+}
+
 /// Relative density struct.
 ///
 /// Also known as specific gravity which can be presented in different units
@@ -240,8 +333,7 @@ impl RelativeDensity {
     }
 
     pub fn as_plato(&self) -> f64 {
-        (-1.0 * 616.868) + (1111.14 * self.sg) - (630.272 * self.sg.powi(2))
-            + (135.997 * self.sg.powi(3))
+        -616.868 + (1111.14 * self.sg) - (630.272 * self.sg.powi(2)) + (135.997 * self.sg.powi(3))
     }
 
     pub fn as_brix(&self) -> f64 {
@@ -327,8 +419,8 @@ impl ::std::cmp::PartialEq for RelativeDensity {
 #[cfg(test)]
 mod tests {
     use super::{
-        EnergyParser, MassParser, RelativeDensity, RelativeDensityParser, TemperatureParser,
-        VolumeParser,
+        EnergyParser, LengthParser, MassParser, PressureParser, RelativeDensity,
+        RelativeDensityParser, TemperatureParser, VolumeParser,
     };
     use approx::assert_relative_eq;
 
@@ -619,6 +711,40 @@ mod tests {
         assert_relative_eq!(123.0, VolumeParser::parse("123 P").unwrap().as_pints(),);
     }
 
+    // Pressure
+    #[test]
+    fn pressure_from_string() {
+        assert_relative_eq!(12.0, PressureParser::parse("12").unwrap().as_psi());
+        assert_relative_eq!(12.0, PressureParser::parse("12psi").unwrap().as_psi());
+        assert_relative_eq!(0.8, PressureParser::parse("0.8 bar").unwrap().as_bars());
+        assert_relative_eq!(
+            80.0,
+            PressureParser::parse("80kPa").unwrap().as_kilopascals(),
+            epsilon = 1e-9
+        );
+    }
+
+    // Length
+    #[test]
+    fn length_from_string() {
+        assert_relative_eq!(1.5, LengthParser::parse("1.5").unwrap().as_metres());
+        assert_relative_eq!(
+            150.0,
+            LengthParser::parse("150 cm").unwrap().as_centimetres(),
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(
+            5.0,
+            LengthParser::parse("5ft").unwrap().as_feet(),
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(
+            0.25,
+            LengthParser::parse("0.25in").unwrap().as_inches(),
+            epsilon = 1e-9
+        );
+    }
+
     #[test]
     fn relative_density_conversions() {
         let rd = RelativeDensity::from_specific_gravity(1.092);
@@ -0,0 +1,363 @@
+//! A recipe designer which solves the ingredient weights for a beer style.
+//!
+//! The recipe is designed to land in the middle of the OG, IBU and SRM
+//! ranges of the style. The fermentables keep their relative weights, except
+//! for the darkest one which is adjusted to reach the color. If the color
+//! can't be reached that way, all fermentables keep their relative weights
+//! and only the OG is reached. The hops keep their relative weights and are
+//! scaled to reach the IBU using the Tinseth model like the recipe.
+//!
+//! ```
+//! use rustybeer::beer_styles::find_beer_style;
+//! use rustybeer::calculators::ibu::{HopAddition, HopAdditionType};
+//! use rustybeer::calculators::og::FermentableAddition;
+//! use rustybeer::designer::design_recipe;
+//! use rustybeer::fermentables::FERMENTABLES;
+//! use rustybeer::measurements::{Mass, Volume};
+//!
+//! let find = |name: &str| FERMENTABLES.iter().find(|f| f.name == name).unwrap();
+//! // Weights are only used as proportions
+//! let grist = vec![
+//!     FermentableAddition::from_fermentable(find("Pale Malt (2 Row) US"), Mass::from_kilograms(0.9)),
+//!     FermentableAddition::from_fermentable(find("Caramel/Crystal Malt - 40L"), Mass::from_kilograms(0.1)),
+//! ];
+//! let hops = vec![HopAddition::new(Mass::from_grams(1.), 0.1, 60, HopAdditionType::Pellet)];
+//!
+//! let design = design_recipe(
+//!     "Pale Ale".to_owned(),
+//!     find_beer_style("American Pale Ale").unwrap(),
+//!     &grist,
+//!     &hops,
+//!     &Volume::from_litres(20.),
+//!     &Volume::from_litres(25.),
+//!     0.72,
+//! )
+//! .unwrap();
+//! assert!(design.og.contains());
+//! assert_eq!(40., design.ibu.value.round());
+//! assert_eq!(7.5, (design.color.value * 10.).round() / 10.);
+//! ```
+
+use crate::beer_styles::BeerStyle;
+use crate::calculators::color::{calculate_mcu_from_srm, ColorModel};
+use crate::calculators::ibu::HopAddition;
+use crate::calculators::og::{calculate_fermentable_weights, FermentableAddition};
+use crate::conversions::RelativeDensity;
+use crate::recipe::Recipe;
+use measurements::Volume;
+use std::fmt;
+
+/// Value of the recipe compared to the range of the style
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StyleFit {
+    pub value: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl StyleFit {
+    pub fn new(value: f64, min: f64, max: f64) -> Self {
+        Self { value, min, max }
+    }
+
+    /// Whether the value is within the range
+    pub fn contains(&self) -> bool {
+        self.value >= self.min && self.value <= self.max
+    }
+
+    /// Position of the value in the range, 0 at the minimum and 1 at the maximum
+    pub fn position(&self) -> f64 {
+        match self.max > self.min {
+            true => (self.value - self.min) / (self.max - self.min),
+            false => 0.5,
+        }
+    }
+}
+
+/// A designed recipe and how it fits the style
+#[derive(Debug, Clone)]
+pub struct Design {
+    pub recipe: Recipe,
+    pub og: StyleFit,
+    pub fg: StyleFit,
+    pub abv: StyleFit,
+    pub ibu: StyleFit,
+    pub color: StyleFit,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DesignError {
+    /// The fermentables have no gravity points
+    NoFermentables,
+    /// The hops don't add any bitterness, for example only dry hops
+    NoBitterness,
+}
+
+impl fmt::Display for DesignError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DesignError::NoFermentables => write!(f, "fermentables have no gravity points"),
+            DesignError::NoBitterness => write!(f, "hops don't add any bitterness"),
+        }
+    }
+}
+
+impl std::error::Error for DesignError {}
+
+/// Designs a recipe in the middle of the OG, IBU and SRM ranges of the style
+///
+/// # Arguments
+///
+/// * `name`: name of the recipe
+/// * `style`: the beer style
+/// * `fermentables`: the grain bill, weights are used as the proportions
+/// * `hops`: the hop additions, weights are used as the proportions
+/// * `batch_size`: volume of wort into the fermenter
+/// * `boil_size`: volume of wort at the start of the boil
+/// * `efficiency`: brewhouse efficiency as decimal (0.72 for 72%)
+pub fn design_recipe(
+    name: String,
+    style: &BeerStyle,
+    fermentables: &[FermentableAddition],
+    hops: &[HopAddition],
+    batch_size: &Volume,
+    boil_size: &Volume,
+    efficiency: f64,
+) -> Result<Design, DesignError> {
+    let target_og = RelativeDensity::from_specific_gravity(
+        (style.original_gravity_min + style.original_gravity_max) as f64 / 2.,
+    );
+    let target_ibu = (style.ibu_min as f64 + style.ibu_max as f64) / 2.;
+    let target_srm = (style.color_srm_min + style.color_srm_max) as f64 / 2.;
+
+    let mut recipe = Recipe::new(name, *batch_size, *boil_size, efficiency);
+    recipe.fermentables =
+        match _solve_color(fermentables, batch_size, efficiency, &target_og, target_srm) {
+            Some(fermentables) => fermentables,
+            None => calculate_fermentable_weights(fermentables, batch_size, efficiency, &target_og)
+                .ok_or(DesignError::NoFermentables)?,
+        };
+    recipe.style = Some(style.clone());
+
+    recipe.hops = hops.to_vec();
+    if !recipe.hops.is_empty() {
+        let ibu = recipe.ibu();
+        if ibu <= 0. {
+            return Err(DesignError::NoBitterness);
+        }
+        for hop in recipe.hops.iter_mut() {
+            hop.weight = hop.weight * (target_ibu / ibu);
+        }
+    }
+
+    Ok(Design {
+        og: StyleFit::new(
+            recipe.og().as_specific_gravity(),
+            style.original_gravity_min as f64,
+            style.original_gravity_max as f64,
+        ),
+        fg: StyleFit::new(
+            recipe.fg().as_specific_gravity(),
+            style.final_gravity_min as f64,
+            style.final_gravity_max as f64,
+        ),
+        abv: StyleFit::new(recipe.abv(), style.abv_min as f64, style.abv_max as f64),
+        ibu: StyleFit::new(recipe.ibu(), style.ibu_min as f64, style.ibu_max as f64),
+        color: StyleFit::new(
+            recipe.color(),
+            style.color_srm_min as f64,
+            style.color_srm_max as f64,
+        ),
+        recipe,
+    })
+}
+
+/// Internal function to solve the weights reaching both the OG and the color
+/// by scaling the darkest fermentable separately from the others. `None` if
+/// there's no such non-negative solution.
+fn _solve_color(
+    fermentables: &[FermentableAddition],
+    batch_size: &Volume,
+    efficiency: f64,
+    target_og: &RelativeDensity,
+    target_srm: f64,
+) -> Option<Vec<FermentableAddition>> {
+    let darkest = fermentables
+        .iter()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.color.total_cmp(&b.color))?
+        .0;
+    let points = |f: &FermentableAddition| f.gravity_points(efficiency);
+    let mcu = |f: &FermentableAddition| f.weight.as_pounds() * f.color;
+
+    // Gravity points and MCU of the other fermentables and the darkest one
+    let (mut others_points, mut others_mcu) = (0., 0.);
+    for (_, f) in fermentables
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != darkest)
+    {
+        others_points += points(f);
+        others_mcu += mcu(f);
+    }
+    let (dark_points, dark_mcu) = (points(&fermentables[darkest]), mcu(&fermentables[darkest]));
+
+    let target_points = (target_og.as_specific_gravity() - 1.) * 1000. * batch_size.as_gallons();
+    let target_mcu =
+        calculate_mcu_from_srm(target_srm, ColorModel::Morey) * batch_size.as_gallons();
+
+    let determinant = others_points * dark_mcu - dark_points * others_mcu;
+    if determinant.abs() < f64::EPSILON {
+        return None;
+    }
+    let others_ratio = (target_points * dark_mcu - dark_points * target_mcu) / determinant;
+    let dark_ratio = (others_points * target_mcu - target_points * others_mcu) / determinant;
+    if others_ratio < 0. || dark_ratio < 0. {
+        return None;
+    }
+
+    Some(
+        fermentables
+            .iter()
+            .enumerate()
+            .map(|(i, f)| FermentableAddition {
+                weight: f.weight
+                    * match i == darkest {
+                        true => dark_ratio,
+                        false => others_ratio,
+                    },
+                ..f.clone()
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::assert_approx;
+    use crate::beer_styles::find_beer_style;
+    use crate::calculators::ibu::HopAdditionType;
+    use crate::fermentables::FERMENTABLES;
+    use measurements::Mass;
+
+    /// Grist of US pale malt and crystal malt from the fermentables list
+    fn grist() -> Vec<FermentableAddition> {
+        [
+            ("Pale Malt (2 Row) US", 0.9),
+            ("Caramel/Crystal Malt - 40L", 0.1),
+        ]
+        .iter()
+        .map(|(name, kilograms)| {
+            let fermentable = FERMENTABLES.iter().find(|f| f.name == *name).unwrap();
+            FermentableAddition::from_fermentable(fermentable, Mass::from_kilograms(*kilograms))
+        })
+        .collect()
+    }
+
+    fn hops() -> Vec<HopAddition> {
+        vec![
+            HopAddition::new(Mass::from_grams(2.), 0.1, 60, HopAdditionType::Pellet),
+            HopAddition::new(Mass::from_grams(1.), 0.1, 10, HopAdditionType::Pellet),
+            HopAddition::dry_hop(Mass::from_grams(3.), 0.1, 4, HopAdditionType::Pellet),
+        ]
+    }
+
+    fn pale_ale(fermentables: &[FermentableAddition], hops: &[HopAddition]) -> Design {
+        design_recipe(
+            "Pale Ale".to_owned(),
+            find_beer_style("American Pale Ale").unwrap(),
+            fermentables,
+            hops,
+            &Volume::from_litres(20.),
+            &Volume::from_litres(25.),
+            0.72,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn style_fit() {
+        let fit = StyleFit::new(40., 30., 50.);
+        assert!(fit.contains());
+        assert_approx!(0.5, fit.position());
+        assert!(!StyleFit::new(55., 30., 50.).contains());
+        assert_approx!(0.5, StyleFit::new(5., 5., 5.).position());
+    }
+
+    #[test]
+    fn middle_of_style() {
+        let design = pale_ale(&grist(), &hops());
+        assert_approx!(1.0525, design.og.value);
+        assert_approx!(40., design.ibu.value);
+        assert_approx!(7.5, design.color.value);
+        assert!(design.fg.contains());
+        assert!(design.abv.contains());
+
+        let recipe = &design.recipe;
+        assert_eq!("American Pale Ale", recipe.style.as_ref().unwrap().name);
+        assert_approx!(4.3220, recipe.fermentables[0].weight.as_kilograms());
+        assert_approx!(0.4363, recipe.fermentables[1].weight.as_kilograms());
+        // Hops keep their proportions
+        assert_approx!(
+            2.,
+            recipe.hops[0].weight.as_grams() / recipe.hops[1].weight.as_grams()
+        );
+        assert_approx!(
+            3.,
+            recipe.hops[2].weight.as_grams() / recipe.hops[1].weight.as_grams()
+        );
+    }
+
+    #[test]
+    fn color_out_of_reach() {
+        // Single fermentable can't be adjusted for the color
+        let design = pale_ale(&grist()[..1], &[]);
+        assert_approx!(1.0525, design.og.value);
+        assert!(!design.color.contains());
+        assert_approx!(0., design.ibu.value);
+
+        // Too dark even without the darkest fermentable
+        let mut fermentables = grist();
+        fermentables[0].color = 20.;
+        let design = pale_ale(&fermentables, &hops());
+        assert_approx!(1.0525, design.og.value);
+        assert_approx!(
+            9.,
+            design.recipe.fermentables[0].weight.as_kilograms()
+                / design.recipe.fermentables[1].weight.as_kilograms()
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let style = find_beer_style("American Pale Ale").unwrap();
+        let volume = Volume::from_litres(20.);
+        assert_eq!(
+            Some(DesignError::NoFermentables),
+            design_recipe(
+                "Empty".to_owned(),
+                style,
+                &[],
+                &hops(),
+                &volume,
+                &volume,
+                0.72
+            )
+            .err()
+        );
+        assert_eq!(
+            Some(DesignError::NoBitterness),
+            design_recipe(
+                "Dry Hopped".to_owned(),
+                style,
+                &grist(),
+                &hops()[2..],
+                &volume,
+                &volume,
+                0.72
+            )
+            .err()
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use measurements::Volume;
+use serde::{Deserialize, Serialize};
+
+/// Brewing equipment losses used to calculate the volumes of a brew day.
+///
+/// Missing values default to the ones of a typical 20 litre home brewing
+/// system when deserialised.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct EquipmentProfile {
+    pub name: String,
+    /// volume evaporated from the kettle in an hour
+    #[serde(with = "crate::serialization::litres")]
+    pub evaporation_rate: Volume,
+    /// wort left in the kettle with the trub and in the chiller
+    #[serde(with = "crate::serialization::litres")]
+    pub trub_chiller_loss: Volume,
+    /// wort left under the false bottom of the mash tun
+    #[serde(with = "crate::serialization::litres")]
+    pub mash_tun_dead_space: Volume,
+    /// beer left in the fermenter with the yeast cake
+    #[serde(with = "crate::serialization::litres")]
+    pub fermenter_loss: Volume,
+    /// water absorbed by the grain in litres per kilogram
+    pub grain_absorption: f64,
+    /// shrinkage of the wort when cooled from boiling in percentage
+    pub cooling_shrinkage: f64,
+}
+
+impl Default for EquipmentProfile {
+    fn default() -> Self {
+        Self {
+            name: String::from("Default"),
+            evaporation_rate: Volume::from_litres(4.),
+            trub_chiller_loss: Volume::from_litres(1.),
+            mash_tun_dead_space: Volume::from_litres(1.),
+            fermenter_loss: Volume::from_litres(1.),
+            grain_absorption: 1.,
+            cooling_shrinkage: 4.,
+        }
+    }
+}
+
+impl EquipmentProfile {
+    /// Checks that the losses are not negative and the cooling shrinkage is a
+    /// percentage below 100, describing the first invalid value otherwise
+    pub fn validate(&self) -> Result<(), String> {
+        let losses = [
+            ("evaporation_rate", self.evaporation_rate.as_litres()),
+            ("trub_chiller_loss", self.trub_chiller_loss.as_litres()),
+            ("mash_tun_dead_space", self.mash_tun_dead_space.as_litres()),
+            ("fermenter_loss", self.fermenter_loss.as_litres()),
+            ("grain_absorption", self.grain_absorption),
+        ];
+        for (name, value) in losses {
+            if value < 0. || value.is_nan() {
+                return Err(format!("{} must not be negative, got {}", name, value));
+            }
+        }
+        if !(0. ..100.).contains(&self.cooling_shrinkage) {
+            return Err(format!(
+                "cooling_shrinkage must be at least 0 and below 100%, got {}",
+                self.cooling_shrinkage
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::assert_approx;
+
+    #[test]
+    fn partial_profile() {
+        let equipment: EquipmentProfile =
+            serde_json::from_str(r#"{"name": "Kettle", "evaporation_rate": 3.5}"#).unwrap();
+        assert_eq!("Kettle", equipment.name);
+        assert_approx!(3.5, equipment.evaporation_rate.as_litres());
+        assert_approx!(1., equipment.fermenter_loss.as_litres());
+        assert_approx!(4., equipment.cooling_shrinkage);
+    }
+
+    #[test]
+    fn invalid_profiles() {
+        assert!(EquipmentProfile::default().validate().is_ok());
+        for shrinkage in [-1., 100., 150.] {
+            let equipment = EquipmentProfile {
+                cooling_shrinkage: shrinkage,
+                ..Default::default()
+            };
+            assert!(equipment.validate().is_err());
+        }
+        let equipment = EquipmentProfile {
+            evaporation_rate: Volume::from_litres(-4.),
+            ..Default::default()
+        };
+        assert_eq!(
+            Err(String::from(
+                "evaporation_rate must not be negative, got -4"
+            )),
+            equipment.validate()
+        );
+        let equipment = EquipmentProfile {
+            fermenter_loss: Volume::from_litres(-0.5),
+            ..Default::default()
+        };
+        assert!(equipment.validate().is_err());
+    }
+}
@@ -0,0 +1,223 @@
+use crate::conversions::RelativeDensity;
+use crate::strings::contains_case_insensitive;
+/// Fermentables list curated from https://www.brewersfriend.com/fermentables/
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Extract potential of sucrose in PPG, which is 100% extract
+pub const SUCROSE_PPG: f64 = 46.214;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum FermentableType {
+    /// Malted grain which needs to be mashed
+    #[default]
+    Grain,
+    /// Liquid malt extract
+    Extract,
+    /// Dry malt extract
+    #[serde(rename = "Dry Extract")]
+    DryExtract,
+    Sugar,
+    /// Unmalted grain which needs to be mashed with malt
+    Adjunct,
+}
+
+impl FermentableType {
+    /// Parses type from its name, for example "Dry Extract"
+    pub fn from_name(name: &str) -> Option<FermentableType> {
+        match name {
+            "Grain" => Some(FermentableType::Grain),
+            "Extract" => Some(FermentableType::Extract),
+            "Dry Extract" => Some(FermentableType::DryExtract),
+            "Sugar" => Some(FermentableType::Sugar),
+            "Adjunct" => Some(FermentableType::Adjunct),
+            _ => None,
+        }
+    }
+
+    /// Name of the type, reverse of `FermentableType::from_name`
+    pub fn name(&self) -> &'static str {
+        match self {
+            FermentableType::Grain => "Grain",
+            FermentableType::Extract => "Extract",
+            FermentableType::DryExtract => "Dry Extract",
+            FermentableType::Sugar => "Sugar",
+            FermentableType::Adjunct => "Adjunct",
+        }
+    }
+
+    /// Whether the fermentable is mashed and thus affected by the brewhouse efficiency
+    pub fn is_mashed(&self) -> bool {
+        matches!(self, FermentableType::Grain | FermentableType::Adjunct)
+    }
+}
+
+impl fmt::Display for FermentableType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Fermentable {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub fermentable_type: FermentableType,
+    /// potential extract in gravity points per pound per gallon
+    pub ppg: f64,
+    /// color in degrees Lovibond
+    pub color: f64,
+    /// diastatic power in degrees Lintner
+    pub diastatic_power: f64,
+    /// maximum recommended share of the grain bill in percentage
+    pub max_in_batch: f64,
+    pub country: String,
+    pub description: String,
+}
+
+impl Fermentable {
+    /// Potential extract as the gravity of 1 lb dissolved in 1 gal of water
+    pub fn potential(&self) -> RelativeDensity {
+        RelativeDensity::from_specific_gravity(1. + self.ppg / 1000.)
+    }
+
+    /// Potential extract as percentage of the extract of sucrose
+    pub fn extract_percentage(&self) -> f64 {
+        self.ppg / SUCROSE_PPG * 100.
+    }
+}
+
+static FERMENTABLES_JSON: &str = include_str!("json/fermentables.json");
+
+/// All available fermentables.
+///
+/// Data will be loaded from JSON on the first use.
+pub static FERMENTABLES: Lazy<Vec<Fermentable>> = Lazy::new(|| {
+    serde_json::from_str(FERMENTABLES_JSON).expect("fermentables data could not be deserialised")
+});
+
+/// Criteria for selecting a fermentable.
+///
+/// If an attribute is `None`, it is ignored.
+#[derive(Debug, Clone, Default)]
+pub struct Criteria {
+    pub name: Option<String>,
+    pub country: Option<String>,
+    pub fermentable_type: Option<FermentableType>,
+    /// fermentables with color (°L) of at most this
+    pub max_color: Option<f64>,
+    /// fermentables with diastatic power (°Lintner) of at least this
+    pub min_diastatic_power: Option<f64>,
+}
+
+impl Criteria {
+    /// Whether the given fermentable matches **all** criteria that are `Some`.
+    pub fn matches(&self, fermentable: &Fermentable) -> bool {
+        if let Some(name) = &self.name {
+            if !contains_case_insensitive(&fermentable.name, name) {
+                return false;
+            }
+        }
+        if let Some(country) = &self.country {
+            if !contains_case_insensitive(&fermentable.country, country) {
+                return false;
+            }
+        }
+        if let Some(fermentable_type) = self.fermentable_type {
+            if fermentable_type != fermentable.fermentable_type {
+                return false;
+            }
+        }
+        if let Some(max_color) = self.max_color {
+            if fermentable.color > max_color {
+                return false;
+            }
+        }
+        if let Some(min_diastatic_power) = self.min_diastatic_power {
+            if fermentable.diastatic_power < min_diastatic_power {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::assert_approx;
+
+    #[test]
+    fn maris_otter() {
+        let maris_otter = FERMENTABLES
+            .iter()
+            .find(|&f| f.name == "Maris Otter")
+            .unwrap();
+        assert_eq!(FermentableType::Grain, maris_otter.fermentable_type);
+        assert_approx!(1.038, maris_otter.potential().as_specific_gravity());
+        assert_approx!(82.2262, maris_otter.extract_percentage());
+    }
+
+    #[test]
+    fn all_types_present() {
+        for fermentable_type in [
+            FermentableType::Grain,
+            FermentableType::Extract,
+            FermentableType::DryExtract,
+            FermentableType::Sugar,
+            FermentableType::Adjunct,
+        ] {
+            assert!(FERMENTABLES
+                .iter()
+                .any(|f| f.fermentable_type == fermentable_type));
+            assert_eq!(
+                Some(fermentable_type),
+                FermentableType::from_name(fermentable_type.name())
+            );
+        }
+    }
+
+    static TEST_FERMENTABLE: Lazy<Fermentable> = Lazy::new(|| Fermentable {
+        name: "Test Malt".to_owned(),
+        fermentable_type: FermentableType::Grain,
+        ppg: 37.,
+        color: 3.5,
+        diastatic_power: 50.,
+        max_in_batch: 100.,
+        country: "UK".to_owned(),
+        description: "Test".to_owned(),
+    });
+
+    #[test]
+    fn no_criteria_matches() {
+        let criteria = Criteria::default();
+        assert!(criteria.matches(&TEST_FERMENTABLE));
+    }
+
+    #[test]
+    fn criteria_matches_inclusive() {
+        let mut criteria = Criteria {
+            max_color: Some(3.4),
+            ..Default::default()
+        };
+        assert!(!criteria.matches(&TEST_FERMENTABLE));
+        criteria.max_color = Some(3.5);
+        assert!(criteria.matches(&TEST_FERMENTABLE));
+
+        criteria.min_diastatic_power = Some(51.);
+        assert!(!criteria.matches(&TEST_FERMENTABLE));
+        criteria.min_diastatic_power = Some(50.);
+        assert!(criteria.matches(&TEST_FERMENTABLE));
+
+        criteria.fermentable_type = Some(FermentableType::Sugar);
+        assert!(!criteria.matches(&TEST_FERMENTABLE));
+        criteria.fermentable_type = Some(FermentableType::Grain);
+        assert!(criteria.matches(&TEST_FERMENTABLE));
+
+        criteria.name = Some("malt".to_owned());
+        assert!(criteria.matches(&TEST_FERMENTABLE));
+        criteria.country = Some("uk".to_owned());
+        assert!(criteria.matches(&TEST_FERMENTABLE));
+    }
+}
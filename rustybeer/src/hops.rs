@@ -55,12 +55,12 @@ impl Criteria {
     /// Whether the given hop matches **all** criteria that are `Some`.
     pub fn matches(&self, hop: &Hop) -> bool {
         if let Some(name) = &self.name {
-            if !contains_case_insensitive(&hop.name, &name) {
+            if !contains_case_insensitive(&hop.name, name) {
                 return false;
             }
         }
         if let Some(country) = &self.country {
-            if !contains_case_insensitive(&hop.country, &country) {
+            if !contains_case_insensitive(&hop.country, country) {
                 return false;
             }
         }
@@ -75,12 +75,12 @@ impl Criteria {
             }
         }
         if let Some(purpose) = &self.purpose {
-            if !hop.purpose.contains(&purpose) {
+            if !hop.purpose.contains(purpose) {
                 return false;
             }
         }
         if let Some(substituted) = &self.substituted {
-            if !hop.substitutions.contains(&substituted) {
+            if !hop.substitutions.contains(substituted) {
                 return false;
             }
         }
@@ -124,10 +124,11 @@ pub mod tests {
 
     #[test]
     fn criteria_matches_inclusive() {
-        let mut criteria = Criteria::default();
-
         // Out of range values fails
-        criteria.alpha_acid = Some(-0.1);
+        let mut criteria = Criteria {
+            alpha_acid: Some(-0.1),
+            ..Default::default()
+        };
         assert!(!criteria.matches(&TEST_HOP));
         criteria.alpha_acid = Some(60.1);
         assert!(!criteria.matches(&TEST_HOP));
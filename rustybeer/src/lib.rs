@@ -1,17 +1,35 @@
 //! Utilities for working with `rustybeer`:
 //! * List of beer styles that can be indexed and requested
 //! * List of hops and their AA%
+//! * List of fermentables and their potential and color
 //! * List of yeasts and their properties
+//! * Water profiles of classic brewing cities
+//! * List of priming sugars and their extract and fermentability
+//! * Equipment profiles with the losses of a brewing system
 //! * Units conversions from strings
+//! * Recipes that calculate their own OG, FG, ABV, IBU and color
+//! * Recipe designer solving the ingredient weights for a beer style
+//! * BeerXML import and export of recipes
+//! * BeerJSON import, export and validation of recipes
 
 pub mod abv_calories;
 pub mod beer_styles;
+pub mod beerjson;
+pub mod beerxml;
 pub mod calculators;
 pub mod conversions;
+pub mod designer;
+pub mod equipment;
+pub mod fermentables;
 pub mod hops;
+pub mod priming_sugars;
+pub mod recipe;
+pub mod water;
 pub mod yeasts;
 
 pub use measurements;
 
+mod least_squares;
 mod macros;
+mod serialization;
 mod strings;
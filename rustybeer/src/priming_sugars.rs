@@ -0,0 +1,65 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+/// Sugar used to prime beer at packaging time
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct PrimingSugar {
+    pub name: String,
+    /// fraction of the weight that is extract, 0.91 for corn sugar that is
+    /// dextrose monohydrate (42 of the 46 PPG of sucrose)
+    pub extract: f64,
+    /// fraction of the extract that is fermentable
+    pub fermentability: f64,
+}
+
+impl PrimingSugar {
+    pub fn new(name: String, extract: f64, fermentability: f64) -> Self {
+        Self {
+            name,
+            extract,
+            fermentability,
+        }
+    }
+
+    /// CO2 produced by the sugar compared to the same weight of sucrose
+    pub fn sucrose_equivalent(&self) -> f64 {
+        self.extract * self.fermentability
+    }
+}
+
+static PRIMING_SUGARS_JSON: &str = include_str!("json/priming_sugars.json");
+
+/// Common priming sugars.
+///
+/// Their sucrose equivalents are the factors of the priming sugar table this
+/// calculator was first written with, 0.68 for dry malt extract and 0.5 for
+/// the less fermentable Laaglander extract. The extract of the sugars is that
+/// of their dry matter, and the fermentability the rest of the factor, which
+/// is below 1 only for malt extracts and for syrups with unfermentable
+/// solids.
+///
+/// Data will be loaded from JSON on the first use.
+pub static PRIMING_SUGARS: Lazy<Vec<PrimingSugar>> = Lazy::new(|| {
+    serde_json::from_str(PRIMING_SUGARS_JSON).expect("priming sugar data could not be deserialised")
+});
+
+/// Finds a priming sugar by its name, ignoring case
+pub fn find_priming_sugar(name: &str) -> Option<&'static PrimingSugar> {
+    PRIMING_SUGARS
+        .iter()
+        .find(|sugar| sugar.name.eq_ignore_ascii_case(name))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::assert_approx;
+
+    #[test]
+    fn priming_sugars() {
+        assert_eq!(17, PRIMING_SUGARS.len());
+        let dme = find_priming_sugar("dme - all varieties").unwrap();
+        assert_approx!(0.68, dme.sucrose_equivalent());
+        assert!(find_priming_sugar("Unobtainium").is_none());
+    }
+}
@@ -0,0 +1,393 @@
+//! A beer recipe which ties the ingredients and process together and
+//! calculates its own vital statistics using the calculators.
+//!
+//! # Example
+//! ```
+//! use rustybeer::calculators::ibu::{HopAddition, HopAdditionType};
+//! use rustybeer::calculators::og::FermentableAddition;
+//! use rustybeer::conversions::RelativeDensity;
+//! use rustybeer::measurements::{Mass, Volume};
+//! use rustybeer::recipe::Recipe;
+//!
+//! let mut recipe = Recipe::new(
+//!     "Pale Ale".to_owned(),
+//!     Volume::from_litres(20.),
+//!     Volume::from_litres(25.),
+//!     0.72,
+//! );
+//! recipe.fermentables.push(FermentableAddition::new(
+//!     "Pale Malt (2 Row)".to_owned(),
+//!     Mass::from_kilograms(4.5),
+//!     RelativeDensity::from_specific_gravity(1.037),
+//!     2.,
+//! ));
+//! recipe.hops.push(HopAddition::new(
+//!     Mass::from_grams(28.),
+//!     0.064,
+//!     60,
+//!     HopAdditionType::Pellet,
+//! ));
+//!
+//! println!("OG: {:.3}", recipe.og().as_specific_gravity());
+//! println!("FG: {:.3}", recipe.fg().as_specific_gravity());
+//! println!("ABV: {:.1}%", recipe.abv());
+//! println!("IBU: {:.0}", recipe.ibu());
+//! println!("SRM: {:.1}", recipe.color());
+//! ```
+
+use crate::beer_styles::BeerStyle;
+use crate::calculators::abv::calculate_abv;
+use crate::calculators::color::{calculate_mcu, calculate_srm, ColorModel};
+use crate::calculators::diluting::calculate_new_gravity;
+use crate::calculators::fg::calculate_fg;
+use crate::calculators::ibu::{
+    calculate_ibu, calculate_utilization_ratio, HopAddition, HopUse, IbuModel,
+};
+use crate::calculators::mash::MashStep;
+use crate::calculators::og::{calculate_og, FermentableAddition};
+use crate::conversions::RelativeDensity;
+use crate::water::WaterProfile;
+use crate::yeasts::Yeast;
+use measurements::{Mass, Volume};
+use serde::{Deserialize, Serialize};
+
+/// Apparent attenuation used when the recipe has no yeast or the yeast
+/// attenuation is unknown.
+pub const DEFAULT_ATTENUATION: u8 = 75;
+
+/// Boil time used for recipes which don't define one.
+pub const DEFAULT_BOIL_TIME: u32 = 60;
+
+fn default_boil_time() -> u32 {
+    DEFAULT_BOIL_TIME
+}
+
+/// Sugar added to carbonate the beer at packaging
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrimingAddition {
+    /// name of the priming sugar
+    pub name: String,
+    /// weight of the sugar for the whole batch, serialized in grams
+    #[serde(with = "crate::serialization::grams")]
+    pub weight: Mass,
+}
+
+/// A beer recipe
+///
+/// Volumes are serialized in litres, weights in grams and temperatures in Celsius.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Recipe {
+    /// name of the recipe
+    pub name: String,
+    /// volume of wort into the fermenter
+    #[serde(with = "crate::serialization::litres")]
+    pub batch_size: Volume,
+    /// volume of wort at the start of the boil
+    #[serde(with = "crate::serialization::litres")]
+    pub boil_size: Volume,
+    /// boil time (min)
+    #[serde(default = "default_boil_time")]
+    pub boil_time_mins: u32,
+    /// brewhouse efficiency as decimal (0.72 for 72%)
+    pub efficiency: f64,
+    #[serde(default)]
+    pub fermentables: Vec<FermentableAddition>,
+    #[serde(default)]
+    pub hops: Vec<HopAddition>,
+    #[serde(default)]
+    pub yeast: Option<Yeast>,
+    #[serde(default)]
+    pub mash_steps: Vec<MashStep>,
+    #[serde(default)]
+    pub water: Option<WaterProfile>,
+    #[serde(default)]
+    pub style: Option<BeerStyle>,
+    #[serde(default)]
+    pub priming: Option<PrimingAddition>,
+}
+
+impl Recipe {
+    /// Creates a recipe without any ingredients
+    pub fn new(name: String, batch_size: Volume, boil_size: Volume, efficiency: f64) -> Self {
+        Self {
+            name,
+            batch_size,
+            boil_size,
+            boil_time_mins: DEFAULT_BOIL_TIME,
+            efficiency,
+            fermentables: Vec::new(),
+            hops: Vec::new(),
+            yeast: None,
+            mash_steps: Vec::new(),
+            water: None,
+            style: None,
+            priming: None,
+        }
+    }
+
+    /// Original gravity of the wort in the fermenter
+    pub fn og(&self) -> RelativeDensity {
+        calculate_og(&self.fermentables, &self.batch_size, self.efficiency)
+    }
+
+    /// Average gravity of the wort during the boil
+    pub fn boil_gravity(&self) -> RelativeDensity {
+        calculate_new_gravity(&self.og(), &self.batch_size, &self.boil_size)
+    }
+
+    /// Attenuation of the recipe yeast or `DEFAULT_ATTENUATION` if unknown
+    pub fn attenuation(&self) -> u8 {
+        self.yeast
+            .as_ref()
+            .and_then(|y| y.average_attenuation())
+            .unwrap_or(DEFAULT_ATTENUATION)
+    }
+
+    /// Final gravity estimated from the yeast attenuation
+    pub fn fg(&self) -> RelativeDensity {
+        calculate_fg(&self.og(), self.attenuation())
+    }
+
+    /// Alcohol by volume in percentage
+    pub fn abv(&self) -> f64 {
+        calculate_abv(&self.og(), &self.fg())
+    }
+
+    /// Bitterness of the beer in IBU
+    pub fn ibu(&self) -> f64 {
+        calculate_ibu(
+            self.hops.clone(),
+            &self.batch_size,
+            &self.boil_gravity(),
+            &IbuModel::Tinseth,
+        )
+    }
+
+    /// Color of the beer in SRM using the Morey equation
+    pub fn color(&self) -> f64 {
+        let grains: Vec<(Mass, f64)> = self
+            .fermentables
+            .iter()
+            .map(|f| (f.weight, f.color))
+            .collect();
+        calculate_srm(calculate_mcu(&grains, &self.batch_size), ColorModel::Morey)
+    }
+
+    /// Scales the recipe to a new batch size, boil size and efficiency, `None`
+    /// if a volume or efficiency of either recipe isn't greater than zero
+    ///
+    /// Mashed fermentables are adjusted for the efficiency and extracts and
+    /// sugars for the volume only, so the OG stays the same. Boiled and
+    /// whirlpool hops are adjusted for the change of the Tinseth utilization
+    /// with the boil gravity to keep the IBU, while mash and dry hops and the
+    /// priming sugar are scaled with the volume.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch_size`: new volume of wort into the fermenter
+    /// * `boil_size`: new volume of wort at the start of the boil
+    /// * `efficiency`: new brewhouse efficiency as decimal (0.72 for 72%)
+    pub fn scale(&self, batch_size: &Volume, boil_size: &Volume, efficiency: f64) -> Option<Self> {
+        let positive = [
+            batch_size.as_litres(),
+            boil_size.as_litres(),
+            efficiency,
+            self.batch_size.as_litres(),
+            self.boil_size.as_litres(),
+            self.efficiency,
+        ]
+        .iter()
+        .all(|value| *value > 0.);
+        if !positive {
+            return None;
+        }
+
+        let volume_ratio = batch_size.as_litres() / self.batch_size.as_litres();
+        let efficiency_ratio = self.efficiency / efficiency;
+
+        let mut scaled = Self {
+            batch_size: *batch_size,
+            boil_size: *boil_size,
+            efficiency,
+            ..self.clone()
+        };
+        for fermentable in scaled.fermentables.iter_mut() {
+            fermentable.weight = match fermentable.fermentable_type.is_mashed() {
+                true => fermentable.weight * volume_ratio * efficiency_ratio,
+                false => fermentable.weight * volume_ratio,
+            };
+        }
+
+        let utilization_ratio =
+            calculate_utilization_ratio(&self.boil_gravity(), &scaled.boil_gravity());
+        for hop in scaled.hops.iter_mut() {
+            hop.weight = match hop.hop_use {
+                HopUse::Mash { .. } | HopUse::DryHop { .. } => hop.weight * volume_ratio,
+                _ => hop.weight * volume_ratio * utilization_ratio,
+            };
+        }
+        if let Some(priming) = scaled.priming.as_mut() {
+            priming.weight = priming.weight * volume_ratio;
+        }
+        Some(scaled)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::assert_approx;
+    use crate::calculators::ibu::HopAdditionType;
+    use crate::fermentables::FermentableType;
+    use measurements::Temperature;
+
+    fn pale_ale() -> Recipe {
+        let mut recipe = Recipe::new(
+            "Pale Ale".to_owned(),
+            Volume::from_litres(20.),
+            Volume::from_litres(25.),
+            0.72,
+        );
+        recipe.fermentables = vec![
+            FermentableAddition::new(
+                "Pale Malt (2 Row)".to_owned(),
+                Mass::from_kilograms(4.5),
+                RelativeDensity::from_specific_gravity(1.037),
+                2.,
+            ),
+            FermentableAddition::new(
+                "Caramel/Crystal Malt - 40L".to_owned(),
+                Mass::from_kilograms(0.5),
+                RelativeDensity::from_specific_gravity(1.034),
+                40.,
+            ),
+        ];
+        recipe.hops = vec![
+            HopAddition::new(Mass::from_grams(28.), 0.064, 60, HopAdditionType::Pellet),
+            HopAddition::new(Mass::from_grams(28.), 0.064, 15, HopAdditionType::Pellet),
+        ];
+        recipe.mash_steps = vec![MashStep::new(
+            "Saccharification".to_owned(),
+            Temperature::from_celsius(66.),
+            60,
+        )];
+        recipe
+    }
+
+    #[test]
+    fn og() {
+        assert_approx!(1.0551, pale_ale().og().as_specific_gravity());
+    }
+
+    #[test]
+    fn fg() {
+        assert_approx!(1.0138, pale_ale().fg().as_specific_gravity());
+
+        let mut recipe = pale_ale();
+        recipe.yeast = Some(Yeast {
+            min_attenuation: Some(70),
+            max_attenuation: Some(80),
+            ..Default::default()
+        });
+        assert_approx!(1.0138, recipe.fg().as_specific_gravity());
+        recipe.yeast.as_mut().unwrap().max_attenuation = Some(90);
+        assert_approx!(1.0110, recipe.fg().as_specific_gravity());
+    }
+
+    #[test]
+    fn abv() {
+        assert_approx!(5.4268, pale_ale().abv());
+    }
+
+    #[test]
+    fn ibu() {
+        assert_approx!(35.8661, pale_ale().ibu());
+        assert_approx!(
+            0.,
+            Recipe::new(
+                "Empty".to_owned(),
+                Volume::from_litres(20.),
+                Volume::from_litres(25.),
+                0.72
+            )
+            .ibu()
+        );
+    }
+
+    #[test]
+    fn color() {
+        assert_approx!(8.2514, pale_ale().color());
+    }
+
+    #[test]
+    fn scale() {
+        let mut recipe = pale_ale();
+        recipe.hops.push(HopAddition::dry_hop(
+            Mass::from_grams(50.),
+            0.064,
+            4,
+            HopAdditionType::Pellet,
+        ));
+        recipe.priming = Some(PrimingAddition {
+            name: "Table Sugar".to_owned(),
+            weight: Mass::from_grams(120.),
+        });
+
+        let scaled = recipe
+            .scale(&Volume::from_litres(200.), &Volume::from_litres(230.), 0.65)
+            .unwrap();
+        assert_approx!(200., scaled.batch_size.as_litres());
+        assert_approx!(0.65, scaled.efficiency);
+        assert_approx!(49.8462, scaled.fermentables[0].weight.as_kilograms());
+        assert_approx!(5.5385, scaled.fermentables[1].weight.as_kilograms());
+        assert_approx!(289.819, scaled.hops[0].weight.as_grams());
+        assert_approx!(500., scaled.hops[2].weight.as_grams());
+        assert_approx!(1200., scaled.priming.as_ref().unwrap().weight.as_grams());
+        assert_approx!(
+            recipe.og().as_specific_gravity(),
+            scaled.og().as_specific_gravity()
+        );
+        assert_approx!(recipe.ibu(), scaled.ibu());
+
+        // Extracts only depend on the volume
+        let mut fermentable = recipe.fermentables[1].clone();
+        fermentable.fermentable_type = FermentableType::DryExtract;
+        recipe.fermentables[1] = fermentable;
+        let scaled = recipe
+            .scale(&Volume::from_litres(10.), &Volume::from_litres(12.5), 0.8)
+            .unwrap();
+        assert_approx!(2.025, scaled.fermentables[0].weight.as_kilograms());
+        assert_approx!(0.25, scaled.fermentables[1].weight.as_kilograms());
+        assert_approx!(
+            recipe.og().as_specific_gravity(),
+            scaled.og().as_specific_gravity()
+        );
+        assert_approx!(recipe.ibu(), scaled.ibu());
+
+        let (batch_size, boil_size) = (Volume::from_litres(10.), Volume::from_litres(12.5));
+        assert!(recipe.scale(&batch_size, &boil_size, 0.).is_none());
+        assert!(recipe
+            .scale(&Volume::from_litres(0.), &boil_size, 0.8)
+            .is_none());
+        assert!(recipe
+            .scale(&batch_size, &Volume::from_litres(-1.), 0.8)
+            .is_none());
+    }
+
+    #[test]
+    fn serialization() {
+        let recipe = pale_ale();
+        let json = serde_json::to_string(&recipe).unwrap();
+        let deserialized: Recipe = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(recipe.name, deserialized.name);
+        assert_approx!(20., deserialized.batch_size.as_litres());
+        assert_approx!(4500., deserialized.fermentables[0].weight.as_grams());
+        assert_approx!(66., deserialized.mash_steps[0].temperature.as_celsius());
+        assert_approx!(
+            recipe.og().as_specific_gravity(),
+            deserialized.og().as_specific_gravity()
+        );
+        assert_approx!(recipe.ibu(), deserialized.ibu());
+    }
+}
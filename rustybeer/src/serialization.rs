@@ -0,0 +1,45 @@
+//! Serde helpers for `measurements` units, which don't implement serde themselves.
+//!
+//! Each module (de)serializes the unit as a plain number in the unit the module is
+//! named after and can be used with `#[serde(with = "...")]`.
+
+pub mod grams {
+    use measurements::Mass;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(mass: &Mass, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(mass.as_grams())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Mass, D::Error> {
+        Ok(Mass::from_grams(f64::deserialize(deserializer)?))
+    }
+}
+
+pub mod litres {
+    use measurements::Volume;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(volume: &Volume, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(volume.as_litres())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Volume, D::Error> {
+        Ok(Volume::from_litres(f64::deserialize(deserializer)?))
+    }
+}
+
+pub mod celsius {
+    use measurements::Temperature;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(temp: &Temperature, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(temp.as_celsius())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Temperature, D::Error> {
+        Ok(Temperature::from_celsius(f64::deserialize(deserializer)?))
+    }
+}
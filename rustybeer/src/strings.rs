@@ -1,4 +1,4 @@
-pub fn contains_case_insensitive(checked: &String, contain: &String) -> bool {
+pub fn contains_case_insensitive(checked: &str, contain: &str) -> bool {
     checked
         .to_ascii_lowercase()
         .contains(&contain.to_ascii_lowercase())
@@ -11,8 +11,8 @@ mod tests {
     #[test]
     fn test_contains_case_insensitive() {
         assert!(contains_case_insensitive(
-            &"DoIHaveTheSubString".to_owned(),
-            &"substring".to_owned()
+            "DoIHaveTheSubString",
+            "substring"
         ));
     }
 }
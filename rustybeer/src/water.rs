@@ -0,0 +1,132 @@
+/// Classic brewing city water profiles curated from How to Brew by John Palmer
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Brewing water ion concentrations in ppm (mg/l)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WaterProfile {
+    pub name: String,
+    pub calcium: f64,
+    pub magnesium: f64,
+    pub sodium: f64,
+    pub chloride: f64,
+    pub sulfate: f64,
+    pub bicarbonate: f64,
+}
+
+impl WaterProfile {
+    /// Ratio of sulfate to chloride, infinite if there is sulfate but no chloride
+    pub fn sulfate_chloride_ratio(&self) -> f64 {
+        match self.chloride > 0. {
+            true => self.sulfate / self.chloride,
+            false if self.sulfate > 0. => f64::INFINITY,
+            false => 0.,
+        }
+    }
+
+    /// Alkalinity as CaCO3 (ppm)
+    pub fn alkalinity(&self) -> f64 {
+        self.bicarbonate * 50. / 61.017
+    }
+
+    /// Residual alkalinity as CaCO3 (ppm), the alkalinity left after
+    /// calcium and magnesium have reacted with the malt phosphates
+    pub fn residual_alkalinity(&self) -> f64 {
+        self.alkalinity() - self.calcium / 1.4 - self.magnesium / 1.7
+    }
+}
+
+impl FromStr for WaterProfile {
+    type Err = String;
+
+    /// Parses a water profile from a profile name or from comma separated
+    /// ppm of Ca, Mg, Na, Cl, SO4 and HCO3
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(water) = find_water_profile(s) {
+            return Ok(water.clone());
+        }
+
+        let ions = s
+            .split(',')
+            .map(|ion| ion.trim().parse::<f64>())
+            .collect::<Result<Vec<f64>, _>>()
+            .map_err(|_| format!("unknown water profile {}", s))?;
+        match ions[..] {
+            [calcium, magnesium, sodium, chloride, sulfate, bicarbonate] => Ok(WaterProfile {
+                name: "Custom".to_owned(),
+                calcium,
+                magnesium,
+                sodium,
+                chloride,
+                sulfate,
+                bicarbonate,
+            }),
+            _ => Err(format!(
+                "expected 6 comma separated ion concentrations, got {}",
+                s
+            )),
+        }
+    }
+}
+
+static WATER_PROFILES_JSON: &str = include_str!("json/water_profiles.json");
+
+/// Water profiles of classic brewing cities and distilled water.
+///
+/// Data will be loaded from JSON on the first use.
+pub static WATER_PROFILES: Lazy<Vec<WaterProfile>> = Lazy::new(|| {
+    serde_json::from_str(WATER_PROFILES_JSON).expect("water profile data could not be deserialised")
+});
+
+/// Finds a water profile by its name, ignoring case
+pub fn find_water_profile(name: &str) -> Option<&'static WaterProfile> {
+    WATER_PROFILES
+        .iter()
+        .find(|profile| profile.name.eq_ignore_ascii_case(name))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::assert_approx;
+
+    #[test]
+    fn burton() {
+        let burton = find_water_profile("burton").unwrap();
+        assert_approx!(295., burton.calcium);
+        assert_approx!(29., burton.sulfate_chloride_ratio());
+        assert!(find_water_profile("Atlantis").is_none());
+    }
+
+    #[test]
+    fn sulfate_chloride_ratio() {
+        let mut water = WaterProfile::default();
+        assert_approx!(0., water.sulfate_chloride_ratio());
+        water.sulfate = 50.;
+        assert!(water.sulfate_chloride_ratio().is_infinite());
+        water.chloride = 100.;
+        assert_approx!(0.5, water.sulfate_chloride_ratio());
+    }
+
+    #[test]
+    fn residual_alkalinity() {
+        let dublin = find_water_profile("Dublin").unwrap();
+        assert_approx!(261.4026, dublin.alkalinity());
+        assert_approx!(174.7639, dublin.residual_alkalinity());
+        assert_approx!(0., WaterProfile::default().residual_alkalinity());
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!(
+            Ok(find_water_profile("Munich").unwrap().clone()),
+            "munich".parse()
+        );
+        let custom: WaterProfile = "50, 10, 15, 75, 150, 0".parse().unwrap();
+        assert_eq!("Custom", custom.name);
+        assert_approx!(150., custom.sulfate);
+        assert!("1,2,3".parse::<WaterProfile>().is_err());
+        assert!("Atlantis".parse::<WaterProfile>().is_err());
+    }
+}
@@ -3,10 +3,11 @@ use crate::strings::contains_case_insensitive;
 /// Yeast list curated from https://www.brewersfriend.com/yeast/
 use measurements::temperature::Temperature;
 use once_cell::sync::Lazy;
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
+use std::str::FromStr;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Level {
     Low,
     MedLow,
@@ -16,62 +17,183 @@ pub enum Level {
     VeryHigh,
 }
 
+impl Level {
+    /// Parses level from its name, for example "Med-Low"
+    pub fn from_name(name: &str) -> Option<Level> {
+        match name {
+            "Low" => Some(Level::Low),
+            "Med-Low" => Some(Level::MedLow),
+            "Medium" => Some(Level::Medium),
+            "Med-High" => Some(Level::MedHigh),
+            "High" => Some(Level::High),
+            "Very High" => Some(Level::VeryHigh),
+            _ => None,
+        }
+    }
+
+    /// Name of the level, reverse of `Level::from_name`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Level::Low => "Low",
+            Level::MedLow => "Med-Low",
+            Level::Medium => "Medium",
+            Level::MedHigh => "Med-High",
+            Level::High => "High",
+            Level::VeryHigh => "Very High",
+        }
+    }
+}
+
 impl fmt::Display for Level {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{:?}", self)
     }
 }
 
-#[derive(Debug, Default, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize)]
+pub enum YeastForm {
+    Dry,
+    #[default]
+    Liquid,
+    /// Yeast harvested from a previous fermentation
+    Slurry,
+}
+
+impl YeastForm {
+    /// Parses form from its name, for example "Dry"
+    pub fn from_name(name: &str) -> Option<YeastForm> {
+        match name {
+            "Dry" => Some(YeastForm::Dry),
+            "Liquid" => Some(YeastForm::Liquid),
+            "Slurry" => Some(YeastForm::Slurry),
+            _ => None,
+        }
+    }
+
+    /// Name of the form, reverse of `YeastForm::from_name`
+    pub fn name(&self) -> &'static str {
+        match self {
+            YeastForm::Dry => "Dry",
+            YeastForm::Liquid => "Liquid",
+            YeastForm::Slurry => "Slurry",
+        }
+    }
+}
+
+impl fmt::Display for YeastForm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for YeastForm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "dry" => Ok(YeastForm::Dry),
+            "liquid" => Ok(YeastForm::Liquid),
+            "slurry" => Ok(YeastForm::Slurry),
+            _ => Err(format!("unknown yeast form {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
 pub struct Yeast {
     pub company: String,
     pub name: String,
     pub id: Option<String>,
+    #[serde(default)]
+    pub form: YeastForm,
     pub min_attenuation: Option<u8>,
     pub max_attenuation: Option<u8>,
-    #[serde(deserialize_with = "level_from_str")]
+    #[serde(
+        default,
+        deserialize_with = "level_from_str",
+        serialize_with = "level_to_str"
+    )]
     pub attenuation_level: Option<Level>,
-    #[serde(deserialize_with = "level_from_str")]
+    #[serde(
+        default,
+        deserialize_with = "level_from_str",
+        serialize_with = "level_to_str"
+    )]
     pub flocculation: Option<Level>,
-    #[serde(deserialize_with = "temp_from_str")]
+    #[serde(
+        default,
+        deserialize_with = "temp_from_str",
+        serialize_with = "temp_to_fahrenheit"
+    )]
     pub min_temp: Option<Temperature>,
-    #[serde(deserialize_with = "temp_from_str")]
+    #[serde(
+        default,
+        deserialize_with = "temp_from_str",
+        serialize_with = "temp_to_fahrenheit"
+    )]
     pub max_temp: Option<Temperature>,
     pub alc_tolerance: Option<u8>,
-    #[serde(deserialize_with = "level_from_str")]
+    #[serde(
+        default,
+        deserialize_with = "level_from_str",
+        serialize_with = "level_to_str"
+    )]
     pub alc_tolerance_level: Option<Level>,
 }
 
+impl Yeast {
+    /// Returns average attenuation of the yeast, if known.
+    pub fn average_attenuation(&self) -> Option<u8> {
+        match (self.min_attenuation, self.max_attenuation) {
+            (Some(min), Some(max)) => Some(((min as u16 + max as u16) / 2) as u8),
+            (Some(att), None) | (None, Some(att)) => Some(att),
+            (None, None) => None,
+        }
+    }
+}
+
 fn level_from_str<'de, D>(deserializer: D) -> Result<Option<Level>, D::Error>
 where
     D: Deserializer<'de>,
 {
     let m = <&str>::deserialize(deserializer);
     match m {
-        Ok(s) => match s {
-            "Low" => Ok(Some(Level::Low)),
-            "Med-Low" => Ok(Some(Level::MedLow)),
-            "Medium" => Ok(Some(Level::Medium)),
-            "Med-High" => Ok(Some(Level::MedHigh)),
-            "High" => Ok(Some(Level::High)),
-            "Very High" => Ok(Some(Level::VeryHigh)),
-            _ => Ok(None),
-        },
+        Ok(s) => Ok(Level::from_name(s)),
         Err(_) => Ok(None),
     }
 }
 
+fn level_to_str<S>(level: &Option<Level>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match level {
+        Some(l) => serializer.serialize_str(l.name()),
+        None => serializer.serialize_none(),
+    }
+}
+
 fn temp_from_str<'de, D>(deserializer: D) -> Result<Option<Temperature>, D::Error>
 where
     D: Deserializer<'de>,
 {
-    let t = u8::deserialize(deserializer);
+    let t = f64::deserialize(deserializer);
     match t {
-        Ok(v) => Ok(Some(Temperature::from_fahrenheit(v as f64))),
+        Ok(v) => Ok(Some(Temperature::from_fahrenheit(v))),
         Err(_) => Ok(None),
     }
 }
 
+fn temp_to_fahrenheit<S>(temp: &Option<Temperature>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match temp {
+        Some(t) => serializer.serialize_f64(t.as_fahrenheit()),
+        None => serializer.serialize_none(),
+    }
+}
+
 static YEASTS_JSON: &str = include_str!("json/yeasts.json");
 
 /// All available yeasts.
@@ -95,13 +217,13 @@ impl Criteria {
     /// Whether the given yeast matches **all** criteria that are `Some`.
     pub fn matches(&self, yeast: &Yeast) -> bool {
         if let Some(company) = &self.company {
-            if !contains_case_insensitive(&yeast.company, &company) {
+            if !contains_case_insensitive(&yeast.company, company) {
                 return false;
             }
         }
 
         if let Some(name) = &self.name {
-            if !contains_case_insensitive(&yeast.name, &name) {
+            if !contains_case_insensitive(&yeast.name, name) {
                 return false;
             }
         }
@@ -141,6 +263,7 @@ mod tests {
         alc_tolerance_level: None,
         attenuation_level: None,
         id: None,
+        form: YeastForm::Liquid,
         flocculation: None,
     });
 
@@ -171,4 +294,17 @@ mod tests {
         criteria.name = Some("66".to_owned());
         assert!(criteria.matches(&TEST_YEAST));
     }
+
+    #[test]
+    fn forms() {
+        let nottingham = YEASTS
+            .iter()
+            .find(|y| y.company == "Lallemand" && y.name == "Nottingham Ale Yeast")
+            .unwrap();
+        assert_eq!(YeastForm::Dry, nottingham.form);
+        assert!(YEASTS.iter().any(|y| y.form == YeastForm::Liquid));
+        for form in [YeastForm::Dry, YeastForm::Liquid, YeastForm::Slurry] {
+            assert_eq!(Some(form), YeastForm::from_name(form.name()));
+        }
+    }
 }